@@ -0,0 +1,884 @@
+//! # Terminal Art
+//!
+//! Multi-line renderings of cards, hands, and dominos for terminal games. The one-line
+//! `Display` impls elsewhere in the crate stay the compact default; the functions here
+//! draw box-framed cards with rank/suit corners and pip layouts, card backs for face-down
+//! cards, overlapping and fanned hands that fit a target width, and domino tiles with pip
+//! grids in either orientation.
+//!
+//! Everything renders to a plain `String` of newline-separated rows. [`ArtStyle`] picks
+//! between Unicode box drawing and a pure-ASCII fallback, and optionally wraps colored
+//! glyphs (red suits, Uno colors, card backs, domino pips) in ANSI escape codes.
+//!
+//! Any card face can be drawn by implementing [`CardArt`]; the default methods wrap the
+//! face's `display_front` text inside the frame. [`StandardCard`] and [`UnoCard`] provide
+//! richer faces out of the box.
+//!
+//! # Example
+//! ```
+//! use gametools::Card;
+//! use gametools::art::{ArtStyle, card_art};
+//! use gametools::cards::{Rank, StandardCard, Suit};
+//!
+//! let card = Card::new_card(StandardCard::new_card(Rank::Five, Suit::Hearts));
+//! let art = card_art(&card, &ArtStyle::ascii());
+//! assert_eq!(
+//!     art,
+//!     "+---------+\n\
+//!      |5H       |\n\
+//!      |  H   H  |\n\
+//!      |         |\n\
+//!      |    H    |\n\
+//!      |         |\n\
+//!      |  H   H  |\n\
+//!      |       5H|\n\
+//!      +---------+"
+//! );
+//! ```
+use crate::cards::uno_cards::{UnoAction, UnoCard, UnoCardKind, UnoColor};
+use crate::cards::{Card, CardFaces, Hand, Rank, StandardCard, Suit};
+use crate::dominos::{Domino, Train};
+
+/// Width in columns of a rendered card, frame included.
+pub const CARD_WIDTH: usize = 11;
+/// Height in rows of a rendered card, frame included.
+pub const CARD_HEIGHT: usize = 9;
+/// Columns available inside the card frame.
+pub const CARD_INNER_WIDTH: usize = CARD_WIDTH - 2;
+/// Rows available for the card body, between the two corner rows.
+pub const CARD_BODY_HEIGHT: usize = CARD_HEIGHT - 4;
+
+/// Columns a card must keep visible when overlapped so its corner stays readable.
+const MIN_REVEAL: usize = 4;
+/// Rows the outer cards of a fanned hand drop below the middle card.
+const FAN_DEPTH: usize = 2;
+/// Columns inside one half of a domino tile.
+const HALF_WIDTH: usize = 7;
+/// Rows inside one half of a domino tile.
+const HALF_HEIGHT: usize = 3;
+/// Pip colors by number when color is on, cycling for sets past double-six; blanks have none.
+const PIP_COLORS: [AnsiColor; 6] = [
+    AnsiColor::Cyan,
+    AnsiColor::Green,
+    AnsiColor::Red,
+    AnsiColor::Yellow,
+    AnsiColor::Blue,
+    AnsiColor::Magenta,
+];
+
+/// Foreground colors available through ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+impl AnsiColor {
+    /// The SGR foreground code for this color.
+    #[must_use]
+    pub fn code(self) -> u8 {
+        match self {
+            AnsiColor::Black => 30,
+            AnsiColor::Red => 31,
+            AnsiColor::Green => 32,
+            AnsiColor::Yellow => 33,
+            AnsiColor::Blue => 34,
+            AnsiColor::Magenta => 35,
+            AnsiColor::Cyan => 36,
+            AnsiColor::White => 37,
+        }
+    }
+}
+
+/// Characters used for frames, pips, and card backs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// Box-drawing frames, suit symbols, and `●` pips.
+    #[default]
+    Unicode,
+    /// `+`, `-`, `|` frames, suit letters, and `o` pips for terminals without Unicode.
+    Ascii,
+}
+
+/// Rendering options shared by all of the art functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtStyle {
+    /// Character set for frames and symbols.
+    pub charset: Charset,
+    /// Whether colored glyphs are wrapped in ANSI escape codes.
+    pub color: bool,
+    /// Color of the pattern on card backs when `color` is on.
+    pub back_color: AnsiColor,
+}
+impl Default for ArtStyle {
+    fn default() -> Self {
+        Self {
+            charset: Charset::Unicode,
+            color: false,
+            back_color: AnsiColor::Blue,
+        }
+    }
+}
+impl ArtStyle {
+    /// Unicode frames and symbols without color.
+    #[must_use]
+    pub fn unicode() -> Self {
+        Self::default()
+    }
+
+    /// Plain ASCII frames and letters without color.
+    #[must_use]
+    pub fn ascii() -> Self {
+        Self {
+            charset: Charset::Ascii,
+            ..Self::default()
+        }
+    }
+
+    /// Turn ANSI coloring on or off.
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// How the cards of a hand are arranged side by side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HandLayout {
+    /// Full cards separated by a space, wrapping onto more rows when too wide.
+    Spread,
+    /// Cards overlap just enough to fit the width, keeping each corner visible.
+    #[default]
+    Overlap,
+    /// Overlapping cards along a shallow arc, outer cards lower than the middle.
+    Fan,
+}
+
+/// Which way a domino tile is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Left half beside right half.
+    #[default]
+    Horizontal,
+    /// Left half above right half.
+    Vertical,
+}
+
+/// Describes how a card face is drawn inside the card frame.
+///
+/// Only [`CardFaces`] is required; the defaults wrap `display_front` across the body and
+/// leave the corners blank.
+pub trait CardArt: CardFaces {
+    /// Up to three characters printed in the top-left and bottom-right corners.
+    fn art_corner(&self, _charset: Charset) -> String {
+        String::new()
+    }
+
+    /// The [`CARD_BODY_HEIGHT`] rows drawn between the corners. Rows longer than
+    /// [`CARD_INNER_WIDTH`] are cut off and shorter ones are padded.
+    fn art_body(&self, _charset: Charset) -> Vec<String> {
+        wrap_centered(&self.display_front(), CARD_INNER_WIDTH, CARD_BODY_HEIGHT)
+    }
+
+    /// Color applied to the corners and body when ANSI color is enabled.
+    fn art_color(&self) -> Option<AnsiColor> {
+        None
+    }
+}
+
+impl CardArt for StandardCard {
+    fn art_corner(&self, charset: Charset) -> String {
+        if self.rank == Rank::Joker {
+            return "*".to_string();
+        }
+        format!("{}{}", self.rank, suit_symbol(self.suit, charset))
+    }
+
+    fn art_body(&self, charset: Charset) -> Vec<String> {
+        let pip = suit_symbol(self.suit, charset);
+        let mut grid = [[false; 3]; CARD_BODY_HEIGHT];
+        let mut label = None;
+        match self.rank {
+            Rank::Joker => label = Some("JOKER"),
+            Rank::Jack | Rank::Queen | Rank::King => {
+                grid[0][1] = true;
+                grid[4][1] = true;
+                label = Some(match self.rank {
+                    Rank::Jack => "JACK",
+                    Rank::Queen => "QUEEN",
+                    _ => "KING",
+                });
+            }
+            rank => {
+                for &(row, col) in standard_pips(rank) {
+                    grid[row][col] = true;
+                }
+            }
+        }
+        grid.iter()
+            .enumerate()
+            .map(|(row, cols)| match label {
+                Some(text) if row == CARD_BODY_HEIGHT / 2 => center(text, CARD_INNER_WIDTH),
+                _ => {
+                    let [a, b, c] = cols.map(|on| if on { pip } else { ' ' });
+                    format!("  {a} {b} {c}  ")
+                }
+            })
+            .collect()
+    }
+
+    fn art_color(&self) -> Option<AnsiColor> {
+        match self.suit {
+            Suit::Hearts | Suit::Diamonds => Some(AnsiColor::Red),
+            Suit::Wild => Some(AnsiColor::Magenta),
+            Suit::Clubs | Suit::Spades => None,
+        }
+    }
+}
+
+impl CardArt for UnoCard {
+    fn art_corner(&self, _charset: Charset) -> String {
+        match self.kind {
+            UnoCardKind::Number(number) => number.to_string(),
+            UnoCardKind::Action(action) => match action {
+                UnoAction::DrawTwo => "+2",
+                UnoAction::Skip => "S",
+                UnoAction::Reverse => "R",
+                UnoAction::DrawOne => "+1",
+                UnoAction::DrawFive => "+5",
+                UnoAction::SkipEveryone => "SA",
+                UnoAction::Flip => "F",
+            }
+            .to_string(),
+            UnoCardKind::Wild => "W".to_string(),
+            UnoCardKind::WildDrawFour => "+4".to_string(),
+            UnoCardKind::WildDrawTwo => "W+2".to_string(),
+            UnoCardKind::WildDrawColor => "W+C".to_string(),
+            UnoCardKind::WildShuffleHands => "WS".to_string(),
+        }
+    }
+
+    fn art_body(&self, _charset: Charset) -> Vec<String> {
+        let text = format!("{} {}", self.color, self.kind);
+        wrap_centered(&text, CARD_INNER_WIDTH, CARD_BODY_HEIGHT)
+    }
+
+    fn art_color(&self) -> Option<AnsiColor> {
+        match self.color {
+            UnoColor::Red => Some(AnsiColor::Red),
+            UnoColor::Blue => Some(AnsiColor::Blue),
+            UnoColor::Green => Some(AnsiColor::Green),
+            UnoColor::Yellow | UnoColor::Orange => Some(AnsiColor::Yellow),
+            UnoColor::Pink | UnoColor::Purple => Some(AnsiColor::Magenta),
+            UnoColor::Teal => Some(AnsiColor::Cyan),
+            UnoColor::Black => None,
+        }
+    }
+}
+
+/// Draw a single card, showing its back pattern when it is face down.
+///
+/// ```
+/// use gametools::Card;
+/// use gametools::art::{ArtStyle, card_art};
+/// use gametools::cards::{Rank, StandardCard, Suit};
+///
+/// let mut card = Card::new_card(StandardCard::new_card(Rank::Ace, Suit::Spades));
+/// card.face_up = false;
+/// assert!(card_art(&card, &ArtStyle::ascii()).contains("|#########|"));
+/// ```
+#[must_use]
+pub fn card_art<T: CardArt>(card: &Card<T>, style: &ArtStyle) -> String {
+    card_block(card, style).render(style.color)
+}
+
+/// Draw a hand of cards with the chosen layout, keeping each row within `max_width`
+/// columns where possible.
+///
+/// Overlapped and fanned hands squeeze the cards together until only the corner of each
+/// card shows; if even that is too wide, the hand wraps onto further rows.
+///
+/// ```
+/// use gametools::{AddCard, Card, Hand};
+/// use gametools::art::{ArtStyle, HandLayout, hand_art};
+/// use gametools::cards::{Rank, StandardCard, Suit};
+///
+/// let mut hand = Hand::<StandardCard>::new("dealer");
+/// for rank in [Rank::Two, Rank::Seven, Rank::King] {
+///     hand.add_card(Card::new_card(StandardCard::new_card(rank, Suit::Clubs)));
+/// }
+/// let art = hand_art(&hand, HandLayout::Overlap, 30, &ArtStyle::unicode());
+/// assert!(art.lines().all(|line| line.chars().count() <= 30));
+/// assert!(art.lines().nth(1).unwrap().starts_with("│2♣"));
+/// ```
+#[must_use]
+pub fn hand_art<T: CardArt>(
+    hand: &Hand<T>,
+    layout: HandLayout,
+    max_width: usize,
+    style: &ArtStyle,
+) -> String {
+    cards_art(hand.cards(), layout, max_width, style)
+}
+
+/// Draw any slice of cards with the chosen layout; see [`hand_art`].
+#[must_use]
+pub fn cards_art<T: CardArt>(
+    cards: &[Card<T>],
+    layout: HandLayout,
+    max_width: usize,
+    style: &ArtStyle,
+) -> String {
+    if cards.is_empty() {
+        return String::new();
+    }
+    let (step, per_row) = match layout {
+        HandLayout::Spread => (
+            CARD_WIDTH + 1,
+            fit_count(max_width, CARD_WIDTH, CARD_WIDTH + 1),
+        ),
+        HandLayout::Overlap | HandLayout::Fan => {
+            let step = if cards.len() > 1 {
+                (max_width.saturating_sub(CARD_WIDTH) / (cards.len() - 1))
+                    .clamp(MIN_REVEAL, CARD_WIDTH + 1)
+            } else {
+                CARD_WIDTH + 1
+            };
+            (step, fit_count(max_width, CARD_WIDTH, step))
+        }
+    };
+    cards
+        .chunks(per_row)
+        .map(|row| {
+            let depth = if layout == HandLayout::Fan && row.len() > 2 {
+                FAN_DEPTH
+            } else {
+                0
+            };
+            let width = step * (row.len() - 1) + CARD_WIDTH;
+            let mut canvas = Block::blank(width, CARD_HEIGHT + depth);
+            for (i, card) in row.iter().enumerate() {
+                canvas.paste(
+                    &card_block(card, style),
+                    i * step,
+                    fan_drop(i, row.len(), depth),
+                );
+            }
+            canvas.render(style.color)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Draw a single domino tile with pip grids on each half.
+///
+/// Halves above nine pips show their count as digits instead of a grid.
+///
+/// ```
+/// use gametools::Domino;
+/// use gametools::art::{ArtStyle, Orientation, domino_art};
+///
+/// let art = domino_art(&Domino::new(2, 5, 0), Orientation::Horizontal, &ArtStyle::ascii());
+/// assert_eq!(
+///     art,
+///     "+-------+-------+\n\
+///      | o     | o   o |\n\
+///      |       |   o   |\n\
+///      |     o | o   o |\n\
+///      +-------+-------+"
+/// );
+/// ```
+#[must_use]
+pub fn domino_art(tile: &Domino, orientation: Orientation, style: &ArtStyle) -> String {
+    domino_block(tile, orientation, style.charset).render(style.color)
+}
+
+/// Draw a row of domino tiles separated by a space, wrapping onto more rows to stay
+/// within `max_width` columns.
+#[must_use]
+pub fn dominos_art(
+    tiles: &[Domino],
+    orientation: Orientation,
+    max_width: usize,
+    style: &ArtStyle,
+) -> String {
+    let Some(first) = tiles.first() else {
+        return String::new();
+    };
+    let sample = domino_block(first, orientation, style.charset);
+    let step = sample.width + 1;
+    tiles
+        .chunks(fit_count(max_width, sample.width, step))
+        .map(|row| {
+            let mut canvas = Block::blank(step * row.len() - 1, sample.height());
+            for (i, tile) in row.iter().enumerate() {
+                canvas.paste(&domino_block(tile, orientation, style.charset), i * step, 0);
+            }
+            canvas.render(style.color)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Draw the tiles of a train, in play order, as horizontal dominos.
+#[must_use]
+pub fn train_art(train: &Train, max_width: usize, style: &ArtStyle) -> String {
+    dominos_art(train.tiles(), Orientation::Horizontal, max_width, style)
+}
+
+/// One drawn character and the color it is printed in.
+type Cell = (char, Option<AnsiColor>);
+
+/// A grid of optionally colored characters that can be layered and rendered.
+#[derive(Debug, Clone)]
+struct Block {
+    width: usize,
+    rows: Vec<Vec<Option<Cell>>>,
+}
+impl Block {
+    fn blank(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            rows: vec![vec![None; width]; height],
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Write `text` at (`x`, `y`), clipping anything past the right edge.
+    fn put(&mut self, x: usize, y: usize, text: &str, color: Option<AnsiColor>) {
+        let row = &mut self.rows[y];
+        for (cell, ch) in row.iter_mut().skip(x).zip(text.chars()) {
+            *cell = Some((ch, color));
+        }
+    }
+
+    /// Layer `other` on top of this block with its top-left corner at (`x`, `y`).
+    fn paste(&mut self, other: &Block, x: usize, y: usize) {
+        for (dst, src) in self.rows.iter_mut().skip(y).zip(&other.rows) {
+            for (cell, value) in dst.iter_mut().skip(x).zip(src) {
+                if value.is_some() {
+                    *cell = *value;
+                }
+            }
+        }
+    }
+
+    fn render(&self, color: bool) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut line = String::new();
+                let mut current = None;
+                for cell in row {
+                    let (ch, tint) = cell.unwrap_or((' ', None));
+                    let tint = if color && ch != ' ' { tint } else { None };
+                    if tint != current {
+                        if current.is_some() {
+                            line.push_str("\x1b[0m");
+                        }
+                        if let Some(c) = tint {
+                            line.push_str(&format!("\x1b[{}m", c.code()));
+                        }
+                        current = tint;
+                    }
+                    line.push(ch);
+                }
+                if current.is_some() {
+                    line.push_str("\x1b[0m");
+                }
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn frame(block: &mut Block, x: usize, y: usize, width: usize, height: usize, charset: Charset) {
+    let (h, v, tl, tr, bl, br) = match charset {
+        Charset::Unicode => ("─", "│", "┌", "┐", "└", "┘"),
+        Charset::Ascii => ("-", "|", "+", "+", "+", "+"),
+    };
+    let bar = h.repeat(width - 2);
+    block.put(x, y, &format!("{tl}{bar}{tr}"), None);
+    block.put(x, y + height - 1, &format!("{bl}{bar}{br}"), None);
+    for row in y + 1..y + height - 1 {
+        block.put(x, row, v, None);
+        block.put(x + width - 1, row, v, None);
+        block.put(x + 1, row, &" ".repeat(width - 2), None);
+    }
+}
+
+fn card_block<T: CardArt>(card: &Card<T>, style: &ArtStyle) -> Block {
+    let mut block = Block::blank(CARD_WIDTH, CARD_HEIGHT);
+    frame(&mut block, 0, 0, CARD_WIDTH, CARD_HEIGHT, style.charset);
+    if !card.face_up {
+        let pattern = match style.charset {
+            Charset::Unicode => "░",
+            Charset::Ascii => "#",
+        }
+        .repeat(CARD_INNER_WIDTH);
+        for row in 1..CARD_HEIGHT - 1 {
+            block.put(1, row, &pattern, Some(style.back_color));
+        }
+        return block;
+    }
+    let tint = card.faces.art_color();
+    let corner: String = card
+        .faces
+        .art_corner(style.charset)
+        .chars()
+        .take(3)
+        .collect();
+    block.put(1, 1, &corner, tint);
+    let right = CARD_WIDTH - 1 - corner.chars().count();
+    block.put(right, CARD_HEIGHT - 2, &corner, tint);
+    for (i, line) in card
+        .faces
+        .art_body(style.charset)
+        .iter()
+        .take(CARD_BODY_HEIGHT)
+        .enumerate()
+    {
+        block.put(1, 2 + i, line, tint);
+    }
+    block
+}
+
+fn domino_block(tile: &Domino, orientation: Orientation, charset: Charset) -> Block {
+    let (h, v, left_t, right_t, top_t, bottom_t) = match charset {
+        Charset::Unicode => ("─", "│", "├", "┤", "┬", "┴"),
+        Charset::Ascii => ("-", "|", "+", "+", "+", "+"),
+    };
+    let halves = [tile.left(), tile.right()];
+    match orientation {
+        Orientation::Horizontal => {
+            let width = HALF_WIDTH * 2 + 3;
+            let mut block = Block::blank(width, HALF_HEIGHT + 2);
+            frame(&mut block, 0, 0, width, HALF_HEIGHT + 2, charset);
+            let mid = HALF_WIDTH + 1;
+            block.put(mid, 0, top_t, None);
+            block.put(mid, HALF_HEIGHT + 1, bottom_t, None);
+            for row in 1..=HALF_HEIGHT {
+                block.put(mid, row, v, None);
+            }
+            for (half, &pips) in halves.iter().enumerate() {
+                for (row, line) in pip_rows(pips, charset).iter().enumerate() {
+                    block.put(1 + half * (HALF_WIDTH + 1), 1 + row, line, pip_color(pips));
+                }
+            }
+            block
+        }
+        Orientation::Vertical => {
+            let width = HALF_WIDTH + 2;
+            let height = HALF_HEIGHT * 2 + 3;
+            let mut block = Block::blank(width, height);
+            frame(&mut block, 0, 0, width, height, charset);
+            let mid = HALF_HEIGHT + 1;
+            block.put(
+                0,
+                mid,
+                &format!("{left_t}{}{right_t}", h.repeat(HALF_WIDTH)),
+                None,
+            );
+            for (half, &pips) in halves.iter().enumerate() {
+                for (row, line) in pip_rows(pips, charset).iter().enumerate() {
+                    block.put(1, 1 + half * (HALF_HEIGHT + 1) + row, line, pip_color(pips));
+                }
+            }
+            block
+        }
+    }
+}
+
+/// The tint for a domino half with `pips` pips; blanks have none.
+fn pip_color(pips: u8) -> Option<AnsiColor> {
+    let idx = usize::from(pips.checked_sub(1)?);
+    Some(PIP_COLORS[idx % PIP_COLORS.len()])
+}
+
+/// Rows for one domino half: a 3x3 pip grid, or the count itself above nine.
+fn pip_rows(pips: u8, charset: Charset) -> Vec<String> {
+    if pips > 9 {
+        let mut rows = vec![" ".repeat(HALF_WIDTH); HALF_HEIGHT];
+        rows[HALF_HEIGHT / 2] = center(&pips.to_string(), HALF_WIDTH);
+        return rows;
+    }
+    let dot = match charset {
+        Charset::Unicode => '●',
+        Charset::Ascii => 'o',
+    };
+    // cells numbered 0..9 left to right, top to bottom
+    let cells: &[usize] = match pips {
+        0 => &[],
+        1 => &[4],
+        2 => &[0, 8],
+        3 => &[0, 4, 8],
+        4 => &[0, 2, 6, 8],
+        5 => &[0, 2, 4, 6, 8],
+        6 => &[0, 2, 3, 5, 6, 8],
+        7 => &[0, 2, 3, 4, 5, 6, 8],
+        8 => &[0, 1, 2, 3, 5, 6, 7, 8],
+        _ => &[0, 1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    (0..HALF_HEIGHT)
+        .map(|row| {
+            let [a, b, c] = [0, 1, 2].map(|col| {
+                if cells.contains(&(row * 3 + col)) {
+                    dot
+                } else {
+                    ' '
+                }
+            });
+            format!(" {a} {b} {c} ")
+        })
+        .collect()
+}
+
+/// Pip positions, as (row, column) in a 5x3 grid, for the number ranks.
+fn standard_pips(rank: Rank) -> &'static [(usize, usize)] {
+    match rank {
+        Rank::Two => &[(0, 1), (4, 1)],
+        Rank::Three => &[(0, 1), (2, 1), (4, 1)],
+        Rank::Four => &[(0, 0), (0, 2), (4, 0), (4, 2)],
+        Rank::Five => &[(0, 0), (0, 2), (2, 1), (4, 0), (4, 2)],
+        Rank::Six => &[(0, 0), (0, 2), (2, 0), (2, 2), (4, 0), (4, 2)],
+        Rank::Seven => &[(0, 0), (0, 2), (1, 1), (2, 0), (2, 2), (4, 0), (4, 2)],
+        Rank::Eight => &[
+            (0, 0),
+            (0, 2),
+            (1, 1),
+            (2, 0),
+            (2, 2),
+            (3, 1),
+            (4, 0),
+            (4, 2),
+        ],
+        Rank::Nine => &[
+            (0, 0),
+            (0, 2),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (3, 0),
+            (3, 2),
+            (4, 0),
+            (4, 2),
+        ],
+        Rank::Ten => &[
+            (0, 0),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (4, 0),
+            (4, 2),
+        ],
+        _ => &[(2, 1)],
+    }
+}
+
+fn suit_symbol(suit: Suit, charset: Charset) -> char {
+    match charset {
+        Charset::Unicode => suit
+            .to_string()
+            .chars()
+            .next()
+            .expect("suit display is never empty"),
+        Charset::Ascii => suit.code(),
+    }
+}
+
+/// How many items `width` columns wide, placed every `step` columns, fit in `max_width`.
+/// Always at least one.
+fn fit_count(max_width: usize, width: usize, step: usize) -> usize {
+    max_width.saturating_sub(width) / step + 1
+}
+
+/// Rows card `index` of `count` sits below the top of a fan that is `depth` rows deep.
+fn fan_drop(index: usize, count: usize, depth: usize) -> usize {
+    if depth == 0 || count < 2 {
+        return 0;
+    }
+    let mid = (count - 1) as f64 / 2.0;
+    let offset = (index as f64 - mid) / mid;
+    (offset * offset * depth as f64).round() as usize
+}
+
+fn center(text: &str, width: usize) -> String {
+    let len = text.chars().count().min(width);
+    let left = (width - len) / 2;
+    let body: String = text.chars().take(width).collect();
+    format!(
+        "{}{body}{}",
+        " ".repeat(left),
+        " ".repeat(width - len - left)
+    )
+}
+
+/// Word-wrap `text` into `height` centered rows of `width` columns, middle-aligned.
+fn wrap_centered(text: &str, width: usize, height: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines.truncate(height);
+    let top = (height - lines.len()) / 2;
+    let mut rows = vec![" ".repeat(width); height];
+    for (i, line) in lines.iter().enumerate() {
+        rows[top + i] = center(line, width);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank: Rank, suit: Suit) -> Card<StandardCard> {
+        Card::new_card(StandardCard::new_card(rank, suit))
+    }
+
+    #[test]
+    fn unicode_card_has_box_frame_and_corners() {
+        let art = card_art(&card(Rank::Ten, Suit::Spades), &ArtStyle::unicode());
+        let lines: Vec<&str> = art.lines().collect();
+        assert_eq!(lines.len(), CARD_HEIGHT);
+        assert_eq!(lines[0], "┌─────────┐");
+        assert_eq!(lines[1], "│10♠      │");
+        assert_eq!(lines[7], "│      10♠│");
+        assert_eq!(lines[8], "└─────────┘");
+        assert!(lines.iter().all(|line| line.chars().count() == CARD_WIDTH));
+        let pips: usize = lines[2..7].iter().map(|l| l.matches('♠').count()).sum();
+        assert_eq!(pips, 10);
+    }
+
+    #[test]
+    fn face_cards_and_jokers_show_labels() {
+        let king = card_art(&card(Rank::King, Suit::Diamonds), &ArtStyle::ascii());
+        assert!(king.contains("|  KING   |"));
+        let joker = card_art(&card(Rank::Joker, Suit::Wild), &ArtStyle::ascii());
+        assert!(joker.contains("|  JOKER  |"));
+        assert!(joker.contains("|*        |"));
+    }
+
+    #[test]
+    fn color_wraps_red_suits_and_backs_only() {
+        let style = ArtStyle::unicode().with_color(true);
+        let hearts = card_art(&card(Rank::Two, Suit::Hearts), &style);
+        assert!(hearts.contains("\x1b[31m2♥"));
+        let clubs = card_art(&card(Rank::Two, Suit::Clubs), &style);
+        assert!(!clubs.contains('\x1b'));
+        let mut back = card(Rank::Two, Suit::Clubs);
+        back.face_up = false;
+        assert!(card_art(&back, &style).contains("\x1b[34m░"));
+        assert!(!card_art(&back, &ArtStyle::unicode()).contains('\x1b'));
+    }
+
+    #[test]
+    fn color_tints_domino_pips() {
+        let tile = Domino::new(0, 3, 0);
+        let plain = domino_art(&tile, Orientation::Horizontal, &ArtStyle::unicode());
+        assert!(!plain.contains('\x1b'));
+        let style = ArtStyle::unicode().with_color(true);
+        let tinted = domino_art(&tile, Orientation::Horizontal, &style);
+        assert!(tinted.contains("\x1b[31m●"));
+        assert!(!tinted.contains("\x1b[36m"), "blank halves stay uncolored");
+        let row = dominos_art(&[tile], Orientation::Vertical, 40, &style);
+        assert!(row.contains("\x1b[31m●"));
+    }
+
+    #[test]
+    fn generic_faces_wrap_their_front_text() {
+        let uno = Card::new_card(UnoCard {
+            color: UnoColor::Green,
+            kind: UnoCardKind::Action(UnoAction::Skip),
+        });
+        let art = card_art(&uno, &ArtStyle::ascii());
+        assert!(art.contains("|S        |"));
+        assert!(art.contains("Green"));
+        assert!(art.contains("Skip"));
+    }
+
+    #[test]
+    fn hand_layouts_fit_requested_width() {
+        let cards: Vec<_> = Rank::normal_ranks()
+            .into_iter()
+            .map(|rank| card(rank, Suit::Clubs))
+            .collect();
+        let spread = cards_art(&cards[..3], HandLayout::Spread, 80, &ArtStyle::ascii());
+        assert_eq!(spread.lines().count(), CARD_HEIGHT);
+        assert_eq!(spread.lines().next().unwrap().len(), CARD_WIDTH * 3 + 2);
+
+        let overlap = cards_art(&cards, HandLayout::Overlap, 60, &ArtStyle::ascii());
+        assert_eq!(overlap.lines().count(), CARD_HEIGHT);
+        assert!(overlap.lines().all(|line| line.len() <= 60));
+        assert!(overlap.lines().nth(1).unwrap().starts_with("|2C |3C"));
+
+        let wrapped = cards_art(&cards, HandLayout::Overlap, 30, &ArtStyle::ascii());
+        assert!(wrapped.lines().count() > CARD_HEIGHT);
+        assert!(wrapped.lines().all(|line| line.len() <= 30));
+
+        let fan = cards_art(&cards[..5], HandLayout::Fan, 40, &ArtStyle::ascii());
+        let lines: Vec<&str> = fan.lines().collect();
+        assert_eq!(lines.len(), CARD_HEIGHT + FAN_DEPTH);
+        assert_eq!(lines[0].trim(), "+---------+");
+    }
+
+    #[test]
+    fn vertical_dominos_stack_halves() {
+        let art = domino_art(
+            &Domino::new(6, 0, 0),
+            Orientation::Vertical,
+            &ArtStyle::unicode(),
+        );
+        assert_eq!(
+            art,
+            "┌───────┐\n\
+             │ ●   ● │\n\
+             │ ●   ● │\n\
+             │ ●   ● │\n\
+             ├───────┤\n\
+             │       │\n\
+             │       │\n\
+             │       │\n\
+             └───────┘"
+        );
+        let big = domino_art(
+            &Domino::new(12, 1, 1),
+            Orientation::Horizontal,
+            &ArtStyle::ascii(),
+        );
+        assert!(big.contains("|  12   |   o   |"));
+    }
+
+    #[test]
+    fn train_tiles_wrap_to_width() {
+        let mut train = Train::new("amy", true, 3);
+        for (i, (l, r)) in [(3, 4), (4, 1), (1, 1)].into_iter().enumerate() {
+            train.play(Domino::new(l, r, i), "amy").unwrap();
+        }
+        let wide = train_art(&train, 80, &ArtStyle::ascii());
+        assert_eq!(wide.lines().count(), HALF_HEIGHT + 2);
+        let narrow = train_art(&train, 40, &ArtStyle::ascii());
+        assert_eq!(narrow.lines().count(), (HALF_HEIGHT + 2) * 2);
+        assert!(narrow.lines().all(|line| line.len() <= 40));
+    }
+}
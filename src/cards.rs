@@ -77,11 +77,24 @@
 //! assert_eq!(hand.size(), 3);
 //! ```
 pub mod card;
+pub mod card_counting;
+pub mod combos;
+pub mod custom_deck;
 pub mod deck;
+pub mod deckbuilding;
+pub mod draw_odds;
+#[cfg(feature = "fair-shuffle")]
+pub mod fair_shuffle;
 pub mod hand;
 pub mod pile;
+pub mod shedding_game;
+pub mod solitaire;
+pub mod solitaire_solver;
 pub mod std_playing_cards;
+mod table;
+pub mod tarot;
 pub mod uno_cards;
+pub mod uno_game;
 
 pub use card::{Card, CardFaces};
 pub use deck::Deck;
@@ -198,6 +211,29 @@ where
     }
 }
 
+/// Reorder `cards` so that new position `i` holds the card previously at `order[i]`.
+///
+/// Shared by the collections that expose a `rearrange` method. `cards` is left
+/// untouched when `order` is not a permutation of its positions.
+pub(crate) fn rearrange_cards<T: CardFaces>(
+    cards: &mut Vec<Card<T>>,
+    order: &[usize],
+) -> GameResult<()> {
+    let mut seen = vec![false; cards.len()];
+    if order.len() != seen.len() {
+        return Err(CardError::InvalidArrangement.into());
+    }
+    for &idx in order {
+        match seen.get_mut(idx) {
+            Some(slot) if !*slot => *slot = true,
+            _ => return Err(CardError::InvalidArrangement.into()),
+        }
+    }
+    let mut old: Vec<Option<Card<T>>> = std::mem::take(cards).into_iter().map(Some).collect();
+    *cards = order.iter().filter_map(|&idx| old[idx].take()).collect();
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,419 @@
+//! # Card Counting
+//!
+//! A [`CardCounter`] follows every card revealed from a shoe of [`StandardCard`]s. It keeps
+//! the running and true counts for a [`CountSystem`] and the exact makeup of the cards not
+//! yet seen, so it can also give the odds of what comes next. It is meant for blackjack
+//! trainers and computer players.
+//!
+//! Hi-Lo, KO, and Omega II are built in, and [`CountSystem::custom`] takes any tag table.
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::cards::card_counting::{CardCounter, CountSystem, shoe};
+//! use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut counter = CardCounter::new(CountSystem::hi_lo(), &shoe(6));
+//! counter.observe(&StandardCard::new_card(Rank::Five, Suit::Hearts))?;
+//! counter.observe(&StandardCard::new_card(Rank::Six, Suit::Clubs))?;
+//! counter.observe(&StandardCard::new_card(Rank::King, Suit::Spades))?;
+//!
+//! assert_eq!(counter.running_count(), 1);
+//! assert!(counter.true_count() > 0.0);
+//! assert_eq!(counter.remaining_rank(Rank::Five), 23);
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::std_playing_cards::{Rank, StandardCard, Suit, standard_52};
+use crate::cards::{Card, Deck};
+use crate::{CardError, GameResult};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A blackjack shoe of `decks` standard 52-card decks, unshuffled.
+///
+/// ```
+/// use gametools::cards::card_counting::shoe;
+///
+/// assert_eq!(shoe(6).len(), 312);
+/// ```
+#[must_use]
+pub fn shoe(decks: usize) -> Vec<StandardCard> {
+    (0..decks).flat_map(|_| standard_52()).collect()
+}
+
+/// A card counting system: the tag added to the count for each rank, and where the running
+/// count starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountSystem {
+    /// Name of the system, for display.
+    pub name: String,
+    /// Tag for each rank; ranks not listed count 0.
+    pub tags: BTreeMap<Rank, i32>,
+    /// Starting running count, before adjusting for the number of decks.
+    pub initial_count: i32,
+    /// Added to the starting running count for each full deck in the shoe.
+    pub initial_count_per_deck: i32,
+}
+
+impl CountSystem {
+    /// A balanced system with the given tags, starting from a count of zero.
+    ///
+    /// ```
+    /// use gametools::cards::card_counting::CountSystem;
+    /// use gametools::cards::std_playing_cards::Rank;
+    ///
+    /// let aces = CountSystem::custom("aces", &[(Rank::Ace, -1)]);
+    /// assert_eq!(aces.tag(Rank::Ace), -1);
+    /// assert_eq!(aces.tag(Rank::Two), 0);
+    /// ```
+    #[must_use]
+    pub fn custom(name: &str, tags: &[(Rank, i32)]) -> Self {
+        Self {
+            name: name.to_string(),
+            tags: tags.iter().copied().collect(),
+            initial_count: 0,
+            initial_count_per_deck: 0,
+        }
+    }
+
+    /// Set where the running count starts, for unbalanced systems: `initial` plus
+    /// `per_deck` for each deck in the shoe.
+    #[must_use]
+    pub fn with_initial_count(mut self, initial: i32, per_deck: i32) -> Self {
+        self.initial_count = initial;
+        self.initial_count_per_deck = per_deck;
+        self
+    }
+
+    /// Hi-Lo: 2-6 count +1, 7-9 count 0, tens and aces count -1.
+    #[must_use]
+    pub fn hi_lo() -> Self {
+        Self::custom(
+            "Hi-Lo",
+            &Self::ranked(&[1, 1, 1, 1, 1, 0, 0, 0, -1, -1, -1, -1, -1]),
+        )
+    }
+
+    /// Knock-Out: Hi-Lo with 7 counted +1. Unbalanced, so the running count starts at
+    /// 4 - 4 x decks and is used without converting to a true count.
+    #[must_use]
+    pub fn ko() -> Self {
+        Self::custom(
+            "KO",
+            &Self::ranked(&[1, 1, 1, 1, 1, 1, 0, 0, -1, -1, -1, -1, -1]),
+        )
+        .with_initial_count(4, -4)
+    }
+
+    /// Omega II: 2, 3, 7 count +1; 4, 5, 6 count +2; 9 counts -1; tens count -2; 8 and
+    /// aces count 0.
+    #[must_use]
+    pub fn omega_ii() -> Self {
+        Self::custom(
+            "Omega II",
+            &Self::ranked(&[1, 1, 2, 2, 2, 1, 0, -1, -2, -2, -2, -2, 0]),
+        )
+    }
+
+    /// Pair tags listed from Two up to Ace with their ranks.
+    fn ranked(tags: &[i32; 13]) -> Vec<(Rank, i32)> {
+        Rank::normal_ranks()
+            .into_iter()
+            .zip(tags.iter().copied())
+            .collect()
+    }
+
+    /// The tag for `rank`.
+    #[must_use]
+    pub fn tag(&self, rank: Rank) -> i32 {
+        self.tags.get(&rank).copied().unwrap_or(0)
+    }
+}
+
+impl Default for CountSystem {
+    fn default() -> Self {
+        Self::hi_lo()
+    }
+}
+
+/// Tracks the count and the unseen cards of a shoe as cards are revealed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CardCounter {
+    system: CountSystem,
+    shoe: BTreeMap<(Rank, Suit), usize>,
+    remaining: BTreeMap<(Rank, Suit), usize>,
+    shoe_size: usize,
+    seen: usize,
+    running: i32,
+}
+
+impl CardCounter {
+    /// Start counting a freshly shuffled shoe made of `shoe`.
+    #[must_use]
+    pub fn new(system: CountSystem, shoe: &[StandardCard]) -> Self {
+        let mut composition = BTreeMap::new();
+        for card in shoe {
+            *composition.entry((card.rank, card.suit)).or_insert(0) += 1;
+        }
+        let mut counter = Self {
+            system,
+            remaining: composition.clone(),
+            shoe: composition,
+            shoe_size: shoe.len(),
+            seen: 0,
+            running: 0,
+        };
+        counter.reshuffle();
+        counter
+    }
+
+    /// Start counting the cards currently in `deck`.
+    #[must_use]
+    pub fn from_deck(system: CountSystem, deck: &Deck<StandardCard>) -> Self {
+        let faces: Vec<StandardCard> = deck.cards().iter().map(|card| card.faces).collect();
+        Self::new(system, &faces)
+    }
+
+    /// Put every card back in the shoe and reset the count.
+    pub fn reshuffle(&mut self) {
+        self.remaining = self.shoe.clone();
+        self.seen = 0;
+        let decks = i32::try_from(self.shoe_size / 52).unwrap_or(i32::MAX);
+        self.running = self.system.initial_count + self.system.initial_count_per_deck * decks;
+    }
+
+    /// Record a revealed card.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if no such card remains unseen in the shoe.
+    pub fn observe(&mut self, card: &StandardCard) -> GameResult<()> {
+        let count = self
+            .remaining
+            .get_mut(&(card.rank, card.suit))
+            .filter(|count| **count > 0)
+            .ok_or(CardError::CardNotFound)?;
+        *count -= 1;
+        self.seen += 1;
+        self.running += self.system.tag(card.rank);
+        Ok(())
+    }
+
+    /// Record a revealed [`Card`]; face-down cards are ignored until they are turned up.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if no such card remains unseen in the shoe.
+    pub fn observe_card(&mut self, card: &Card<StandardCard>) -> GameResult<()> {
+        if card.face_up {
+            self.observe(&card.faces)?;
+        }
+        Ok(())
+    }
+
+    /// Record several revealed cards, stopping at the first that cannot be found.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if a card does not remain unseen in the shoe.
+    pub fn observe_all<'a>(
+        &mut self,
+        cards: impl IntoIterator<Item = &'a StandardCard>,
+    ) -> GameResult<()> {
+        cards.into_iter().try_for_each(|card| self.observe(card))
+    }
+
+    /// The counting system in use.
+    #[must_use]
+    pub fn system(&self) -> &CountSystem {
+        &self.system
+    }
+
+    /// The running count.
+    #[must_use]
+    pub fn running_count(&self) -> i32 {
+        self.running
+    }
+
+    /// Decks' worth of cards not yet seen.
+    #[must_use]
+    pub fn decks_remaining(&self) -> f64 {
+        self.cards_remaining() as f64 / 52.0
+    }
+
+    /// The running count divided by the decks remaining. With less than half a deck left
+    /// the divisor is held at one half.
+    #[must_use]
+    pub fn true_count(&self) -> f64 {
+        f64::from(self.running) / self.decks_remaining().max(0.5)
+    }
+
+    /// Cards revealed since the last reshuffle.
+    #[must_use]
+    pub fn cards_seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Cards not yet seen.
+    #[must_use]
+    pub fn cards_remaining(&self) -> usize {
+        self.shoe_size - self.seen
+    }
+
+    /// Fraction of the shoe dealt so far.
+    #[must_use]
+    pub fn penetration(&self) -> f64 {
+        if self.shoe_size == 0 {
+            return 0.0;
+        }
+        self.seen as f64 / self.shoe_size as f64
+    }
+
+    /// Unseen copies of each card, by rank and suit.
+    #[must_use]
+    pub fn composition(&self) -> &BTreeMap<(Rank, Suit), usize> {
+        &self.remaining
+    }
+
+    /// Unseen copies of one card.
+    #[must_use]
+    pub fn remaining(&self, rank: Rank, suit: Suit) -> usize {
+        self.remaining.get(&(rank, suit)).copied().unwrap_or(0)
+    }
+
+    /// Unseen cards of `rank`.
+    #[must_use]
+    pub fn remaining_rank(&self, rank: Rank) -> usize {
+        self.count_where(|r, _| r == rank)
+    }
+
+    /// Unseen cards of `suit`.
+    #[must_use]
+    pub fn remaining_suit(&self, suit: Suit) -> usize {
+        self.count_where(|_, s| s == suit)
+    }
+
+    fn count_where(&self, test: impl Fn(Rank, Suit) -> bool) -> usize {
+        self.remaining
+            .iter()
+            .filter(|((rank, suit), _)| test(*rank, *suit))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Probability that the next card satisfies `test`, given the unseen cards.
+    ///
+    /// ```
+    /// use gametools::cards::card_counting::{CardCounter, CountSystem, shoe};
+    /// use gametools::cards::std_playing_cards::Rank;
+    ///
+    /// let counter = CardCounter::new(CountSystem::hi_lo(), &shoe(1));
+    /// let ten_value = counter.probability(|card| {
+    ///     matches!(card.rank, Rank::Ten | Rank::Jack | Rank::Queen | Rank::King)
+    /// });
+    /// assert!((ten_value - 16.0 / 52.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn probability(&self, test: impl Fn(&StandardCard) -> bool) -> f64 {
+        let total = self.cards_remaining();
+        if total == 0 {
+            return 0.0;
+        }
+        let hits = self.count_where(|rank, suit| test(&StandardCard::new_card(rank, suit)));
+        hits as f64 / total as f64
+    }
+
+    /// Probability that the next card is of `rank`.
+    #[must_use]
+    pub fn rank_probability(&self, rank: Rank) -> f64 {
+        self.probability(|card| card.rank == rank)
+    }
+
+    /// Probability that the next card is of `suit`.
+    #[must_use]
+    pub fn suit_probability(&self, suit: Suit) -> f64 {
+        self.probability(|card| card.suit == suit)
+    }
+
+    /// Probability that the next card is exactly `rank` of `suit`.
+    #[must_use]
+    pub fn card_probability(&self, rank: Rank, suit: Suit) -> f64 {
+        self.probability(|card| card.rank == rank && card.suit == suit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sc(rank: Rank, suit: Suit) -> StandardCard {
+        StandardCard::new_card(rank, suit)
+    }
+
+    #[test]
+    fn systems_balance_as_documented() {
+        let full_deck = |system: &CountSystem| -> i32 {
+            standard_52().iter().map(|card| system.tag(card.rank)).sum()
+        };
+        assert_eq!(full_deck(&CountSystem::hi_lo()), 0);
+        assert_eq!(full_deck(&CountSystem::omega_ii()), 0);
+        assert_eq!(full_deck(&CountSystem::ko()), 4);
+        assert_eq!(CountSystem::omega_ii().tag(Rank::Five), 2);
+    }
+
+    #[test]
+    fn unbalanced_count_ends_at_pivot() {
+        let mut counter = CardCounter::new(CountSystem::ko(), &shoe(2));
+        assert_eq!(counter.running_count(), -4);
+        counter.observe_all(&shoe(2)).unwrap();
+        assert_eq!(counter.running_count(), 4);
+        assert_eq!(counter.cards_remaining(), 0);
+        counter.reshuffle();
+        assert_eq!((counter.running_count(), counter.cards_seen()), (-4, 0));
+    }
+
+    #[test]
+    fn true_count_divides_by_decks_left() {
+        let mut counter = CardCounter::new(CountSystem::hi_lo(), &shoe(2));
+        let lows: Vec<_> = [Rank::Two, Rank::Three, Rank::Four, Rank::Five]
+            .iter()
+            .flat_map(|rank| Suit::normal_suits().into_iter().map(|suit| sc(*rank, suit)))
+            .chain((0..2).map(|_| sc(Rank::Seven, Suit::Clubs)))
+            .collect();
+        counter.observe_all(&lows).unwrap();
+        assert_eq!(counter.running_count(), 16);
+        assert_eq!(counter.cards_remaining(), 86);
+        assert!((counter.true_count() - 16.0 / (86.0 / 52.0)).abs() < 1e-9);
+        assert!((counter.penetration() - 18.0 / 104.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn composition_and_probabilities_track_unseen_cards() {
+        let mut counter = CardCounter::new(CountSystem::hi_lo(), &standard_52());
+        counter.observe(&sc(Rank::Ace, Suit::Spades)).unwrap();
+        let mut face_down = Card::new_card(sc(Rank::Ace, Suit::Hearts));
+        face_down.face_up = false;
+        counter.observe_card(&face_down).unwrap();
+
+        assert_eq!(counter.remaining(Rank::Ace, Suit::Spades), 0);
+        assert_eq!(counter.remaining_rank(Rank::Ace), 3);
+        assert_eq!(counter.remaining_suit(Suit::Spades), 12);
+        assert!((counter.rank_probability(Rank::Ace) - 3.0 / 51.0).abs() < 1e-9);
+        assert!((counter.suit_probability(Suit::Hearts) - 13.0 / 51.0).abs() < 1e-9);
+        assert_eq!(counter.card_probability(Rank::Ace, Suit::Spades), 0.0);
+        assert_eq!(
+            counter.observe(&sc(Rank::Ace, Suit::Spades)),
+            Err(CardError::CardNotFound.into())
+        );
+    }
+
+    #[test]
+    fn counter_reads_a_deck() {
+        let deck = Deck::from_faces("shoe", shoe(1));
+        let counter = CardCounter::from_deck(CountSystem::custom("none", &[]), &deck);
+        assert_eq!(counter.cards_remaining(), 52);
+        assert_eq!(counter.system().name, "none");
+    }
+}
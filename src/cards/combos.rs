@@ -0,0 +1,538 @@
+//! # Card Combinations
+//!
+//! Set, run, and flush finders that work for any card type. A face type implements
+//! [`Combinable`] to say how its cards group (rank), line up in sequences, and share a
+//! suit, and which of its cards are naturally wild. A [`ComboFinder`] then searches a
+//! slice of cards, optionally under a different wild rule such as [`deuces_wild`] or
+//! [`one_eyed_jacks`], and returns every match it finds.
+//!
+//! ```
+//! use gametools::Hand;
+//! use gametools::cards::StandardCard;
+//! use gametools::cards::combos::{ComboFinder, deuces_wild};
+//!
+//! let hand = Hand::<StandardCard>::from_text("ann", "KS KH 9C 2D 5H 6H 7H").unwrap();
+//!
+//! // with no wilds, only the kings pair up
+//! let pairs = ComboFinder::new(hand.cards()).sets(2);
+//! assert_eq!(pairs.len(), 1);
+//!
+//! // with deuces wild, the two joins the kings or fills out other sets and runs
+//! let finder = ComboFinder::new(hand.cards()).with_wild_rule(deuces_wild);
+//! assert_eq!(finder.sets(3).len(), 1);
+//! assert!(!finder.runs(4, true).is_empty()); // 4-5-6-7 of hearts, the 2 standing in for the 4
+//! ```
+use crate::cards::custom_deck::AttributeCard;
+use crate::cards::uno_cards::{UnoCard, UnoCardKind, UnoColor};
+use crate::cards::{Card, CardFaces, Hand, Rank, StandardCard, Suit};
+use std::collections::BTreeMap;
+
+/// How a card type takes part in sets, runs, and flushes.
+pub trait Combinable: CardFaces {
+    /// What cards must share to form a set, e.g. rank.
+    type GroupKey: Ord + Clone;
+    /// What cards must share to form a flush or suited run.
+    type SuitKey: Ord + Clone;
+
+    /// The card's set key, or `None` if it can't be part of a set.
+    fn group_key(&self) -> Option<Self::GroupKey>;
+
+    /// Positions the card may take in a run; empty if it can't be part of one. A card
+    /// with several positions (an ace, low or high) fills only one of them in any run.
+    fn sequence_values(&self) -> Vec<u32>;
+
+    /// The card's suit, or `None` if it has none.
+    fn suit_key(&self) -> Option<Self::SuitKey>;
+
+    /// Whether the card is wild on its own, like a joker. Used unless a finder is given
+    /// another wild rule.
+    fn is_natural_wild(&self) -> bool {
+        false
+    }
+
+    /// The lowest and highest run positions the card type has, letting wilds extend a
+    /// run past the naturals actually held. `None` keeps runs within the naturals' range.
+    fn sequence_range() -> Option<(u32, u32)> {
+        None
+    }
+}
+
+impl Combinable for StandardCard {
+    type GroupKey = Rank;
+    type SuitKey = Suit;
+
+    fn group_key(&self) -> Option<Rank> {
+        (self.rank != Rank::Joker).then_some(self.rank)
+    }
+
+    fn sequence_values(&self) -> Vec<u32> {
+        match self.rank {
+            Rank::Joker => Vec::new(),
+            Rank::Ace => vec![1, 14],
+            rank => vec![u32::from(rank as u8)],
+        }
+    }
+
+    fn suit_key(&self) -> Option<Suit> {
+        (self.suit != Suit::Wild).then_some(self.suit)
+    }
+
+    fn is_natural_wild(&self) -> bool {
+        self.rank == Rank::Joker
+    }
+
+    fn sequence_range() -> Option<(u32, u32)> {
+        Some((1, 14))
+    }
+}
+
+impl Combinable for UnoCard {
+    type GroupKey = u8;
+    type SuitKey = UnoColor;
+
+    fn group_key(&self) -> Option<u8> {
+        match self.kind {
+            UnoCardKind::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    fn sequence_values(&self) -> Vec<u32> {
+        self.group_key().map(u32::from).into_iter().collect()
+    }
+
+    fn suit_key(&self) -> Option<UnoColor> {
+        (self.color != UnoColor::Black).then_some(self.color)
+    }
+
+    fn is_natural_wild(&self) -> bool {
+        self.kind.is_wild()
+    }
+
+    fn sequence_range() -> Option<(u32, u32)> {
+        Some((0, 9))
+    }
+}
+
+/// Data-driven cards combine on the keys set in their
+/// [`FaceFormat`](crate::cards::custom_deck::FaceFormat): `group_on` and `suit_on` name the
+/// fields shared by sets and flushes, `sequence_on` the stat giving run positions, and
+/// `wild_tag` the tag that makes a card wild. A card missing a key's field is left out of
+/// that kind of combination.
+impl Combinable for AttributeCard {
+    type GroupKey = String;
+    type SuitKey = String;
+
+    fn group_key(&self) -> Option<String> {
+        let field = self.field(self.format.group_on.as_deref()?);
+        (!field.is_empty()).then_some(field)
+    }
+
+    fn sequence_values(&self) -> Vec<u32> {
+        self.format
+            .sequence_on
+            .as_deref()
+            .and_then(|stat| self.stat(stat))
+            .and_then(|value| u32::try_from(value).ok())
+            .into_iter()
+            .collect()
+    }
+
+    fn suit_key(&self) -> Option<String> {
+        let field = self.field(self.format.suit_on.as_deref()?);
+        (!field.is_empty()).then_some(field)
+    }
+
+    fn is_natural_wild(&self) -> bool {
+        self.format
+            .wild_tag
+            .as_deref()
+            .is_some_and(|tag| self.has_tag(tag))
+    }
+}
+
+/// Wild rule for [`StandardCard`]s: every two is wild, as are jokers.
+#[must_use]
+pub fn deuces_wild(card: &StandardCard) -> bool {
+    matches!(card.rank, Rank::Two | Rank::Joker)
+}
+
+/// Wild rule for [`StandardCard`]s: the jacks of spades and hearts (drawn in profile) are
+/// wild, as are jokers.
+#[must_use]
+pub fn one_eyed_jacks(card: &StandardCard) -> bool {
+    card.rank == Rank::Joker
+        || (card.rank == Rank::Jack && matches!(card.suit, Suit::Spades | Suit::Hearts))
+}
+
+/// A group of cards found by a [`ComboFinder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combo<'a, T: CardFaces> {
+    /// The cards making up the combination. Runs list them in sequence order, with wilds
+    /// in the positions they fill; sets and flushes list naturals first.
+    pub cards: Vec<&'a Card<T>>,
+    /// How many of the cards are wilds.
+    pub wilds: usize,
+}
+
+/// Searches a slice of cards for sets, runs, and flushes.
+///
+/// Each finder returns every distinct match, at most one per set key, run position and
+/// suit, or flush suit, and always built around at least one natural card. Natural cards
+/// are preferred over wilds and are chosen in the order they appear in the slice.
+pub struct ComboFinder<'a, T: Combinable> {
+    cards: &'a [Card<T>],
+    naturals: Vec<&'a Card<T>>,
+    wilds: Vec<&'a Card<T>>,
+}
+impl<'a, T: Combinable> ComboFinder<'a, T> {
+    /// Search `cards`, treating the cards that are [naturally wild](Combinable::is_natural_wild)
+    /// as wild.
+    #[must_use]
+    pub fn new(cards: &'a [Card<T>]) -> Self {
+        Self::split(cards, T::is_natural_wild)
+    }
+
+    /// Search `hand`, treating naturally wild cards as wild.
+    #[must_use]
+    pub fn from_hand(hand: &'a Hand<T>) -> Self {
+        Self::new(hand.cards())
+    }
+
+    /// Replace the wild rule: cards for which `rule` returns true are wild, and no others.
+    #[must_use]
+    pub fn with_wild_rule(self, rule: impl Fn(&T) -> bool) -> Self {
+        Self::split(self.cards, rule)
+    }
+
+    /// Treat no cards as wild.
+    #[must_use]
+    pub fn without_wilds(self) -> Self {
+        self.with_wild_rule(|_| false)
+    }
+
+    /// The cards being treated as wild.
+    #[must_use]
+    pub fn wild_cards(&self) -> &[&'a Card<T>] {
+        &self.wilds
+    }
+
+    /// Every set of `size` cards sharing a group key, one per key, ordered by key.
+    #[must_use]
+    pub fn sets(&self, size: usize) -> Vec<Combo<'a, T>> {
+        if size == 0 {
+            return Vec::new();
+        }
+        let mut groups: BTreeMap<T::GroupKey, Vec<&'a Card<T>>> = BTreeMap::new();
+        for &card in &self.naturals {
+            if let Some(key) = card.faces.group_key() {
+                groups.entry(key).or_default().push(card);
+            }
+        }
+        groups
+            .into_values()
+            .filter_map(|cards| self.fill(cards, size))
+            .collect()
+    }
+
+    /// Every flush of `size` cards sharing a suit, one per suit, ordered by suit. The
+    /// highest naturals of the suit (by [`CardFaces::compare`]) are used.
+    #[must_use]
+    pub fn flushes(&self, size: usize) -> Vec<Combo<'a, T>> {
+        if size == 0 {
+            return Vec::new();
+        }
+        self.by_suit()
+            .into_values()
+            .filter_map(|mut cards| {
+                cards.sort_by(|a, b| b.faces.compare(&a.faces));
+                self.fill(cards, size)
+            })
+            .collect()
+    }
+
+    /// Every run of `length` consecutive positions, ordered by starting position (and by
+    /// suit when `same_suit` is set). Wilds fill gaps and may extend a run past the
+    /// naturals held, within the card type's [sequence range](Combinable::sequence_range).
+    #[must_use]
+    pub fn runs(&self, length: usize, same_suit: bool) -> Vec<Combo<'a, T>> {
+        if length == 0 {
+            return Vec::new();
+        }
+        if same_suit {
+            let mut found = Vec::new();
+            for cards in self.by_suit().into_values() {
+                found.extend(self.runs_among(&cards, length));
+            }
+            found.sort_by_key(|combo| self.run_start(combo));
+            found
+        } else {
+            self.runs_among(&self.naturals, length)
+        }
+    }
+
+    fn split(cards: &'a [Card<T>], rule: impl Fn(&T) -> bool) -> Self {
+        let (wilds, naturals) = cards.iter().partition(|card| rule(&card.faces));
+        Self {
+            cards,
+            naturals,
+            wilds,
+        }
+    }
+
+    fn by_suit(&self) -> BTreeMap<T::SuitKey, Vec<&'a Card<T>>> {
+        let mut suits: BTreeMap<T::SuitKey, Vec<&'a Card<T>>> = BTreeMap::new();
+        for &card in &self.naturals {
+            if let Some(suit) = card.faces.suit_key() {
+                suits.entry(suit).or_default().push(card);
+            }
+        }
+        suits
+    }
+
+    /// Take up to `size` of `naturals` and top up with wilds, if that reaches `size`.
+    fn fill(&self, mut naturals: Vec<&'a Card<T>>, size: usize) -> Option<Combo<'a, T>> {
+        naturals.truncate(size);
+        let missing = size - naturals.len();
+        if naturals.is_empty() || missing > self.wilds.len() {
+            return None;
+        }
+        naturals.extend(self.wilds.iter().take(missing));
+        Some(Combo {
+            cards: naturals,
+            wilds: missing,
+        })
+    }
+
+    fn runs_among(&self, naturals: &[&'a Card<T>], length: usize) -> Vec<Combo<'a, T>> {
+        let values: Vec<u32> = naturals
+            .iter()
+            .flat_map(|card| card.faces.sequence_values())
+            .collect();
+        let (Some(&low), Some(&high)) = (values.iter().min(), values.iter().max()) else {
+            return Vec::new();
+        };
+        let (floor, ceiling) = T::sequence_range().unwrap_or((low, high));
+        let span = u32::try_from(length - 1).unwrap_or(u32::MAX);
+        if span > ceiling - floor {
+            return Vec::new();
+        }
+        let wilds = u32::try_from(self.wilds.len()).unwrap_or(u32::MAX);
+        let first = low.saturating_sub(wilds).max(floor);
+        let last = high.min(ceiling.saturating_sub(span));
+
+        let mut found = Vec::new();
+        for start in first..=last {
+            let mut used = vec![false; naturals.len()];
+            let mut cards = Vec::with_capacity(length);
+            let mut wilds_used = 0;
+            for value in start..=start + span {
+                let pick = naturals.iter().enumerate().position(|(idx, card)| {
+                    !used[idx] && card.faces.sequence_values().contains(&value)
+                });
+                if let Some(idx) = pick {
+                    used[idx] = true;
+                    cards.push(naturals[idx]);
+                } else if wilds_used < self.wilds.len() {
+                    cards.push(self.wilds[wilds_used]);
+                    wilds_used += 1;
+                } else {
+                    break;
+                }
+            }
+            if cards.len() == length && wilds_used < length {
+                found.push(Combo {
+                    cards,
+                    wilds: wilds_used,
+                });
+            }
+        }
+        found
+    }
+
+    /// The lowest run position a run's first natural card could be taking.
+    fn run_start(&self, combo: &Combo<'a, T>) -> u32 {
+        combo
+            .cards
+            .iter()
+            .position(|card| !self.wilds.iter().any(|wild| std::ptr::eq(*wild, *card)))
+            .and_then(|offset| {
+                let values = combo.cards[offset].faces.sequence_values();
+                let offset = u32::try_from(offset).ok()?;
+                values.into_iter().map(|v| v.saturating_sub(offset)).min()
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl<T: Combinable> Hand<T> {
+    /// A [`ComboFinder`] over the cards in this hand.
+    #[must_use]
+    pub fn combos(&self) -> ComboFinder<'_, T> {
+        ComboFinder::from_hand(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::uno_cards::UnoAction;
+
+    fn hand(text: &str) -> Hand<StandardCard> {
+        Hand::from_text("test", text).unwrap()
+    }
+
+    fn codes(combo: &Combo<'_, StandardCard>) -> Vec<String> {
+        combo
+            .cards
+            .iter()
+            .map(|card| format!("{}{}", card.faces.rank.code(), card.faces.suit.code()))
+            .collect()
+    }
+
+    #[test]
+    fn sets_return_every_qualifying_rank() {
+        let cards = hand("KS KH 9C 9D 9H 4S");
+        let pairs = cards.combos().sets(2);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(codes(&pairs[0]), ["9C", "9D"]);
+        assert_eq!(codes(&pairs[1]), ["KS", "KH"]);
+        assert_eq!(cards.combos().sets(3).len(), 1);
+        assert!(cards.combos().sets(4).is_empty());
+    }
+
+    #[test]
+    fn jokers_are_natural_wilds_and_rules_can_replace_them() {
+        let cards = hand("KS 2H 7C Jk");
+        let trips = cards.combos().sets(2);
+        // every natural rank pairs with the joker
+        assert_eq!(trips.len(), 3);
+        assert!(trips.iter().all(|combo| combo.wilds == 1));
+        assert!(cards.combos().without_wilds().sets(2).is_empty());
+
+        let deuces = cards.combos().with_wild_rule(deuces_wild);
+        assert_eq!(deuces.wild_cards().len(), 2);
+        let kings = deuces.sets(3);
+        assert_eq!(kings.len(), 2);
+        assert_eq!(codes(&kings[1]), ["KS", "2H", "**"]);
+    }
+
+    #[test]
+    fn one_eyed_jacks_only_count_profile_jacks() {
+        let cards = hand("JS JH JD 5C 5D");
+        let finder = cards.combos().with_wild_rule(one_eyed_jacks);
+        assert_eq!(finder.wild_cards().len(), 2);
+        let quads = finder.sets(4);
+        assert_eq!(quads.len(), 1);
+        assert_eq!(codes(&quads[0]), ["5C", "5D", "JS", "JH"]);
+    }
+
+    #[test]
+    fn runs_use_aces_high_or_low_and_wilds_fill_gaps() {
+        let cards = hand("AS 2D 3C 5H QC KD");
+        let runs = cards.combos().runs(3, false);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(codes(&runs[0]), ["AS", "2D", "3C"]);
+        assert_eq!(codes(&runs[1]), ["QC", "KD", "AS"]);
+
+        let cards = hand("AS 2D 3C 5H QC KD Jk");
+        let wheel = cards.combos().runs(5, false);
+        assert_eq!(wheel.len(), 1);
+        assert_eq!(codes(&wheel[0]), ["AS", "2D", "3C", "**", "5H"]);
+
+        let cards = hand("AS QC KD Jk");
+        let broadway = cards.combos().runs(4, false);
+        assert_eq!(
+            broadway.iter().map(codes).collect::<Vec<_>>(),
+            [vec!["**", "QC", "KD", "AS"]]
+        );
+    }
+
+    #[test]
+    fn suited_runs_and_flushes_group_by_suit() {
+        let cards = hand("4H 5H 6H 7S 8S 9S 2H");
+        let suited = cards.combos().runs(3, true);
+        assert_eq!(suited.len(), 2);
+        assert_eq!(codes(&suited[0]), ["4H", "5H", "6H"]);
+        assert_eq!(codes(&suited[1]), ["7S", "8S", "9S"]);
+        assert_eq!(cards.combos().runs(3, false).len(), 4);
+
+        let flushes = cards.combos().flushes(4);
+        assert_eq!(flushes.len(), 1);
+        assert_eq!(codes(&flushes[0]), ["6H", "5H", "4H", "2H"]);
+    }
+
+    #[test]
+    fn uno_numbers_form_sets_runs_and_color_flushes() {
+        let uno = |color, kind| Card::new_card(UnoCard { color, kind });
+        let cards = vec![
+            uno(UnoColor::Red, UnoCardKind::Number(3)),
+            uno(UnoColor::Blue, UnoCardKind::Number(3)),
+            uno(UnoColor::Red, UnoCardKind::Number(4)),
+            uno(UnoColor::Red, UnoCardKind::Action(UnoAction::Skip)),
+            uno(UnoColor::Black, UnoCardKind::Wild),
+        ];
+        let finder = ComboFinder::new(&cards);
+        // the wild completes the threes but can't stretch the lone four that far
+        assert_eq!(finder.sets(3).len(), 1);
+        assert_eq!(finder.runs(3, true).len(), 2); // 2-3-4 and 3-4-5 in red
+        let flush = finder.flushes(4);
+        assert_eq!(flush.len(), 1);
+        assert_eq!(flush[0].wilds, 1);
+    }
+
+    #[test]
+    fn wilds_never_stretch_runs_past_the_sequence_range() {
+        let uno = |color, kind| Card::new_card(UnoCard { color, kind });
+        let mut cards = vec![uno(UnoColor::Red, UnoCardKind::Number(0))];
+        cards.extend((0..10).map(|_| uno(UnoColor::Black, UnoCardKind::Wild)));
+        let finder = ComboFinder::new(&cards);
+        assert_eq!(finder.runs(10, false).len(), 1);
+        assert!(finder.runs(11, false).is_empty());
+    }
+
+    #[test]
+    fn attribute_cards_combine_on_configured_keys() {
+        use crate::cards::custom_deck::FaceFormat;
+        use std::sync::Arc;
+
+        let format = Arc::new(FaceFormat {
+            group_on: Some("power".to_string()),
+            sequence_on: Some("power".to_string()),
+            suit_on: Some("faction".to_string()),
+            wild_tag: Some("shapeshifter".to_string()),
+            ..FaceFormat::default()
+        });
+        let card = |name: &str, power: i64, faction: i64| {
+            Card::new_card(
+                AttributeCard::new(name)
+                    .with_stat("power", power)
+                    .with_stat("faction", faction)
+                    .with_format(Arc::clone(&format)),
+            )
+        };
+        let mimic = Card::new_card(
+            AttributeCard::new("Mimic")
+                .with_tag("shapeshifter")
+                .with_format(Arc::clone(&format)),
+        );
+        let cards = vec![
+            card("Imp", 0, 1),
+            card("Ogre", 1, 1),
+            card("Elf", 1, 2),
+            mimic,
+        ];
+        let finder = ComboFinder::new(&cards);
+        assert_eq!(finder.wild_cards().len(), 1);
+        let sets = finder.without_wilds().sets(2);
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].cards[0].faces.name, "Ogre");
+
+        let finder = ComboFinder::new(&cards);
+        assert_eq!(finder.runs(2, true).len(), 1);
+        assert_eq!(finder.flushes(3).len(), 1);
+        // without a sequence range, wilds can't carry a run past the powers held
+        assert_eq!(finder.runs(3, false).len(), 0);
+        assert_eq!(finder.runs(2, false).len(), 1);
+    }
+}
@@ -0,0 +1,612 @@
+//! # Data-Driven Custom Decks
+//!
+//! A generic "attribute bag" card face for prototyping card games without writing a new
+//! Rust type for each one. An [`AttributeCard`] has a name, a set of tags, named integer
+//! stats, and rules text; its [`FaceFormat`] decides how it is displayed and which fields
+//! are used to match and order cards.
+//!
+//! A [`DeckList`] describes a whole deck, with per-card quantities and optional size and
+//! copy limits. Deck lists load straight from TOML with the `toml` feature
+//! (`DeckList::from_toml`) or from JSON with the `json` feature (`DeckList::from_json`).
+//!
+//! ```
+//! use gametools::{CardCollection, CardFaces};
+//! use gametools::cards::custom_deck::{AttributeCard, CardEntry, DeckList, FaceFormat};
+//!
+//! let list = DeckList {
+//!     name: "starter".to_string(),
+//!     format: FaceFormat {
+//!         front: "{name} [{cost}]".to_string(),
+//!         ..FaceFormat::default()
+//!     },
+//!     max_copies: Some(3),
+//!     cards: vec![
+//!         CardEntry::new(AttributeCard::new("Goblin").with_stat("cost", 1), 3),
+//!         CardEntry::new(AttributeCard::new("Ogre").with_stat("cost", 4), 1),
+//!     ],
+//!     ..DeckList::default()
+//! };
+//!
+//! let deck = list.build().unwrap();
+//! assert_eq!(deck.size(), 4);
+//! assert_eq!(deck.cards()[3].faces.display_front(), "Ogre [4]");
+//! ```
+use crate::{
+    DeckListError, GameResult,
+    cards::{CardFaces, Deck},
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How an [`AttributeCard`] is displayed, matched, compared, and combined.
+///
+/// Templates substitute `{name}`, `{text}`, `{tags}` (comma separated), or any stat name,
+/// e.g. `"{name} ({cost})"`. Stats a card lacks render as nothing. Match and compare keys
+/// use the same field names; prefix a compare key with `-` to sort it high-to-low. The
+/// combo keys tell a [`ComboFinder`](crate::cards::combos::ComboFinder) how the cards form
+/// sets, runs, and flushes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FaceFormat {
+    /// Template for the card front.
+    pub front: String,
+    /// Template for the card back, if the cards have one worth showing.
+    pub back: Option<String>,
+    /// Fields that must all be equal for two cards to match.
+    pub match_on: Vec<String>,
+    /// Fields compared in order to sort cards.
+    pub compare_on: Vec<String>,
+    /// Field cards must share to form a set in [`combos`](crate::cards::combos); without
+    /// it no card takes part in sets.
+    pub group_on: Option<String>,
+    /// Stat giving a card's position in a run; without it no card takes part in runs.
+    pub sequence_on: Option<String>,
+    /// Field cards must share to form a flush or suited run.
+    pub suit_on: Option<String>,
+    /// Tag marking cards that are wild on their own.
+    pub wild_tag: Option<String>,
+}
+impl Default for FaceFormat {
+    fn default() -> Self {
+        Self {
+            front: "{name}".to_string(),
+            back: None,
+            match_on: vec!["name".to_string()],
+            compare_on: vec!["name".to_string()],
+            group_on: None,
+            sequence_on: None,
+            suit_on: None,
+            wild_tag: None,
+        }
+    }
+}
+impl FaceFormat {
+    /// Check that templates are well formed and no key is blank.
+    ///
+    /// # Errors
+    /// - [`DeckListError::BadTemplate`] for an unclosed or empty `{}` placeholder.
+    /// - [`DeckListError::EmptyKey`] for a blank match, compare, or combo key.
+    pub fn validate(&self) -> GameResult<()> {
+        for template in std::iter::once(&self.front).chain(&self.back) {
+            if !template_is_valid(template) {
+                return Err(DeckListError::BadTemplate(template.clone()).into());
+            }
+        }
+        let keys = self
+            .match_on
+            .iter()
+            .chain(&self.compare_on)
+            .chain(&self.group_on)
+            .chain(&self.sequence_on)
+            .chain(&self.suit_on)
+            .chain(&self.wild_tag);
+        if keys
+            .map(|key| key.trim_start_matches('-'))
+            .any(str::is_empty)
+        {
+            return Err(DeckListError::EmptyKey.into());
+        }
+        Ok(())
+    }
+}
+
+/// A card face defined entirely by data: name, tags, integer stats, and rules text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttributeCard {
+    /// The card's name.
+    pub name: String,
+    /// Free-form labels such as "creature" or "spell".
+    pub tags: BTreeSet<String>,
+    /// Named numeric attributes such as cost or attack.
+    pub stats: BTreeMap<String, i64>,
+    /// Rules or flavor text.
+    pub text: String,
+    /// Display, match, and compare settings shared by the cards of a deck. Cards built
+    /// from a [`DeckList`] all point at the same copy.
+    pub format: Arc<FaceFormat>,
+}
+impl AttributeCard {
+    /// Create a card with just a name and the default [`FaceFormat`].
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            tags: BTreeSet::new(),
+            stats: BTreeMap::new(),
+            text: String::new(),
+            format: Arc::default(),
+        }
+    }
+
+    /// Add a tag.
+    #[must_use]
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.insert(tag.to_string());
+        self
+    }
+
+    /// Set a stat.
+    #[must_use]
+    pub fn with_stat(mut self, stat: &str, value: i64) -> Self {
+        self.stats.insert(stat.to_string(), value);
+        self
+    }
+
+    /// Set the rules text.
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_string();
+        self
+    }
+
+    /// Use the given display, match, and compare settings, either owned or already shared
+    /// through an `Arc`.
+    #[must_use]
+    pub fn with_format(mut self, format: impl Into<Arc<FaceFormat>>) -> Self {
+        self.format = format.into();
+        self
+    }
+
+    /// The value of a stat, if the card has it.
+    #[must_use]
+    pub fn stat(&self, stat: &str) -> Option<i64> {
+        self.stats.get(stat).copied()
+    }
+
+    /// Whether the card carries the tag.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// The text of a named field: `name`, `text`, `tags`, or a stat (empty if missing).
+    #[must_use]
+    pub fn field(&self, field: &str) -> String {
+        match field {
+            "name" => self.name.clone(),
+            "text" => self.text.clone(),
+            "tags" => self.tags.iter().cloned().collect::<Vec<_>>().join(", "),
+            stat => self.stat(stat).map(|v| v.to_string()).unwrap_or_default(),
+        }
+    }
+
+    /// Fill a template's placeholders from this card's fields.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let mut output = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            output.push_str(&rest[..open]);
+            output.push_str(&self.field(&rest[open + 1..open + close]));
+            rest = &rest[open + close + 1..];
+        }
+        output.push_str(rest);
+        output
+    }
+
+    fn compare_field(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "name" | "text" | "tags" => self.field(field).cmp(&other.field(field)),
+            stat => self.stat(stat).cmp(&other.stat(stat)),
+        }
+    }
+}
+impl CardFaces for AttributeCard {
+    fn display_front(&self) -> String {
+        self.render(&self.format.front)
+    }
+
+    fn display_back(&self) -> Option<String> {
+        self.format.back.as_ref().map(|back| self.render(back))
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.format
+            .match_on
+            .iter()
+            .all(|key| self.field(key) == other.field(key))
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        self.format
+            .compare_on
+            .iter()
+            .map(|key| match key.strip_prefix('-') {
+                Some(key) => self.compare_field(other, key).reverse(),
+                None => self.compare_field(other, key),
+            })
+            .find(|order| order.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// One line of a [`DeckList`]: a card and how many copies to include.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CardEntry {
+    /// The card's name.
+    pub name: String,
+    /// Copies of the card in the deck.
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub quantity: usize,
+    /// Tags for the card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: BTreeSet<String>,
+    /// Stats for the card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: BTreeMap<String, i64>,
+    /// Rules text for the card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub text: String,
+}
+impl CardEntry {
+    /// An entry for `quantity` copies of `card`. The card's own format is ignored in favor
+    /// of the deck list's.
+    #[must_use]
+    pub fn new(card: AttributeCard, quantity: usize) -> Self {
+        Self {
+            name: card.name,
+            quantity,
+            tags: card.tags,
+            stats: card.stats,
+            text: card.text,
+        }
+    }
+
+    /// The card this entry describes, sharing `format`.
+    #[must_use]
+    pub fn card(&self, format: &Arc<FaceFormat>) -> AttributeCard {
+        AttributeCard {
+            name: self.name.clone(),
+            tags: self.tags.clone(),
+            stats: self.stats.clone(),
+            text: self.text.clone(),
+            format: Arc::clone(format),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn one() -> usize {
+    1
+}
+
+/// A named list of cards with quantities, a shared [`FaceFormat`], and optional limits.
+///
+/// In TOML a deck list looks like:
+///
+/// ```toml
+/// name = "Goblin Rush"
+/// min_size = 4
+/// max_copies = 3
+///
+/// [format]
+/// front = "{name} ({cost})"
+/// compare_on = ["cost", "name"]
+///
+/// [[cards]]
+/// name = "Goblin"
+/// quantity = 3
+/// tags = ["creature"]
+/// stats = { cost = 1, attack = 2 }
+///
+/// [[cards]]
+/// name = "Warcry"
+/// text = "Goblins get +1 attack."
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeckList {
+    /// The deck's name, also used as the name of the built [`Deck`].
+    pub name: String,
+    /// Display, match, and compare settings applied to every card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub format: FaceFormat,
+    /// Fewest cards the deck may hold.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_size: Option<usize>,
+    /// Most cards the deck may hold.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_size: Option<usize>,
+    /// Most copies of any one card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_copies: Option<usize>,
+    /// The cards, each listed once with its quantity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cards: Vec<CardEntry>,
+}
+impl DeckList {
+    /// Parse and validate a deck list written in TOML.
+    ///
+    /// # Errors
+    /// - [`DeckListError::Parse`] if the text isn't a valid deck list.
+    /// - Any error from [`validate`](Self::validate).
+    #[cfg(feature = "toml")]
+    pub fn from_toml(text: &str) -> GameResult<Self> {
+        let list: Self = toml::from_str(text).map_err(|e| DeckListError::Parse(e.to_string()))?;
+        list.validate()?;
+        Ok(list)
+    }
+
+    /// Parse and validate a deck list written in JSON.
+    ///
+    /// # Errors
+    /// - [`DeckListError::Parse`] if the text isn't a valid deck list.
+    /// - Any error from [`validate`](Self::validate).
+    #[cfg(feature = "json")]
+    pub fn from_json(text: &str) -> GameResult<Self> {
+        let list: Self =
+            serde_json::from_str(text).map_err(|e| DeckListError::Parse(e.to_string()))?;
+        list.validate()?;
+        Ok(list)
+    }
+
+    /// Total number of cards, counting every copy.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.cards.iter().map(|entry| entry.quantity).sum()
+    }
+
+    /// Check the list against its own limits and the format's templates.
+    ///
+    /// # Errors
+    /// - [`DeckListError::EmptyName`], [`DeckListError::ZeroQuantity`], or
+    ///   [`DeckListError::DuplicateCard`] for a bad card entry.
+    /// - [`DeckListError::TooManyCopies`], [`DeckListError::TooFewCards`], or
+    ///   [`DeckListError::TooManyCards`] if a limit is broken.
+    /// - Any error from [`FaceFormat::validate`].
+    pub fn validate(&self) -> GameResult<()> {
+        self.format.validate()?;
+        let mut seen = BTreeSet::new();
+        for (index, entry) in self.cards.iter().enumerate() {
+            if entry.name.trim().is_empty() {
+                return Err(DeckListError::EmptyName(index).into());
+            }
+            if entry.quantity == 0 {
+                return Err(DeckListError::ZeroQuantity(entry.name.clone()).into());
+            }
+            if !seen.insert(entry.name.as_str()) {
+                return Err(DeckListError::DuplicateCard(entry.name.clone()).into());
+            }
+            if let Some(limit) = self.max_copies
+                && entry.quantity > limit
+            {
+                return Err(DeckListError::TooManyCopies {
+                    card: entry.name.clone(),
+                    copies: entry.quantity,
+                    limit,
+                }
+                .into());
+            }
+        }
+        let count = self.size();
+        if let Some(min) = self.min_size
+            && count < min
+        {
+            return Err(DeckListError::TooFewCards { count, min }.into());
+        }
+        if let Some(max) = self.max_size
+            && count > max
+        {
+            return Err(DeckListError::TooManyCards { count, max }.into());
+        }
+        Ok(())
+    }
+
+    /// Every card in the list, with copies repeated, in list order.
+    ///
+    /// # Errors
+    /// - Any error from [`validate`](Self::validate).
+    pub fn faces(&self) -> GameResult<Vec<AttributeCard>> {
+        self.validate()?;
+        let format = Arc::new(self.format.clone());
+        Ok(self
+            .cards
+            .iter()
+            .flat_map(|entry| std::iter::repeat_n(entry.card(&format), entry.quantity))
+            .collect())
+    }
+
+    /// Build an unshuffled [`Deck`] from the list.
+    ///
+    /// # Errors
+    /// - Any error from [`validate`](Self::validate).
+    pub fn build(&self) -> GameResult<Deck<AttributeCard>> {
+        Ok(Deck::from_faces(&self.name, self.faces()?))
+    }
+}
+
+/// Whether every `{` in a template has a closing `}` around a non-blank field name.
+fn template_is_valid(template: &str) -> bool {
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            return false;
+        };
+        let name = &rest[open + 1..open + close];
+        if name.trim().is_empty() || name.contains('{') {
+            return false;
+        }
+        rest = &rest[open + close + 1..];
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameError;
+
+    fn entry(name: &str, cost: i64, quantity: usize) -> CardEntry {
+        CardEntry::new(AttributeCard::new(name).with_stat("cost", cost), quantity)
+    }
+
+    #[test]
+    fn templates_render_fields_and_stats() {
+        let card = AttributeCard::new("Goblin")
+            .with_tag("creature")
+            .with_tag("goblin")
+            .with_stat("attack", 2)
+            .with_text("Haste")
+            .with_format(FaceFormat {
+                front: "{name} {attack}/{defense} [{tags}]: {text}".to_string(),
+                back: Some("Monsters".to_string()),
+                ..FaceFormat::default()
+            });
+        assert_eq!(card.display_front(), "Goblin 2/ [creature, goblin]: Haste");
+        assert_eq!(card.display_back().as_deref(), Some("Monsters"));
+        assert!(card.has_tag("goblin"));
+        assert_eq!(card.stat("defense"), None);
+    }
+
+    #[test]
+    fn match_and_compare_follow_configured_keys() {
+        let format = Arc::new(FaceFormat {
+            match_on: vec!["cost".to_string()],
+            compare_on: vec!["-cost".to_string(), "name".to_string()],
+            ..FaceFormat::default()
+        });
+        let ogre = entry("Ogre", 4, 1).card(&format);
+        let troll = entry("Troll", 4, 1).card(&format);
+        let imp = entry("Imp", 1, 1).card(&format);
+        assert!(ogre.matches(&troll));
+        assert!(!ogre.matches(&imp));
+        let mut cards = [imp.clone(), troll.clone(), ogre.clone()];
+        cards.sort_by(CardFaces::compare);
+        assert_eq!(cards.map(|c| c.name), ["Ogre", "Troll", "Imp"]);
+    }
+
+    #[test]
+    fn validation_catches_bad_lists() {
+        let list = |cards, min_size, max_copies| DeckList {
+            name: "test".to_string(),
+            min_size,
+            max_copies,
+            cards,
+            ..DeckList::default()
+        };
+        let err = |list: DeckList| match list.validate() {
+            Err(GameError::DeckListError(e)) => e,
+            other => panic!("expected a deck list error, got {other:?}"),
+        };
+        assert_eq!(
+            err(list(vec![entry("", 1, 1)], None, None)),
+            DeckListError::EmptyName(0)
+        );
+        assert_eq!(
+            err(list(vec![entry("Imp", 1, 0)], None, None)),
+            DeckListError::ZeroQuantity("Imp".to_string())
+        );
+        assert_eq!(
+            err(list(
+                vec![entry("Imp", 1, 1), entry("Imp", 2, 1)],
+                None,
+                None
+            )),
+            DeckListError::DuplicateCard("Imp".to_string())
+        );
+        assert!(matches!(
+            err(list(vec![entry("Imp", 1, 5)], None, Some(4))),
+            DeckListError::TooManyCopies { copies: 5, .. }
+        ));
+        assert_eq!(
+            err(list(vec![entry("Imp", 1, 3)], Some(10), None)),
+            DeckListError::TooFewCards { count: 3, min: 10 }
+        );
+        let mut bad_template = list(vec![entry("Imp", 1, 1)], None, None);
+        bad_template.format.front = "{name".to_string();
+        assert!(matches!(err(bad_template), DeckListError::BadTemplate(_)));
+    }
+
+    #[test]
+    fn build_expands_quantities_in_order() {
+        let list = DeckList {
+            name: "pair".to_string(),
+            cards: vec![entry("Imp", 1, 2), entry("Ogre", 4, 1)],
+            ..DeckList::default()
+        };
+        let deck = list.build().unwrap();
+        assert_eq!(deck.name, "pair");
+        let names: Vec<_> = deck.cards().iter().map(|c| c.faces.name.as_str()).collect();
+        assert_eq!(names, ["Imp", "Imp", "Ogre"]);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn loads_from_toml() {
+        let toml_text = r#"
+            name = "Goblin Rush"
+            max_copies = 3
+
+            [format]
+            front = "{name} ({cost})"
+
+            [[cards]]
+            name = "Goblin"
+            quantity = 3
+            tags = ["creature"]
+            stats = { cost = 1, attack = 2 }
+
+            [[cards]]
+            name = "Warcry"
+            text = "Goblins get +1 attack."
+        "#;
+        let list = DeckList::from_toml(toml_text).unwrap();
+        assert_eq!(list.size(), 4);
+        let faces = list.faces().unwrap();
+        assert_eq!(faces[0].display_front(), "Goblin (1)");
+        assert_eq!(faces[3].text, "Goblins get +1 attack.");
+        assert!(Arc::ptr_eq(&faces[0].format, &faces[3].format));
+
+        assert!(matches!(
+            DeckList::from_toml(
+                "name = \"x\"\nmax_copies = 1\n[[cards]]\nname = \"Imp\"\nquantity = 2"
+            ),
+            Err(GameError::DeckListError(
+                DeckListError::TooManyCopies { .. }
+            ))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn loads_from_json() {
+        let json_text = r#"{ "name": "tiny", "cards": [ { "name": "Imp", "quantity": 2 } ] }"#;
+        let list = DeckList::from_json(json_text).unwrap();
+        assert_eq!(list.build().unwrap().cards().len(), 2);
+
+        assert!(matches!(
+            DeckList::from_json(r#"{ "cards": 3 }"#),
+            Err(GameError::DeckListError(DeckListError::Parse(_)))
+        ));
+    }
+}
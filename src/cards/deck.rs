@@ -27,7 +27,8 @@
 //! assert_eq!(deck.size(), 2);
 //! ```
 
-use crate::cards::{AddCard, Card, CardCollection, CardFaces, Hand, TakeCard};
+use crate::GameResult;
+use crate::cards::{AddCard, Card, CardCollection, CardFaces, Hand, TakeCard, rearrange_cards};
 use rand::prelude::SliceRandom;
 use uuid::Uuid;
 
@@ -189,6 +190,39 @@ impl<T: CardFaces> Deck<T> {
         self.cards.shuffle(&mut rand::rng());
     }
 
+    /// Reorder the deck so that new position `i` holds the card previously at `order[i]`.
+    ///
+    /// This is the hook for shuffles whose permutation is produced elsewhere, such as
+    /// the jointly derived order of a `fair_shuffle` session (with the `fair-shuffle`
+    /// feature). Remember that cards are drawn from the end of the deck.
+    ///
+    /// # Errors
+    /// - [`CardError::InvalidArrangement`](crate::CardError::InvalidArrangement) unless
+    ///   `order` lists every current position exactly once. The deck is left unchanged.
+    ///
+    /// ```
+    /// use gametools::{Card, CardCollection, CardFaces, Deck, TakeCard};
+    ///
+    /// #[derive(Clone)]
+    /// struct Face(u8);
+    ///
+    /// impl CardFaces for Face {
+    ///     fn display_front(&self) -> String { format!("{}", self.0) }
+    ///     fn display_back(&self) -> Option<String> { None }
+    ///     fn matches(&self, other: &Self) -> bool { self.0 == other.0 }
+    ///     fn compare(&self, other: &Self) -> std::cmp::Ordering { self.0.cmp(&other.0) }
+    /// }
+    ///
+    /// let cards = (0..3).map(|n| Card::new_card(Face(n))).collect::<Vec<_>>();
+    /// let mut deck = Deck::from_cards("demo", cards);
+    /// deck.rearrange(&[2, 0, 1]).unwrap();
+    /// assert_eq!(deck.take_card().unwrap().faces.0, 1);
+    /// assert!(deck.rearrange(&[0, 0]).is_err());
+    /// ```
+    pub fn rearrange(&mut self, order: &[usize]) -> GameResult<()> {
+        rearrange_cards(&mut self.cards, order)
+    }
+
     /// Determine whether the supplied `Card` belongs to this `Deck`.
     ///
     /// ```
@@ -0,0 +1,628 @@
+//! # Deckbuilding
+//!
+//! Building blocks for Dominion- and Slay-the-Spire-style games, where each player keeps
+//! recycling a personal deck and buys new cards into it over the game.
+//!
+//! * [`PlayerDeck`] tracks one player's draw pile, hand, cards in play, discard pile, and
+//!   the exhaust and trash zones. Drawing from an empty draw pile reshuffles the discard
+//!   pile into a new one. Cards are moved between zones by uuid, so identical copies are
+//!   never mixed up.
+//! * [`Market`] holds the shared cards for sale: a row of face-up slots refilled from a
+//!   supply [`Deck`], plus named [`SupplyStack`]s of identical cards. It also reports when
+//!   enough stacks have run out to end the game.
+//!
+//! ```
+//! use gametools::{Card, CardCollection, Deck};
+//! use gametools::cards::deckbuilding::{Market, PlayerDeck, SupplyStack};
+//! use gametools::cards::custom_deck::AttributeCard;
+//!
+//! fn card(name: &str, cost: i64) -> AttributeCard {
+//!     AttributeCard::new(name).with_stat("cost", cost)
+//! }
+//! fn cost(card: &AttributeCard) -> u32 {
+//!     card.stat("cost").unwrap_or(0) as u32
+//! }
+//!
+//! let starter = Deck::from_faces("starter", (0..10).map(|_| card("Copper", 0)));
+//! let mut player = PlayerDeck::new("ann", starter);
+//! player.draw(5);
+//! assert_eq!(player.hand().size(), 5);
+//!
+//! let silvers = (0..10).map(|_| Card::new_card(card("Silver", 3))).collect();
+//! let mut market = Market::new(Deck::from_faces("supply", Vec::new()), 0, cost)
+//!     .with_stack(SupplyStack::new("Silver", 3, silvers));
+//!
+//! let mut coins = 5;
+//! let silver = market.buy_from_stack("Silver", &mut coins).unwrap();
+//! player.gain(silver);
+//! assert_eq!(coins, 2);
+//!
+//! player.end_turn(5);
+//! assert_eq!(player.discard_pile().size(), 6);
+//! assert_eq!(player.hand().size(), 5);
+//! ```
+use crate::{
+    AddCard as _, CardCollection as _, CardError, DeckbuildingError, GameResult, TakeCard as _,
+    cards::{Card, CardFaces, Deck, Hand, Pile},
+};
+
+/// One player's cards, split across the zones of a deckbuilding turn.
+#[derive(Debug, Clone)]
+pub struct PlayerDeck<T: CardFaces> {
+    hand: Hand<T>,
+    draw_pile: Pile<T>,
+    in_play: Pile<T>,
+    discard: Pile<T>,
+    exhausted: Pile<T>,
+    trashed: Pile<T>,
+    reshuffles: usize,
+}
+impl<T: CardFaces> PlayerDeck<T> {
+    /// Shuffle a starting deck into a new player's draw pile. The hand starts empty.
+    #[must_use]
+    pub fn new(player: &str, mut starting: Deck<T>) -> Self {
+        let mut draw_pile = Pile::new_pile("draw");
+        draw_pile.add_cards(starting.take_cards(starting.size()));
+        draw_pile.shuffle();
+        Self {
+            hand: Hand::new(player),
+            draw_pile,
+            in_play: Pile::new_pile("in play"),
+            discard: Pile::new_pile("discard"),
+            exhausted: Pile::new_pile("exhausted"),
+            trashed: Pile::new_pile("trash"),
+            reshuffles: 0,
+        }
+    }
+
+    /// The player's hand.
+    #[must_use]
+    pub fn hand(&self) -> &Hand<T> {
+        &self.hand
+    }
+
+    /// Cards waiting to be drawn; the last card is the top.
+    #[must_use]
+    pub fn draw_pile(&self) -> &Pile<T> {
+        &self.draw_pile
+    }
+
+    /// Cards played this turn.
+    #[must_use]
+    pub fn in_play(&self) -> &Pile<T> {
+        &self.in_play
+    }
+
+    /// Cards waiting to be reshuffled into the draw pile.
+    #[must_use]
+    pub fn discard_pile(&self) -> &Pile<T> {
+        &self.discard
+    }
+
+    /// Cards set aside until [`restore_exhausted`](Self::restore_exhausted) is called.
+    #[must_use]
+    pub fn exhausted(&self) -> &Pile<T> {
+        &self.exhausted
+    }
+
+    /// Cards removed from the deck for good.
+    #[must_use]
+    pub fn trashed(&self) -> &Pile<T> {
+        &self.trashed
+    }
+
+    /// How many times the discard pile has been shuffled into a new draw pile.
+    #[must_use]
+    pub fn reshuffles(&self) -> usize {
+        self.reshuffles
+    }
+
+    /// Cards the player owns: everything except the trash.
+    #[must_use]
+    pub fn total_cards(&self) -> usize {
+        self.hand.size()
+            + self.draw_pile.size()
+            + self.in_play.size()
+            + self.discard.size()
+            + self.exhausted.size()
+    }
+
+    /// Draw up to `count` cards into the hand, shuffling the discard pile into a new draw
+    /// pile whenever the draw pile runs out. Returns the number of cards drawn, which is
+    /// less than `count` only when both piles are empty.
+    pub fn draw(&mut self, count: usize) -> usize {
+        for drawn in 0..count {
+            if self.draw_pile.size() == 0 {
+                if self.discard.size() == 0 {
+                    return drawn;
+                }
+                self.reshuffle_discard();
+            }
+            if let Some(card) = self.draw_pile.take_card() {
+                self.hand.add_card(card);
+            }
+        }
+        count
+    }
+
+    /// Move a card from the hand into play.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if the card is not in the hand.
+    pub fn play(&mut self, card: &Card<T>) -> GameResult<()> {
+        let found = self.take_from_hand(card)?;
+        self.in_play.add_card(found);
+        Ok(())
+    }
+
+    /// Move a card from the hand to the discard pile.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if the card is not in the hand.
+    pub fn discard(&mut self, card: &Card<T>) -> GameResult<()> {
+        let found = self.take_from_hand(card)?;
+        self.discard.add_card(found);
+        Ok(())
+    }
+
+    /// Exhaust a card from the hand or from play, wherever it is.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if the card is neither in the hand nor in play.
+    pub fn exhaust(&mut self, card: &Card<T>) -> GameResult<()> {
+        let found = self.take_from_hand_or_play(card)?;
+        self.exhausted.add_card(found);
+        Ok(())
+    }
+
+    /// Trash a card from the hand or from play, wherever it is.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if the card is neither in the hand nor in play.
+    pub fn trash(&mut self, card: &Card<T>) -> GameResult<()> {
+        let found = self.take_from_hand_or_play(card)?;
+        self.trashed.add_card(found);
+        Ok(())
+    }
+
+    /// Put a card from the hand back on top of the draw pile.
+    ///
+    /// # Errors
+    /// - [`CardError::CardNotFound`] if the card is not in the hand.
+    pub fn hand_to_top(&mut self, card: &Card<T>) -> GameResult<()> {
+        let found = self.take_from_hand(card)?;
+        self.draw_pile.add_card(found);
+        Ok(())
+    }
+
+    /// Put any card on top of the draw pile, so it is the next one drawn.
+    pub fn put_on_top(&mut self, card: Card<T>) {
+        self.draw_pile.add_card(card);
+    }
+
+    /// Add a newly gained card to the discard pile.
+    pub fn gain(&mut self, card: Card<T>) {
+        self.discard.add_card(card);
+    }
+
+    /// Add a newly gained card straight to the hand.
+    pub fn gain_to_hand(&mut self, card: Card<T>) {
+        self.hand.add_card(card);
+    }
+
+    /// Discard the hand and every card in play, then draw a new hand of `draw` cards.
+    /// Returns the number of cards drawn.
+    pub fn end_turn(&mut self, draw: usize) -> usize {
+        let in_hand = self.hand.size();
+        self.discard.add_cards(self.hand.take_cards(in_hand));
+        let played = self.in_play.size();
+        self.discard.add_cards(self.in_play.take_cards(played));
+        self.draw(draw)
+    }
+
+    /// Return exhausted cards to the discard pile, e.g. at the end of a combat.
+    pub fn restore_exhausted(&mut self) {
+        let count = self.exhausted.size();
+        self.discard.add_cards(self.exhausted.take_cards(count));
+    }
+
+    /// Gather every owned card (including exhausted ones, but not the trash) into a freshly
+    /// shuffled draw pile with an empty hand.
+    pub fn reset(&mut self) {
+        for pile in [&mut self.in_play, &mut self.discard, &mut self.exhausted] {
+            let count = pile.size();
+            self.draw_pile.add_cards(pile.take_cards(count));
+        }
+        let in_hand = self.hand.size();
+        self.draw_pile.add_cards(self.hand.take_cards(in_hand));
+        self.draw_pile.shuffle();
+    }
+
+    fn reshuffle_discard(&mut self) {
+        let count = self.discard.size();
+        self.draw_pile.add_cards(self.discard.take_cards(count));
+        self.draw_pile.shuffle();
+        self.reshuffles += 1;
+    }
+
+    fn take_from_hand(&mut self, card: &Card<T>) -> GameResult<Card<T>> {
+        self.hand
+            .take_where(|c| c.uuid == card.uuid)
+            .ok_or_else(|| CardError::CardNotFound.into())
+    }
+
+    fn take_from_hand_or_play(&mut self, card: &Card<T>) -> GameResult<Card<T>> {
+        self.hand
+            .take_where(|c| c.uuid == card.uuid)
+            .or_else(|| self.in_play.take_where(|c| c.uuid == card.uuid))
+            .ok_or_else(|| CardError::CardNotFound.into())
+    }
+}
+
+/// A named pile of cards for sale at a fixed cost, like a Dominion kingdom pile.
+#[derive(Debug, Clone)]
+pub struct SupplyStack<T: CardFaces> {
+    /// Name used to buy from the stack.
+    pub name: String,
+    /// Price of each card in the stack.
+    pub cost: u32,
+    cards: Pile<T>,
+}
+impl<T: CardFaces> SupplyStack<T> {
+    /// Create a stack from its cards.
+    #[must_use]
+    pub fn new(name: &str, cost: u32, cards: Vec<Card<T>>) -> Self {
+        let mut pile = Pile::new_pile(name);
+        pile.add_cards(cards);
+        Self {
+            name: name.to_string(),
+            cost,
+            cards: pile,
+        }
+    }
+
+    /// Cards left in the stack.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.cards.size()
+    }
+
+    /// Whether the stack has run out.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.size() == 0
+    }
+
+    /// The card that would be bought next.
+    #[must_use]
+    pub fn top(&self) -> Option<&Card<T>> {
+        self.cards.check_top_card()
+    }
+}
+
+/// The shared cards for sale: a face-up row refilled from a supply deck, plus named stacks.
+#[derive(Debug, Clone)]
+pub struct Market<T: CardFaces> {
+    supply: Deck<T>,
+    row: Vec<Option<Card<T>>>,
+    stacks: Vec<SupplyStack<T>>,
+    price: fn(&T) -> u32,
+}
+impl<T: CardFaces> Market<T> {
+    /// Create a market whose row has `row_size` slots, filled from the top of `supply`.
+    /// `price` gives the cost of a card bought from the row.
+    #[must_use]
+    pub fn new(supply: Deck<T>, row_size: usize, price: fn(&T) -> u32) -> Self {
+        let mut market = Self {
+            supply,
+            row: (0..row_size).map(|_| None).collect(),
+            stacks: Vec::new(),
+            price,
+        };
+        market.refill();
+        market
+    }
+
+    /// Add a supply stack to the market.
+    #[must_use]
+    pub fn with_stack(mut self, stack: SupplyStack<T>) -> Self {
+        self.stacks.push(stack);
+        self
+    }
+
+    /// The row's slots; `None` marks a slot the supply could not refill.
+    #[must_use]
+    pub fn row(&self) -> &[Option<Card<T>>] {
+        &self.row
+    }
+
+    /// The supply stacks, in the order they were added.
+    #[must_use]
+    pub fn stacks(&self) -> &[SupplyStack<T>] {
+        &self.stacks
+    }
+
+    /// The stack with the given name.
+    #[must_use]
+    pub fn stack(&self, name: &str) -> Option<&SupplyStack<T>> {
+        self.stacks.iter().find(|stack| stack.name == name)
+    }
+
+    /// Cards left in the supply deck that refills the row.
+    #[must_use]
+    pub fn supply_size(&self) -> usize {
+        self.supply.size()
+    }
+
+    /// Price of the card in a row slot, if there is one.
+    #[must_use]
+    pub fn slot_price(&self, slot: usize) -> Option<u32> {
+        self.row
+            .get(slot)?
+            .as_ref()
+            .map(|card| (self.price)(&card.faces))
+    }
+
+    /// Buy the card in a row slot, paying from `coins`, and refill the slot.
+    ///
+    /// # Errors
+    /// - [`DeckbuildingError::EmptySlot`] if the slot is empty or out of range.
+    /// - [`DeckbuildingError::CannotAfford`] if `coins` is less than the price.
+    pub fn buy_from_row(&mut self, slot: usize, coins: &mut u32) -> GameResult<Card<T>> {
+        let cost = self
+            .slot_price(slot)
+            .ok_or(DeckbuildingError::EmptySlot(slot))?;
+        pay(cost, coins)?;
+        self.remove_from_row(slot)
+            .ok_or_else(|| DeckbuildingError::EmptySlot(slot).into())
+    }
+
+    /// Buy the top card of a supply stack, paying from `coins`.
+    ///
+    /// # Errors
+    /// - [`DeckbuildingError::NoSuchStack`] if no stack has that name.
+    /// - [`DeckbuildingError::StackEmpty`] if the stack has run out.
+    /// - [`DeckbuildingError::CannotAfford`] if `coins` is less than the stack's cost.
+    pub fn buy_from_stack(&mut self, name: &str, coins: &mut u32) -> GameResult<Card<T>> {
+        let stack = self
+            .stacks
+            .iter_mut()
+            .find(|stack| stack.name == name)
+            .ok_or_else(|| DeckbuildingError::NoSuchStack(name.to_string()))?;
+        if stack.is_empty() {
+            return Err(DeckbuildingError::StackEmpty(name.to_string()).into());
+        }
+        pay(stack.cost, coins)?;
+        stack
+            .cards
+            .take_card()
+            .ok_or_else(|| DeckbuildingError::StackEmpty(name.to_string()).into())
+    }
+
+    /// Take the card out of a row slot without paying (e.g. to trash or gain it for free)
+    /// and refill the slot.
+    pub fn remove_from_row(&mut self, slot: usize) -> Option<Card<T>> {
+        let card = self.row.get_mut(slot)?.take();
+        self.refill();
+        card
+    }
+
+    /// Fill empty row slots from the supply deck, left to right.
+    pub fn refill(&mut self) {
+        for slot in &mut self.row {
+            if slot.is_none() {
+                *slot = self.supply.take_card();
+            }
+        }
+    }
+
+    /// Number of supply stacks that have run out.
+    #[must_use]
+    pub fn empty_stacks(&self) -> usize {
+        self.stacks.iter().filter(|stack| stack.is_empty()).count()
+    }
+
+    /// Whether the game should end: any of the `critical` stacks is empty, or at least
+    /// `empty_limit` stacks are (a limit of zero turns that check off).
+    ///
+    /// Dominion's rule is `is_game_over(3, &["Province"])`.
+    #[must_use]
+    pub fn is_game_over(&self, empty_limit: usize, critical: &[&str]) -> bool {
+        let critical_gone = critical
+            .iter()
+            .any(|name| self.stack(name).is_some_and(SupplyStack::is_empty));
+        critical_gone || (empty_limit > 0 && self.empty_stacks() >= empty_limit)
+    }
+}
+
+fn pay(cost: u32, coins: &mut u32) -> GameResult<()> {
+    if cost > *coins {
+        return Err(DeckbuildingError::CannotAfford {
+            cost,
+            available: *coins,
+        }
+        .into());
+    }
+    *coins -= cost;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameError;
+    use crate::cards::{Rank, StandardCard, Suit};
+
+    fn face(rank: Rank) -> StandardCard {
+        StandardCard::new_card(rank, Suit::Clubs)
+    }
+
+    fn starter(count: usize) -> PlayerDeck<StandardCard> {
+        PlayerDeck::new(
+            "ann",
+            Deck::from_faces("starter", (0..count).map(|_| face(Rank::Two))),
+        )
+    }
+
+    fn value(card: &StandardCard) -> u32 {
+        u32::from(card.value)
+    }
+
+    #[test]
+    fn draw_reshuffles_discard_when_draw_pile_runs_out() {
+        let mut player = starter(7);
+        assert_eq!(player.draw(5), 5);
+        assert_eq!(player.end_turn(5), 5);
+        // two came from the draw pile, three from the reshuffled discard
+        assert_eq!(player.reshuffles(), 1);
+        assert_eq!(player.hand().size(), 5);
+        assert_eq!(player.draw_pile().size(), 2);
+        assert_eq!(player.discard_pile().size(), 0);
+        assert_eq!(player.draw(10), 2);
+        assert_eq!(player.total_cards(), 7);
+    }
+
+    #[test]
+    fn played_cards_are_discarded_at_end_of_turn() {
+        let mut player = starter(10);
+        player.draw(5);
+        let card = player.hand().cards()[0].clone();
+        player.play(&card).unwrap();
+        assert_eq!(player.in_play().size(), 1);
+        assert_eq!(player.hand().size(), 4);
+        player.end_turn(5);
+        assert_eq!(player.in_play().size(), 0);
+        assert_eq!(player.discard_pile().size(), 5);
+
+        let stranger = Card::new_card(face(Rank::King));
+        assert_eq!(
+            player.play(&stranger),
+            Err(GameError::CardError(CardError::CardNotFound))
+        );
+    }
+
+    #[test]
+    fn exhaust_trash_and_topdeck_move_cards_between_zones() {
+        let mut player = starter(6);
+        let ace = Card::new_card(face(Rank::Ace));
+        player.gain_to_hand(ace.clone());
+        player.draw(3);
+        player.hand_to_top(&ace).unwrap();
+        assert!(
+            player
+                .draw_pile()
+                .check_top_card()
+                .unwrap()
+                .faces
+                .matches(&ace.faces)
+        );
+        player.draw(1);
+
+        let two = player.hand().cards()[0].clone();
+        player.play(&two).unwrap();
+        player.exhaust(&two).unwrap();
+        player.trash(&ace).unwrap();
+        assert_eq!(player.exhausted().size(), 1);
+        assert_eq!(player.trashed().size(), 1);
+        assert_eq!(player.total_cards(), 6);
+
+        player.restore_exhausted();
+        player.reset();
+        assert_eq!(player.draw_pile().size(), 6);
+        assert_eq!(player.hand().size(), 0);
+
+        player.put_on_top(Card::new_card(face(Rank::Queen)));
+        player.draw(1);
+        assert_eq!(player.hand().cards()[0].faces.rank, Rank::Queen);
+    }
+
+    #[test]
+    fn identical_cards_are_moved_by_uuid() {
+        let mut player = starter(4);
+        player.draw(4);
+        let played = player.hand().cards()[1].clone();
+        player.play(&played).unwrap();
+        let kept: Vec<_> = player.hand().cards().iter().map(|c| c.uuid).collect();
+
+        player.exhaust(&played).unwrap();
+        assert_eq!(player.in_play().size(), 0);
+        let hand: Vec<_> = player.hand().cards().iter().map(|c| c.uuid).collect();
+        assert_eq!(hand, kept, "the copies in hand are untouched");
+        assert_eq!(player.exhausted().cards()[0].uuid, played.uuid);
+
+        let discarded = player.hand().cards()[2].clone();
+        player.discard(&discarded).unwrap();
+        assert_eq!(player.discard_pile().cards()[0].uuid, discarded.uuid);
+        assert_eq!(
+            player.discard(&discarded),
+            Err(GameError::CardError(CardError::CardNotFound))
+        );
+    }
+
+    #[test]
+    fn market_row_sells_and_refills_from_supply() {
+        let supply = Deck::from_faces("supply", [Rank::Three, Rank::Four, Rank::Five].map(face));
+        let mut market = Market::new(supply, 2, value);
+        assert_eq!(market.slot_price(0), Some(5));
+        assert_eq!(market.slot_price(1), Some(4));
+
+        let mut coins = 4;
+        assert_eq!(
+            market.buy_from_row(0, &mut coins),
+            Err(GameError::DeckbuildingError(
+                DeckbuildingError::CannotAfford {
+                    cost: 5,
+                    available: 4
+                }
+            ))
+        );
+        let bought = market.buy_from_row(1, &mut coins).unwrap();
+        assert_eq!(bought.faces.rank, Rank::Four);
+        assert_eq!(coins, 0);
+        assert_eq!(market.slot_price(1), Some(3));
+        assert_eq!(market.supply_size(), 0);
+
+        assert!(market.remove_from_row(1).is_some());
+        assert_eq!(market.slot_price(1), None);
+        assert_eq!(
+            market.buy_from_row(1, &mut coins),
+            Err(GameError::DeckbuildingError(DeckbuildingError::EmptySlot(
+                1
+            )))
+        );
+    }
+
+    #[test]
+    fn empty_stacks_end_the_game() {
+        let stack = |name: &str, count: usize| {
+            SupplyStack::new(
+                name,
+                2,
+                (0..count)
+                    .map(|_| Card::new_card(face(Rank::Six)))
+                    .collect(),
+            )
+        };
+        let mut market = Market::new(Deck::from_faces("supply", Vec::new()), 0, value)
+            .with_stack(stack("Province", 2))
+            .with_stack(stack("Village", 1))
+            .with_stack(stack("Smithy", 1));
+        let mut coins = 10;
+        market.buy_from_stack("Village", &mut coins).unwrap();
+        assert!(!market.is_game_over(2, &["Province"]));
+        assert_eq!(
+            market.buy_from_stack("Village", &mut coins),
+            Err(GameError::DeckbuildingError(DeckbuildingError::StackEmpty(
+                "Village".to_string()
+            )))
+        );
+        market.buy_from_stack("Smithy", &mut coins).unwrap();
+        assert!(market.is_game_over(2, &["Province"]));
+        assert!(!market.is_game_over(3, &["Province"]));
+        market.buy_from_stack("Province", &mut coins).unwrap();
+        market.buy_from_stack("Province", &mut coins).unwrap();
+        assert!(market.is_game_over(0, &["Province"]));
+        assert_eq!(coins, 2);
+        assert!(market.buy_from_stack("Duchy", &mut coins).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! # Draw Odds
+//!
+//! Exact hypergeometric probabilities for draws from a [`Deck`]. Cards are matched with a
+//! predicate over their faces, so any [`CardFaces`] type can be asked questions like
+//! "what are the odds of at least 2 lands in my opening 7?".
+//!
+//! * [`Deck::draw_odds`] gives the full distribution of matches in `n` draws, with
+//!   exactly / at-least / at-most queries on the resulting [`DrawOdds`]
+//! * [`Deck::joint_draw_odds`] asks for several categories at once ("at least 1 A and 2 B")
+//! * [`Deck::mulligan_odds`] follows a keep-or-mulligan policy to its expected outcome
+//!
+//! Everything is computed by counting combinations, never by sampling.
+//!
+//! ```
+//! use gametools::{CardFaces, Deck, GameResult};
+//! # fn main() -> GameResult<()> {
+//!
+//! #[derive(Clone)]
+//! enum Mtg { Land, Spell }
+//!
+//! impl CardFaces for Mtg {
+//!     fn display_front(&self) -> String { String::new() }
+//!     fn display_back(&self) -> Option<String> { None }
+//!     fn matches(&self, _other: &Self) -> bool { false }
+//!     fn compare(&self, _other: &Self) -> std::cmp::Ordering { std::cmp::Ordering::Equal }
+//! }
+//!
+//! let deck = Deck::from_faces(
+//!     "library",
+//!     (0..60).map(|n| if n < 24 { Mtg::Land } else { Mtg::Spell }),
+//! );
+//! let lands = deck.draw_odds(7, |card| matches!(card, Mtg::Land))?;
+//! assert!((lands.at_least(2) - 0.8573).abs() < 1e-4);
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::{CardCollection, CardFaces, Deck};
+use crate::{CardError, GameResult};
+
+use std::collections::HashMap;
+
+/// Number of ways to choose `k` items from `n`, as a float.
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// The probability distribution of how many matching cards turn up in a draw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawOdds {
+    distribution: Vec<f64>,
+}
+
+impl DrawOdds {
+    /// The hypergeometric distribution for `draws` cards taken from `population`, of which
+    /// `successes` match.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if `draws` or `successes` exceed `population`.
+    ///
+    /// ```
+    /// use gametools::cards::draw_odds::DrawOdds;
+    ///
+    /// // two aces among the top five cards of a standard deck
+    /// let aces = DrawOdds::hypergeometric(52, 4, 5).unwrap();
+    /// assert!((aces.exactly(2) - 0.0399).abs() < 1e-4);
+    /// ```
+    pub fn hypergeometric(population: usize, successes: usize, draws: usize) -> GameResult<Self> {
+        if draws > population || successes > population {
+            return Err(CardError::StackTooSmall("population".to_string()).into());
+        }
+        let total = choose(population, draws);
+        let distribution = (0..=draws.min(successes))
+            .map(|k| choose(successes, k) * choose(population - successes, draws - k) / total)
+            .collect();
+        Ok(Self { distribution })
+    }
+
+    /// Probability of each number of matches, indexed by the number of matches.
+    #[must_use]
+    pub fn distribution(&self) -> &[f64] {
+        &self.distribution
+    }
+
+    /// Probability of exactly `k` matches.
+    #[must_use]
+    pub fn exactly(&self, k: usize) -> f64 {
+        self.distribution.get(k).copied().unwrap_or(0.0)
+    }
+
+    /// Probability of `k` or more matches.
+    #[must_use]
+    pub fn at_least(&self, k: usize) -> f64 {
+        self.distribution.iter().skip(k).sum()
+    }
+
+    /// Probability of `k` or fewer matches.
+    #[must_use]
+    pub fn at_most(&self, k: usize) -> f64 {
+        self.distribution.iter().take(k.saturating_add(1)).sum()
+    }
+
+    /// Probability that the number of matches falls in `min..=max`.
+    #[must_use]
+    pub fn between(&self, min: usize, max: usize) -> f64 {
+        self.distribution
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| (min..=max).contains(k))
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// The expected number of matches.
+    #[must_use]
+    pub fn expected(&self) -> f64 {
+        self.distribution
+            .iter()
+            .enumerate()
+            .map(|(k, p)| k as f64 * p)
+            .sum()
+    }
+}
+
+/// One category in a [`Deck::joint_draw_odds`] query: cards matching `test`, of which
+/// between `min` and `max` must be drawn.
+pub struct DrawRequirement<'a, T> {
+    test: Box<dyn Fn(&T) -> bool + 'a>,
+    min: usize,
+    max: usize,
+}
+
+impl<'a, T> DrawRequirement<'a, T> {
+    /// At least `k` cards matching `test`.
+    pub fn at_least(test: impl Fn(&T) -> bool + 'a, k: usize) -> Self {
+        Self::between(test, k, usize::MAX)
+    }
+
+    /// Exactly `k` cards matching `test`.
+    pub fn exactly(test: impl Fn(&T) -> bool + 'a, k: usize) -> Self {
+        Self::between(test, k, k)
+    }
+
+    /// At most `k` cards matching `test`.
+    pub fn at_most(test: impl Fn(&T) -> bool + 'a, k: usize) -> Self {
+        Self::between(test, 0, k)
+    }
+
+    /// Between `min` and `max` cards (inclusive) matching `test`.
+    pub fn between(test: impl Fn(&T) -> bool + 'a, min: usize, max: usize) -> Self {
+        Self {
+            test: Box::new(test),
+            min,
+            max,
+        }
+    }
+}
+
+/// How mulligans work in a [`Deck::mulligan_odds`] query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MulliganRule {
+    /// Each new hand is one card smaller than the last.
+    Shrinking,
+    /// Every hand is drawn at full size, and one card per mulligan taken so far goes to the
+    /// bottom of the deck once a hand is kept. The keep decision is made on the full hand.
+    London,
+}
+
+/// Expected results of a keep-or-mulligan policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MulliganOdds {
+    /// Probability the hand is kept after exactly `m` mulligans, indexed by `m`.
+    pub keep_chance: Vec<f64>,
+    /// Expected number of mulligans taken.
+    pub expected_mulligans: f64,
+    /// Expected matching cards in the kept hand (for [`MulliganRule::London`], before
+    /// cards are put on the bottom).
+    pub expected_matches: f64,
+    /// Expected size of the hand finally kept.
+    pub expected_hand_size: f64,
+}
+
+impl<T: CardFaces> Deck<T> {
+    /// Number of cards in the deck whose faces satisfy `test`.
+    pub fn count_matching(&self, test: impl Fn(&T) -> bool) -> usize {
+        self.cards().iter().filter(|card| test(&card.faces)).count()
+    }
+
+    /// Odds for how many cards satisfying `test` appear among `draws` cards drawn from the
+    /// deck as it stands.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if the deck holds fewer than `draws` cards.
+    pub fn draw_odds(&self, draws: usize, test: impl Fn(&T) -> bool) -> GameResult<DrawOdds> {
+        self.ensure_can_draw(draws)?;
+        DrawOdds::hypergeometric(self.size(), self.count_matching(test), draws)
+    }
+
+    /// Probability that `draws` cards meet every requirement at once.
+    ///
+    /// Categories may overlap: a card that passes several tests counts toward each of them.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if the deck holds fewer than `draws` cards.
+    ///
+    /// ```
+    /// use gametools::Deck;
+    /// use gametools::cards::draw_odds::DrawRequirement;
+    /// use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit, standard_52};
+    ///
+    /// let deck = Deck::from_faces("standard", standard_52());
+    /// // at least one ace and at least two hearts in five cards; the ace of hearts is both
+    /// let odds = deck
+    ///     .joint_draw_odds(
+    ///         5,
+    ///         &[
+    ///             DrawRequirement::at_least(|card: &StandardCard| card.rank == Rank::Ace, 1),
+    ///             DrawRequirement::at_least(|card: &StandardCard| card.suit == Suit::Hearts, 2),
+    ///         ],
+    ///     )
+    ///     .unwrap();
+    /// assert!((odds - 0.1252).abs() < 1e-4);
+    /// ```
+    pub fn joint_draw_odds(
+        &self,
+        draws: usize,
+        requirements: &[DrawRequirement<'_, T>],
+    ) -> GameResult<f64> {
+        self.ensure_can_draw(draws)?;
+        // group cards by exactly which requirements they satisfy; cards that satisfy none
+        // are counted separately
+        let mut groups: HashMap<Vec<bool>, usize> = HashMap::new();
+        let mut others = 0;
+        for card in self.cards() {
+            let signature: Vec<bool> = requirements
+                .iter()
+                .map(|req| (req.test)(&card.faces))
+                .collect();
+            if signature.contains(&true) {
+                *groups.entry(signature).or_default() += 1;
+            } else {
+                others += 1;
+            }
+        }
+        let groups: Vec<(Vec<bool>, usize)> = groups.into_iter().collect();
+        let mut counts = vec![0; requirements.len()];
+        let ways = joint_ways(requirements, &groups, others, draws, &mut counts);
+        Ok(ways / choose(self.size(), draws))
+    }
+
+    /// Expected results of drawing `hand_size` cards and taking mulligans while the hand
+    /// holds fewer than `min` or more than `max` cards satisfying `test`. Each mulligan
+    /// shuffles the hand back before redrawing; after `max_mulligans` the hand is kept
+    /// whatever it holds.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if the deck holds fewer than `hand_size` cards.
+    pub fn mulligan_odds(
+        &self,
+        hand_size: usize,
+        rule: MulliganRule,
+        max_mulligans: usize,
+        min: usize,
+        max: usize,
+        test: impl Fn(&T) -> bool,
+    ) -> GameResult<MulliganOdds> {
+        self.ensure_can_draw(hand_size)?;
+        let matching = self.count_matching(test);
+        let max_mulligans = match rule {
+            MulliganRule::Shrinking => max_mulligans.min(hand_size),
+            MulliganRule::London => max_mulligans,
+        };
+
+        let mut odds = MulliganOdds {
+            keep_chance: Vec::with_capacity(max_mulligans + 1),
+            expected_mulligans: 0.0,
+            expected_matches: 0.0,
+            expected_hand_size: 0.0,
+        };
+        let mut reach = 1.0;
+        for mulligans in 0..=max_mulligans {
+            let drawn = match rule {
+                MulliganRule::Shrinking => hand_size - mulligans,
+                MulliganRule::London => hand_size,
+            };
+            let dist = DrawOdds::hypergeometric(self.size(), matching, drawn)?;
+            let last = mulligans == max_mulligans;
+            let (keep, matches) = if last {
+                (1.0, dist.expected())
+            } else {
+                let kept = (min..=max.min(drawn)).map(|k| (k, dist.exactly(k)));
+                kept.fold((0.0, 0.0), |(p, e), (k, pk)| (p + pk, e + k as f64 * pk))
+            };
+            let kept_size = hand_size.saturating_sub(mulligans) as f64;
+            odds.keep_chance.push(reach * keep);
+            odds.expected_mulligans += reach * keep * mulligans as f64;
+            odds.expected_matches += reach * matches;
+            odds.expected_hand_size += reach * keep * kept_size;
+            reach *= 1.0 - keep;
+        }
+        Ok(odds)
+    }
+
+    fn ensure_can_draw(&self, draws: usize) -> GameResult<()> {
+        if draws > self.size() {
+            return Err(CardError::StackTooSmall(self.name.clone()).into());
+        }
+        Ok(())
+    }
+}
+
+/// Ways to draw `draws` cards meeting `requirements`, choosing how many come from each of
+/// `groups` (cards sharing a signature of which requirements they satisfy) and the rest
+/// from `others`. `counts` holds the matches already chosen for each requirement.
+fn joint_ways<T>(
+    requirements: &[DrawRequirement<'_, T>],
+    groups: &[(Vec<bool>, usize)],
+    others: usize,
+    draws: usize,
+    counts: &mut [usize],
+) -> f64 {
+    let Some(((signature, size), rest)) = groups.split_first() else {
+        let met = requirements
+            .iter()
+            .zip(counts.iter())
+            .all(|(req, count)| (req.min..=req.max).contains(count));
+        return if met { choose(others, draws) } else { 0.0 };
+    };
+    let mut total = 0.0;
+    for k in 0..=(*size).min(draws) {
+        let over = requirements
+            .iter()
+            .zip(signature)
+            .zip(counts.iter())
+            .any(|((req, hit), count)| *hit && count + k > req.max);
+        if over {
+            break;
+        }
+        for (count, hit) in counts.iter_mut().zip(signature) {
+            if *hit {
+                *count += k;
+            }
+        }
+        total += choose(*size, k) * joint_ways(requirements, rest, others, draws - k, counts);
+        for (count, hit) in counts.iter_mut().zip(signature) {
+            if *hit {
+                *count -= k;
+            }
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::std_playing_cards::{Rank, StandardCard, Suit, standard_52};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn deck() -> Deck<StandardCard> {
+        Deck::from_faces("standard", standard_52())
+    }
+
+    #[test]
+    fn distribution_sums_to_one() {
+        let odds = deck().draw_odds(13, |c| c.suit == Suit::Spades).unwrap();
+        assert!(close(odds.distribution().iter().sum(), 1.0));
+        assert!(close(odds.expected(), 13.0 * 13.0 / 52.0));
+        assert!(close(odds.at_least(3) + odds.at_most(2), 1.0));
+        assert!(close(odds.between(2, 3), odds.exactly(2) + odds.exactly(3)));
+        assert_eq!(odds.exactly(14), 0.0);
+    }
+
+    #[test]
+    fn known_poker_odds() {
+        let aces = deck().draw_odds(2, |c| c.rank == Rank::Ace).unwrap();
+        assert!(close(aces.exactly(2), 6.0 / 1326.0));
+        let hearts = deck().draw_odds(5, |c| c.suit == Suit::Hearts).unwrap();
+        assert!(close(hearts.exactly(5), 1287.0 / 2_598_960.0));
+    }
+
+    #[test]
+    fn joint_odds_match_hand_counts() {
+        // a full house's shape: exactly three of one rank and two of another
+        let odds = deck()
+            .joint_draw_odds(
+                5,
+                &[
+                    DrawRequirement::exactly(|c: &StandardCard| c.rank == Rank::King, 3),
+                    DrawRequirement::exactly(|c: &StandardCard| c.rank == Rank::Two, 2),
+                ],
+            )
+            .unwrap();
+        assert!(close(odds, 24.0 / 2_598_960.0));
+
+        let single = deck()
+            .joint_draw_odds(
+                5,
+                &[DrawRequirement::at_least(
+                    |c: &StandardCard| c.rank == Rank::Ace,
+                    1,
+                )],
+            )
+            .unwrap();
+        let direct = deck().draw_odds(5, |c| c.rank == Rank::Ace).unwrap();
+        assert!(close(single, direct.at_least(1)));
+        assert!(close(deck().joint_draw_odds(5, &[]).unwrap(), 1.0));
+    }
+
+    #[test]
+    fn overlapping_requirements_match_brute_force() {
+        let cards: Vec<StandardCard> = standard_52()
+            .into_iter()
+            .filter(|c| matches!(c.rank, Rank::Ace | Rank::King | Rank::Queen))
+            .collect();
+        let small = Deck::from_faces("faces", cards.clone());
+        let ace = |c: &StandardCard| c.rank == Rank::Ace;
+        let heart = |c: &StandardCard| c.suit == Suit::Hearts;
+        let red = |c: &StandardCard| matches!(c.suit, Suit::Hearts | Suit::Diamonds);
+        let odds = small
+            .joint_draw_odds(
+                4,
+                &[
+                    DrawRequirement::at_least(ace, 1),
+                    DrawRequirement::between(heart, 1, 2),
+                    DrawRequirement::at_most(red, 2),
+                ],
+            )
+            .unwrap();
+
+        let n = cards.len();
+        let (mut hits, mut total) = (0, 0);
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        let hand = [&cards[a], &cards[b], &cards[c], &cards[d]];
+                        let count = |test: &dyn Fn(&StandardCard) -> bool| {
+                            hand.iter().filter(|card| test(card)).count()
+                        };
+                        total += 1;
+                        if count(&ace) >= 1 && (1..=2).contains(&count(&heart)) && count(&red) <= 2
+                        {
+                            hits += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(close(odds, f64::from(hits) / f64::from(total)));
+    }
+
+    #[test]
+    fn mulligans_shift_expectations() {
+        let red = |c: &StandardCard| matches!(c.suit, Suit::Hearts | Suit::Diamonds);
+        let none = deck()
+            .mulligan_odds(7, MulliganRule::Shrinking, 0, 2, 5, red)
+            .unwrap();
+        assert_eq!(none.keep_chance, vec![1.0]);
+        assert!(close(none.expected_matches, 3.5));
+
+        let shrinking = deck()
+            .mulligan_odds(7, MulliganRule::Shrinking, 2, 2, 5, red)
+            .unwrap();
+        assert!(close(shrinking.keep_chance.iter().sum(), 1.0));
+        assert!(shrinking.expected_mulligans > 0.0);
+        assert!(shrinking.expected_hand_size < 7.0);
+
+        let london = deck()
+            .mulligan_odds(7, MulliganRule::London, 2, 2, 5, red)
+            .unwrap();
+        assert!(close(
+            london.expected_hand_size + london.expected_mulligans,
+            7.0
+        ));
+        assert!(london.keep_chance[0] > london.keep_chance[1]);
+    }
+
+    #[test]
+    fn oversized_draws_are_rejected() {
+        let small = Deck::from_faces("small", standard_52().into_iter().take(3));
+        assert_eq!(
+            small.draw_odds(4, |_| true),
+            Err(CardError::StackTooSmall("small".to_string()).into())
+        );
+        assert!(DrawOdds::hypergeometric(5, 6, 1).is_err());
+    }
+}
@@ -0,0 +1,757 @@
+//! # Verifiable Shuffles
+//!
+//! When a card game runs peer-to-peer, nobody can trust whoever shuffles the [`Deck`]. This
+//! module provides a commit-reveal protocol so that the deck order is derived jointly:
+//!
+//! 1. A [`ShuffleSession`] fixes the deck's current card order (by uuid) before anything
+//!    else happens, so the holder of the deck cannot arrange it after seeing the seeds.
+//! 2. Every [`Participant`] picks a secret seed and publishes a [`Commitment`] to it.
+//! 3. Once the session holds every commitment, participants publish a [`Reveal`] of their
+//!    seed, which the session checks against the earlier commitment.
+//! 4. The starting order and all seeds are hashed together into one combined seed, which
+//!    drives a deterministic Fisher-Yates permutation applied with [`Deck::rearrange`].
+//!
+//! No participant can steer the result without knowing every other seed in advance, and
+//! nobody can change their seed after seeing the others. The last participant to reveal
+//! can still learn the outcome first and refuse to reveal, aborting the shuffle; games
+//! should treat a withheld reveal as a forfeit (or restart without that peer) rather than
+//! simply trying again. The resulting [`ShuffleTranscript`] can be shared and replayed by
+//! anyone to confirm that a dealt [`Card`] (identified by its uuid) really came from the
+//! agreed position in the deck.
+//!
+//! Note that commit-reveal makes the shuffle *fair*, not *secret*: after the reveal every
+//! participant can compute the full deck order. Games that must hide cards from peers need
+//! an additional layer (such as commutative encryption) on top of this one.
+//!
+//! This module needs the `fair-shuffle` feature, which pulls in `sha2` for hashing.
+//!
+//! All participants can run in the same process, which makes it straightforward to simulate
+//! a networked game locally.
+//!
+//! ```
+//! use gametools::cards::fair_shuffle::{Participant, ShuffleSession};
+//! use gametools::cards::std_playing_cards::{StandardCard, standard_52};
+//! use gametools::{Card, Deck, TakeCard};
+//!
+//! let mut deck: Deck<StandardCard> = Deck::from_faces("table", standard_52());
+//! let peers = [Participant::new("ann"), Participant::new("bob")];
+//!
+//! let mut session = ShuffleSession::for_deck(&["ann", "bob"], &deck).unwrap();
+//! for peer in &peers {
+//!     session.commit(peer.name(), peer.commitment()).unwrap();
+//! }
+//! for peer in &peers {
+//!     session.reveal(peer.reveal()).unwrap();
+//! }
+//! let transcript = session.shuffle_deck(&mut deck).unwrap();
+//!
+//! // later, anyone holding the transcript can audit a dealt card
+//! let first = deck.take_card().unwrap();
+//! assert!(transcript.verify_deal(&first, 0).is_ok());
+//! assert!(transcript.verify_deal(&first, 1).is_err());
+//! ```
+
+use crate::cards::{Card, CardFaces, Deck};
+use crate::{GameResult, ShuffleError};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use uuid::Uuid;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A participant's secret contribution to a shuffle.
+pub type Seed = [u8; 32];
+
+/// Domain separator mixed into every hash so digests cannot be confused with other uses.
+const DOMAIN: &[u8] = b"gametools/fair_shuffle/v1";
+
+fn hasher(purpose: &[u8]) -> Sha256 {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update(purpose);
+    hasher
+}
+
+fn hash_named_seed(hasher: &mut Sha256, name: &str, seed: &Seed) {
+    hasher.update((name.len() as u64).to_le_bytes());
+    hasher.update(name.as_bytes());
+    hasher.update(seed);
+}
+
+/// A binding, hiding hash of one participant's seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    /// Compute the commitment that `name` publishes for `seed`.
+    ///
+    /// The name is part of the hash, so one participant cannot replay another's commitment.
+    #[must_use]
+    pub fn compute(name: &str, seed: &Seed) -> Self {
+        let mut hasher = hasher(b"commit");
+        hash_named_seed(&mut hasher, name, seed);
+        Self(hasher.finalize().into())
+    }
+
+    /// The raw digest bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A participant's seed, published once every commitment is in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Reveal {
+    pub participant: String,
+    pub seed: Seed,
+}
+
+impl Reveal {
+    /// The commitment this reveal should match.
+    #[must_use]
+    pub fn commitment(&self) -> Commitment {
+        Commitment::compute(&self.participant, &self.seed)
+    }
+}
+
+/// One party to a shuffle, holding its secret seed until the reveal phase.
+///
+/// In a networked game each peer owns its own `Participant`; in tests several can live in
+/// the same process to simulate the peers.
+#[derive(Debug, Clone)]
+pub struct Participant {
+    name: String,
+    seed: Seed,
+}
+
+impl Participant {
+    /// Create a participant with a freshly generated random seed.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        let mut seed = Seed::default();
+        rand::rng().fill(&mut seed);
+        Self::with_seed(name, seed)
+    }
+
+    /// Create a participant with a known seed, e.g. for reproducible tests.
+    #[must_use]
+    pub fn with_seed(name: &str, seed: Seed) -> Self {
+        Self {
+            name: name.to_string(),
+            seed,
+        }
+    }
+
+    /// The participant's name as listed in the [`ShuffleSession`].
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The commitment to publish during the commit phase.
+    #[must_use]
+    pub fn commitment(&self) -> Commitment {
+        Commitment::compute(&self.name, &self.seed)
+    }
+
+    /// The seed to publish during the reveal phase.
+    #[must_use]
+    pub fn reveal(&self) -> Reveal {
+        Reveal {
+            participant: self.name.clone(),
+            seed: self.seed,
+        }
+    }
+}
+
+/// Collects commitments and reveals for one shuffle.
+///
+/// Every peer can run its own session over the messages it receives; because the derived
+/// order depends only on the participant list, the starting deck order, and the revealed
+/// seeds, honest peers always agree on the result.
+#[derive(Debug, Clone)]
+pub struct ShuffleSession {
+    participants: Vec<String>,
+    deck_order: Vec<Uuid>,
+    commitments: Vec<Option<Commitment>>,
+    seeds: Vec<Option<Seed>>,
+}
+
+impl ShuffleSession {
+    /// Start a session for the listed participants over a deck whose cards are currently
+    /// in `deck_order` (bottom to top, by uuid). The participant order is part of the
+    /// protocol, so every peer must use the same list, and every peer should check the
+    /// deck order before committing.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::NoParticipants`] if `participants` is empty.
+    /// - [`ShuffleError::DuplicateParticipant`] if a name appears twice.
+    pub fn new(participants: &[&str], deck_order: &[Uuid]) -> GameResult<Self> {
+        if participants.is_empty() {
+            return Err(ShuffleError::NoParticipants.into());
+        }
+        for (idx, name) in participants.iter().enumerate() {
+            if participants[..idx].contains(name) {
+                return Err(ShuffleError::DuplicateParticipant((*name).to_string()).into());
+            }
+        }
+        Ok(Self {
+            participants: participants.iter().map(ToString::to_string).collect(),
+            deck_order: deck_order.to_vec(),
+            commitments: vec![None; participants.len()],
+            seeds: vec![None; participants.len()],
+        })
+    }
+
+    /// Start a session over `deck` as it stands now; see [`ShuffleSession::new`].
+    ///
+    /// # Errors
+    /// As for [`ShuffleSession::new`].
+    pub fn for_deck<T: CardFaces>(participants: &[&str], deck: &Deck<T>) -> GameResult<Self> {
+        let order: Vec<Uuid> = deck.cards().iter().map(|card| card.uuid).collect();
+        Self::new(participants, &order)
+    }
+
+    /// Run a complete session in-process for `peers` and shuffle `deck` with the result.
+    ///
+    /// Handy for local games and for simulating networked peers in tests.
+    ///
+    /// # Errors
+    /// As for [`ShuffleSession::new`].
+    pub fn run_local<T: CardFaces>(
+        peers: &[Participant],
+        deck: &mut Deck<T>,
+    ) -> GameResult<ShuffleTranscript> {
+        let names: Vec<&str> = peers.iter().map(Participant::name).collect();
+        let mut session = Self::for_deck(&names, deck)?;
+        for peer in peers {
+            session.commit(peer.name(), peer.commitment())?;
+        }
+        for peer in peers {
+            session.reveal(peer.reveal())?;
+        }
+        session.shuffle_deck(deck)
+    }
+
+    /// The participants in protocol order.
+    #[must_use]
+    pub fn participants(&self) -> &[String] {
+        &self.participants
+    }
+
+    /// The card uuids, in deck order, that the session was started with.
+    #[must_use]
+    pub fn deck_order(&self) -> &[Uuid] {
+        &self.deck_order
+    }
+
+    fn index_of(&self, name: &str) -> GameResult<usize> {
+        self.participants
+            .iter()
+            .position(|p| p == name)
+            .ok_or_else(|| ShuffleError::UnknownParticipant(name.to_string()).into())
+    }
+
+    /// Record the commitment published by `name`.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::UnknownParticipant`] if `name` is not in the session.
+    /// - [`ShuffleError::AlreadyCommitted`] if `name` has already committed.
+    pub fn commit(&mut self, name: &str, commitment: Commitment) -> GameResult<()> {
+        let idx = self.index_of(name)?;
+        if self.commitments[idx].is_some() {
+            return Err(ShuffleError::AlreadyCommitted(name.to_string()).into());
+        }
+        self.commitments[idx] = Some(commitment);
+        Ok(())
+    }
+
+    /// Check a revealed seed against its commitment and record it.
+    ///
+    /// Reveals are refused until every commitment is in; otherwise a late committer could
+    /// pick a seed after seeing the others.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::UnknownParticipant`] if the revealer is not in the session.
+    /// - [`ShuffleError::AwaitingCommitments`] if anyone has yet to commit.
+    /// - [`ShuffleError::AlreadyRevealed`] if the revealer has already revealed.
+    /// - [`ShuffleError::CommitmentMismatch`] if the seed does not match the commitment.
+    pub fn reveal(&mut self, reveal: Reveal) -> GameResult<()> {
+        let idx = self.index_of(&reveal.participant)?;
+        let waiting = self.pending_commitments();
+        if !waiting.is_empty() {
+            return Err(ShuffleError::AwaitingCommitments(waiting).into());
+        }
+        if self.seeds[idx].is_some() {
+            return Err(ShuffleError::AlreadyRevealed(reveal.participant).into());
+        }
+        if self.commitments[idx] != Some(reveal.commitment()) {
+            return Err(ShuffleError::CommitmentMismatch(reveal.participant).into());
+        }
+        self.seeds[idx] = Some(reveal.seed);
+        Ok(())
+    }
+
+    /// Names of participants who have not committed yet.
+    #[must_use]
+    pub fn pending_commitments(&self) -> Vec<String> {
+        self.pending(&self.commitments)
+    }
+
+    /// Names of participants who have not revealed their seed yet.
+    #[must_use]
+    pub fn pending_reveals(&self) -> Vec<String> {
+        self.pending(&self.seeds)
+    }
+
+    fn pending<V>(&self, slots: &[Option<V>]) -> Vec<String> {
+        self.participants
+            .iter()
+            .zip(slots)
+            .filter(|(_, slot)| slot.is_none())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Whether every participant has committed and revealed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.seeds.iter().all(Option::is_some)
+    }
+
+    /// Derive the permutation for the committed deck.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::AwaitingReveals`] if any seed is still outstanding.
+    pub fn permutation(&self) -> GameResult<Vec<usize>> {
+        let transcript = self.transcript()?;
+        Ok(transcript.permutation(transcript.initial_order.len()))
+    }
+
+    /// Shuffle `deck` with the jointly derived permutation and return the transcript
+    /// needed to audit the deal later.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::OrderMismatch`] if `deck` is not in the order the session was
+    ///   started with.
+    /// - [`ShuffleError::AwaitingReveals`] if any seed is still outstanding.
+    pub fn shuffle_deck<T: CardFaces>(&self, deck: &mut Deck<T>) -> GameResult<ShuffleTranscript> {
+        let unchanged = deck.cards().len() == self.deck_order.len()
+            && deck
+                .cards()
+                .iter()
+                .zip(&self.deck_order)
+                .all(|(card, uuid)| card.uuid == *uuid);
+        if !unchanged {
+            return Err(ShuffleError::OrderMismatch.into());
+        }
+        let transcript = self.transcript()?;
+        deck.rearrange(&transcript.permutation(transcript.initial_order.len()))?;
+        Ok(transcript)
+    }
+
+    fn transcript(&self) -> GameResult<ShuffleTranscript> {
+        let waiting = self.pending_reveals();
+        if !waiting.is_empty() {
+            return Err(ShuffleError::AwaitingReveals(waiting).into());
+        }
+        Ok(ShuffleTranscript {
+            participants: self.participants.clone(),
+            commitments: self.commitments.iter().flatten().copied().collect(),
+            seeds: self.seeds.iter().flatten().copied().collect(),
+            initial_order: self.deck_order.clone(),
+        })
+    }
+}
+
+/// Everything needed to replay and audit a completed shuffle.
+///
+/// A transcript can be serialized and handed to any peer or to a third party. Auditors
+/// should compare [`ShuffleTranscript::commitments`] with the commitments they saw during
+/// the game before trusting the other checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShuffleTranscript {
+    participants: Vec<String>,
+    commitments: Vec<Commitment>,
+    seeds: Vec<Seed>,
+    initial_order: Vec<Uuid>,
+}
+
+impl ShuffleTranscript {
+    /// The participants in protocol order.
+    #[must_use]
+    pub fn participants(&self) -> &[String] {
+        &self.participants
+    }
+
+    /// The commitment recorded for `name`, if they took part.
+    #[must_use]
+    pub fn commitment(&self, name: &str) -> Option<Commitment> {
+        let idx = self.participants.iter().position(|p| p == name)?;
+        self.commitments.get(idx).copied()
+    }
+
+    /// All recorded commitments in protocol order.
+    #[must_use]
+    pub fn commitments(&self) -> &[Commitment] {
+        &self.commitments
+    }
+
+    /// Card uuids in deck order before the shuffle.
+    #[must_use]
+    pub fn initial_order(&self) -> &[Uuid] {
+        &self.initial_order
+    }
+
+    /// Check every recorded seed against its commitment.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::AwaitingReveals`] if the transcript is missing seeds.
+    /// - [`ShuffleError::CommitmentMismatch`] naming the first participant whose seed
+    ///   does not match.
+    pub fn verify(&self) -> GameResult<()> {
+        let complete = self.seeds.len().min(self.commitments.len());
+        if complete < self.participants.len() {
+            let missing = self.participants[complete..].to_vec();
+            return Err(ShuffleError::AwaitingReveals(missing).into());
+        }
+        let records = self
+            .participants
+            .iter()
+            .zip(&self.commitments)
+            .zip(&self.seeds);
+        for ((name, commitment), seed) in records {
+            if Commitment::compute(name, seed) != *commitment {
+                return Err(ShuffleError::CommitmentMismatch(name.clone()).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The combined seed that drives the permutation, covering the starting deck order
+    /// and every seed.
+    #[must_use]
+    pub fn combined_seed(&self) -> Seed {
+        let mut hasher = hasher(b"combine");
+        hasher.update((self.initial_order.len() as u64).to_le_bytes());
+        for uuid in &self.initial_order {
+            hasher.update(uuid.as_bytes());
+        }
+        for (name, seed) in self.participants.iter().zip(&self.seeds) {
+            hash_named_seed(&mut hasher, name, seed);
+        }
+        hasher.finalize().into()
+    }
+
+    /// The permutation for a deck of `len` cards: position `i` of the shuffled deck holds
+    /// the card previously at `permutation[i]`.
+    #[must_use]
+    pub fn permutation(&self, len: usize) -> Vec<usize> {
+        let mut stream = SeedStream::new(self.combined_seed());
+        let mut order: Vec<usize> = (0..len).collect();
+        for i in (1..len).rev() {
+            let j = stream.below(i as u64 + 1) as usize;
+            order.swap(i, j);
+        }
+        order
+    }
+
+    /// Card uuids in deck order after the shuffle. The last uuid is drawn first.
+    #[must_use]
+    pub fn shuffled_order(&self) -> Vec<Uuid> {
+        self.permutation(self.initial_order.len())
+            .into_iter()
+            .map(|idx| self.initial_order[idx])
+            .collect()
+    }
+
+    /// The draw position (0 for the first card taken from the deck) of the card with `uuid`.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::UnknownCard`] if the card was not in the shuffled deck.
+    pub fn deal_position(&self, uuid: Uuid) -> GameResult<usize> {
+        let shuffled = self.shuffled_order();
+        shuffled
+            .iter()
+            .rev()
+            .position(|id| *id == uuid)
+            .ok_or_else(|| ShuffleError::UnknownCard(uuid).into())
+    }
+
+    /// Confirm that `card` was the `position`-th card drawn from the shuffled deck and that
+    /// every seed matches its commitment.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::CommitmentMismatch`] if the transcript itself does not verify.
+    /// - [`ShuffleError::UnknownCard`] if the card was not in the shuffled deck.
+    /// - [`ShuffleError::WrongDealPosition`] if the card belongs at a different position.
+    pub fn verify_deal<T: CardFaces>(&self, card: &Card<T>, position: usize) -> GameResult<()> {
+        self.verify()?;
+        let expected = self.deal_position(card.uuid)?;
+        if expected != position {
+            return Err(ShuffleError::WrongDealPosition {
+                uuid: card.uuid,
+                expected,
+                claimed: position,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Confirm that `deck` still holds exactly the undealt tail of the shuffle, in order.
+    ///
+    /// # Errors
+    /// - [`ShuffleError::CommitmentMismatch`] if the transcript itself does not verify.
+    /// - [`ShuffleError::DeckMismatch`] if the deck is larger than the shuffle or out of order.
+    pub fn verify_deck<T: CardFaces>(&self, deck: &Deck<T>) -> GameResult<()> {
+        self.verify()?;
+        let shuffled = self.shuffled_order();
+        let remaining = deck.cards();
+        let matches = remaining.len() <= shuffled.len()
+            && remaining
+                .iter()
+                .zip(&shuffled)
+                .all(|(card, id)| card.uuid == *id);
+        if matches {
+            Ok(())
+        } else {
+            Err(ShuffleError::DeckMismatch {
+                expected: shuffled.len(),
+                actual: remaining.len(),
+            }
+            .into())
+        }
+    }
+}
+
+/// Deterministic byte stream built from SHA-256 in counter mode.
+///
+/// Unlike a library RNG, its output is fixed by this module, so peers running different
+/// versions of `rand` still derive the same permutation.
+struct SeedStream {
+    seed: Seed,
+    counter: u64,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl SeedStream {
+    fn new(seed: Seed) -> Self {
+        Self {
+            seed,
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.used + 8 > self.block.len() {
+            let mut hasher = hasher(b"stream");
+            hasher.update(self.seed);
+            hasher.update(self.counter.to_le_bytes());
+            self.block = hasher.finalize().into();
+            self.counter += 1;
+            self.used = 0;
+        }
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.block[self.used..self.used + 8]);
+        self.used += 8;
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Uniform value in `0..bound`, using rejection sampling to avoid modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return value % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::std_playing_cards::{StandardCard, standard_52};
+    use crate::{GameError, TakeCard};
+
+    fn peers() -> Vec<Participant> {
+        vec![
+            Participant::with_seed("ann", [1; 32]),
+            Participant::with_seed("bob", [2; 32]),
+            Participant::with_seed("cy", [3; 32]),
+        ]
+    }
+
+    fn fresh_deck() -> Deck<StandardCard> {
+        Deck::from_faces("table", standard_52())
+    }
+
+    #[test]
+    fn peers_derive_the_same_order_independently() {
+        let mut first = fresh_deck();
+        let mut second = Deck::from_cards("copy", first.cards().to_vec());
+        let a = ShuffleSession::run_local(&peers(), &mut first).unwrap();
+        let b = ShuffleSession::run_local(&peers(), &mut second).unwrap();
+        assert_eq!(a, b);
+        let order =
+            |deck: &Deck<StandardCard>| deck.cards().iter().map(|c| c.uuid).collect::<Vec<_>>();
+        assert_eq!(order(&first), order(&second));
+        assert_ne!(order(&first), a.initial_order());
+        assert_eq!(order(&first), a.shuffled_order());
+    }
+
+    #[test]
+    fn every_seed_changes_the_result() {
+        let base = ShuffleSession::run_local(&peers(), &mut fresh_deck()).unwrap();
+        let mut altered = peers();
+        altered[2] = Participant::with_seed("cy", [4; 32]);
+        let other = ShuffleSession::run_local(&altered, &mut fresh_deck()).unwrap();
+        assert_ne!(base.permutation(52), other.permutation(52));
+
+        let mut perm = base.permutation(52);
+        perm.sort_unstable();
+        assert_eq!(perm, (0..52).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reveals_wait_for_all_commitments() {
+        let peers = peers();
+        let mut deck = fresh_deck();
+        let mut session = ShuffleSession::for_deck(&["ann", "bob", "cy"], &deck).unwrap();
+        session.commit("ann", peers[0].commitment()).unwrap();
+        assert_eq!(
+            session.reveal(peers[0].reveal()),
+            Err(GameError::ShuffleError(ShuffleError::AwaitingCommitments(
+                vec!["bob".into(), "cy".into()]
+            )))
+        );
+        assert!(session.commit("ann", peers[0].commitment()).is_err());
+        assert!(session.commit("dee", peers[0].commitment()).is_err());
+        session.commit("bob", peers[1].commitment()).unwrap();
+        session.commit("cy", peers[2].commitment()).unwrap();
+        session.reveal(peers[0].reveal()).unwrap();
+        assert!(session.reveal(peers[0].reveal()).is_err());
+        assert!(session.shuffle_deck(&mut deck).is_err());
+        assert_eq!(session.pending_reveals(), ["bob", "cy"]);
+        assert!(!session.is_complete());
+    }
+
+    #[test]
+    fn changed_seed_is_rejected() {
+        let peers = peers();
+        let mut session = ShuffleSession::new(&["ann", "bob", "cy"], &[]).unwrap();
+        for peer in &peers {
+            session.commit(peer.name(), peer.commitment()).unwrap();
+        }
+        let mut cheat = peers[1].reveal();
+        cheat.seed[0] ^= 1;
+        assert_eq!(
+            session.reveal(cheat),
+            Err(GameError::ShuffleError(ShuffleError::CommitmentMismatch(
+                "bob".into()
+            )))
+        );
+        // borrowing another participant's commitment does not help either
+        let stolen = Reveal {
+            participant: "bob".into(),
+            seed: [1; 32],
+        };
+        assert!(session.reveal(stolen).is_err());
+    }
+
+    #[test]
+    fn dealt_cards_verify_against_transcript() {
+        let mut deck = fresh_deck();
+        let transcript = ShuffleSession::run_local(&peers(), &mut deck).unwrap();
+        let dealt: Vec<_> = (0..5).map(|_| deck.take_card().unwrap()).collect();
+        for (pos, card) in dealt.iter().enumerate() {
+            transcript.verify_deal(card, pos).unwrap();
+        }
+        transcript.verify_deck(&deck).unwrap();
+        assert_eq!(
+            transcript.verify_deal(&dealt[1], 3),
+            Err(GameError::ShuffleError(ShuffleError::WrongDealPosition {
+                uuid: dealt[1].uuid,
+                expected: 1,
+                claimed: 3,
+            }))
+        );
+        let stranger = Card::new_card(StandardCard::new_card(
+            crate::cards::Rank::Ace,
+            crate::cards::Suit::Spades,
+        ));
+        assert!(transcript.verify_deal(&stranger, 0).is_err());
+
+        deck.shuffle();
+        assert!(transcript.verify_deck(&deck).is_err());
+    }
+
+    #[test]
+    fn tampered_transcript_fails_verification() {
+        let mut transcript = ShuffleSession::run_local(&peers(), &mut fresh_deck()).unwrap();
+        transcript.verify().unwrap();
+        transcript.seeds[0][5] ^= 0xff;
+        assert!(transcript.verify().is_err());
+        assert!(ShuffleSession::new(&[], &[]).is_err());
+        assert!(ShuffleSession::new(&["ann", "ann"], &[]).is_err());
+    }
+
+    #[test]
+    fn deck_order_is_fixed_before_commitments() {
+        let peers = peers();
+        let mut deck = fresh_deck();
+        let names = ["ann", "bob", "cy"];
+        let mut session = ShuffleSession::for_deck(&names, &deck).unwrap();
+        for peer in &peers {
+            session.commit(peer.name(), peer.commitment()).unwrap();
+        }
+        for peer in &peers {
+            session.reveal(peer.reveal()).unwrap();
+        }
+
+        // the deck holder rearranges the deck once the seeds are known
+        let mut stacked = Deck::from_cards("table", deck.cards().to_vec());
+        stacked
+            .rearrange(&(0..52).rev().collect::<Vec<_>>())
+            .unwrap();
+        assert_eq!(
+            session.shuffle_deck(&mut stacked),
+            Err(GameError::ShuffleError(ShuffleError::OrderMismatch))
+        );
+        let transcript = session.shuffle_deck(&mut deck).unwrap();
+        assert_eq!(transcript.initial_order(), session.deck_order());
+
+        // the same seeds over a different starting order give a different permutation
+        let mut other = ShuffleSession::new(&names, &session.deck_order()[1..]).unwrap();
+        for peer in &peers {
+            other.commit(peer.name(), peer.commitment()).unwrap();
+        }
+        for peer in &peers {
+            other.reveal(peer.reveal()).unwrap();
+        }
+        assert_ne!(
+            other.transcript().unwrap().combined_seed(),
+            transcript.combined_seed()
+        );
+    }
+}
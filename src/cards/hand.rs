@@ -24,7 +24,10 @@
 //! let top = hand.take_card().unwrap();
 //! assert_eq!(top.faces.0, 3);
 //! ```
-use crate::cards::{AddCard, Card, CardCollection, CardFaces, TakeCard};
+use crate::cards::{AddCard, Card, CardCollection, CardFaces, TakeCard, rearrange_cards};
+use crate::{CardError, GameResult};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -77,6 +80,149 @@ impl<T: CardFaces> Hand<T> {
     }
 }
 
+/*
+ * SORTING, GROUPING, AND QUERYING
+ *
+ * Every operation here is stable: sorting keeps cards that compare equal in their current
+ * relative order, and removing cards leaves the rest in the order they were in.
+ */
+impl<T: CardFaces> Hand<T> {
+    /// Sort the hand in place by [`CardFaces::compare`], lowest first.
+    ///
+    /// ```
+    /// use gametools::{AddCard, Card, Hand};
+    /// use gametools::cards::{Rank, StandardCard, Suit};
+    ///
+    /// let mut hand = Hand::<StandardCard>::from_text("ann", "KH 2S 9D").unwrap();
+    /// hand.sort();
+    /// let ranks: Vec<Rank> = hand.cards().iter().map(|c| c.faces.rank).collect();
+    /// assert_eq!(ranks, [Rank::Two, Rank::Nine, Rank::King]);
+    /// ```
+    pub fn sort(&mut self) {
+        self.cards.sort_by(|a, b| a.faces.compare(&b.faces));
+    }
+
+    /// Sort the hand in place with a custom comparison.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&Card<T>, &Card<T>) -> Ordering) {
+        self.cards.sort_by(|a, b| compare(a, b));
+    }
+
+    /// Sort the hand in place by a key extracted from each card.
+    pub fn sort_by_key<K: Ord>(&mut self, key: impl FnMut(&Card<T>) -> K) {
+        self.cards.sort_by_key(key);
+    }
+
+    /// Bucket the cards by a key. Each bucket lists its cards in hand order.
+    ///
+    /// ```
+    /// use gametools::Hand;
+    /// use gametools::cards::{StandardCard, Suit};
+    ///
+    /// let hand = Hand::<StandardCard>::from_text("ann", "KH 2S 9H").unwrap();
+    /// let by_suit = hand.group_by(|card| card.faces.suit);
+    /// assert_eq!(by_suit[&Suit::Hearts].len(), 2);
+    /// assert_eq!(by_suit[&Suit::Spades].len(), 1);
+    /// ```
+    #[must_use]
+    pub fn group_by<K: Ord>(
+        &self,
+        mut key: impl FnMut(&Card<T>) -> K,
+    ) -> BTreeMap<K, Vec<&Card<T>>> {
+        let mut groups: BTreeMap<K, Vec<&Card<T>>> = BTreeMap::new();
+        for card in &self.cards {
+            groups.entry(key(card)).or_default().push(card);
+        }
+        groups
+    }
+
+    /// Positions of every card satisfying `predicate`, in hand order.
+    #[must_use]
+    pub fn positions_where(&self, mut predicate: impl FnMut(&Card<T>) -> bool) -> Vec<usize> {
+        self.cards
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| predicate(card))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Remove and return the card at `index`, if there is one.
+    pub fn take_at(&mut self, index: usize) -> Option<Card<T>> {
+        (index < self.cards.len()).then(|| self.cards.remove(index))
+    }
+
+    /// Remove and return the first card satisfying `predicate`.
+    pub fn take_where(&mut self, predicate: impl FnMut(&Card<T>) -> bool) -> Option<Card<T>> {
+        let idx = self.cards.iter().position(predicate)?;
+        Some(self.cards.remove(idx))
+    }
+
+    /// Remove and return every card satisfying `predicate`, in hand order.
+    ///
+    /// ```
+    /// use gametools::{CardCollection, Hand};
+    /// use gametools::cards::{Rank, StandardCard};
+    ///
+    /// let mut hand = Hand::<StandardCard>::from_text("ann", "AS KH AD 9C").unwrap();
+    /// let aces = hand.take_all_where(|card| card.faces.rank == Rank::Ace);
+    /// assert_eq!(aces.len(), 2);
+    /// assert_eq!(hand.size(), 2);
+    /// ```
+    pub fn take_all_where(&mut self, mut predicate: impl FnMut(&Card<T>) -> bool) -> Vec<Card<T>> {
+        let (taken, kept) = std::mem::take(&mut self.cards)
+            .into_iter()
+            .partition(|card| predicate(card));
+        self.cards = kept;
+        taken
+    }
+
+    /// Move the card at `from` so it ends up at position `to`, shifting the cards between.
+    ///
+    /// # Errors
+    /// - [`CardError::InvalidIndex`] if either position is past the end of the hand.
+    pub fn move_card(&mut self, from: usize, to: usize) -> GameResult<()> {
+        let len = self.cards.len();
+        if let Some(bad) = [from, to].into_iter().find(|&idx| idx >= len) {
+            return Err(CardError::InvalidIndex(bad).into());
+        }
+        let card = self.cards.remove(from);
+        self.cards.insert(to, card);
+        Ok(())
+    }
+
+    /// Swap the cards at two positions.
+    ///
+    /// # Errors
+    /// - [`CardError::InvalidIndex`] if either position is past the end of the hand.
+    pub fn swap(&mut self, a: usize, b: usize) -> GameResult<()> {
+        let len = self.cards.len();
+        if let Some(bad) = [a, b].into_iter().find(|&idx| idx >= len) {
+            return Err(CardError::InvalidIndex(bad).into());
+        }
+        self.cards.swap(a, b);
+        Ok(())
+    }
+
+    /// Rearrange the hand so that new position `i` holds the card previously at `order[i]`.
+    ///
+    /// # Errors
+    /// - [`CardError::InvalidArrangement`] unless `order` lists every current position
+    ///   exactly once. The hand is left unchanged.
+    ///
+    /// ```
+    /// use gametools::Hand;
+    /// use gametools::cards::{Rank, StandardCard};
+    ///
+    /// let mut hand = Hand::<StandardCard>::from_text("ann", "2C 3C 4C").unwrap();
+    /// hand.rearrange(&[2, 0, 1]).unwrap();
+    /// let ranks: Vec<Rank> = hand.cards().iter().map(|c| c.faces.rank).collect();
+    /// assert_eq!(ranks, [Rank::Four, Rank::Two, Rank::Three]);
+    /// ```
+    pub fn rearrange(&mut self, order: &[usize]) -> GameResult<()> {
+        rearrange_cards(&mut self.cards, order)
+    }
+}
+
 impl<T: CardFaces> CardCollection for Hand<T> {
     fn size(&self) -> usize {
         self.cards.len()
@@ -178,6 +324,71 @@ mod tests {
         assert!(hand.take_card().is_none());
     }
 
+    fn ids(hand: &Hand<StubFaces>) -> Vec<u8> {
+        hand.cards.iter().map(|c| c.faces.id).collect()
+    }
+
+    fn hand_of(ids: &[u8]) -> Hand<StubFaces> {
+        let mut hand = Hand::<StubFaces>::new("bob");
+        for &id in ids {
+            hand.add_card(make_card(id));
+        }
+        hand
+    }
+
+    #[test]
+    fn sorting_is_stable_for_equal_keys() {
+        let mut hand = hand_of(&[21, 12, 11, 22, 13]);
+        hand.sort_by_key(|card| card.faces.id / 10);
+        assert_eq!(ids(&hand), vec![12, 11, 13, 21, 22]);
+        hand.sort();
+        assert_eq!(ids(&hand), vec![11, 12, 13, 21, 22]);
+        hand.sort_by(|a, b| b.faces.id.cmp(&a.faces.id));
+        assert_eq!(ids(&hand), vec![22, 21, 13, 12, 11]);
+    }
+
+    #[test]
+    fn group_and_take_by_predicate_keep_hand_order() {
+        let mut hand = hand_of(&[5, 2, 8, 3, 6]);
+        let groups = hand.group_by(|card| card.faces.id % 2);
+        let evens: Vec<u8> = groups[&0].iter().map(|c| c.faces.id).collect();
+        assert_eq!(evens, vec![2, 8, 6]);
+        assert_eq!(hand.positions_where(|c| c.faces.id > 4), vec![0, 2, 4]);
+
+        assert_eq!(hand.take_where(|c| c.faces.id > 5).unwrap().faces.id, 8);
+        let taken: Vec<u8> = hand
+            .take_all_where(|c| c.faces.id % 2 == 1)
+            .iter()
+            .map(|c| c.faces.id)
+            .collect();
+        assert_eq!(taken, vec![5, 3]);
+        assert_eq!(ids(&hand), vec![2, 6]);
+        assert!(hand.take_where(|c| c.faces.id == 9).is_none());
+    }
+
+    #[test]
+    fn take_at_and_reordering_by_position() {
+        let mut hand = hand_of(&[1, 2, 3, 4]);
+        assert_eq!(hand.take_at(1).unwrap().faces.id, 2);
+        assert!(hand.take_at(3).is_none());
+
+        hand.move_card(0, 2).unwrap();
+        assert_eq!(ids(&hand), vec![3, 4, 1]);
+        hand.swap(0, 2).unwrap();
+        assert_eq!(ids(&hand), vec![1, 4, 3]);
+        assert_eq!(hand.move_card(0, 3), Err(CardError::InvalidIndex(3).into()));
+
+        hand.rearrange(&[2, 0, 1]).unwrap();
+        assert_eq!(ids(&hand), vec![3, 1, 4]);
+        for bad in [&[0, 1][..], &[0, 0, 1], &[0, 1, 3]] {
+            assert_eq!(
+                hand.rearrange(bad),
+                Err(CardError::InvalidArrangement.into())
+            );
+        }
+        assert_eq!(ids(&hand), vec![3, 1, 4]);
+    }
+
     #[test]
     fn take_match_removes_matching_card() {
         let mut hand = Hand::<StubFaces>::new("bob");
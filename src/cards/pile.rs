@@ -83,6 +83,12 @@ impl<T: CardFaces> Pile<T> {
     pub fn cards(&self) -> &[Card<T>] {
         &self.cards
     }
+
+    /// Remove and return the first card satisfying `predicate`, counting from the bottom.
+    pub fn take_where(&mut self, predicate: impl FnMut(&Card<T>) -> bool) -> Option<Card<T>> {
+        let idx = self.cards.iter().position(predicate)?;
+        Some(self.cards.remove(idx))
+    }
 }
 impl<T: CardFaces> CardCollection for Pile<T> {
     fn size(&self) -> usize {
@@ -0,0 +1,897 @@
+//! # Shedding Games
+//!
+//! A configurable engine for Crazy Eights, Mau-Mau, and the many other "get rid of your
+//! cards" games played with [`StandardCard`]s. They share Uno's structure — match the top
+//! of the discard pile or draw — but differ in what matches, which ranks are wild, and
+//! which ranks trigger effects. [`SheddingRules`] captures those differences:
+//!
+//! * [`MatchRule`] decides whether cards match on suit, rank, or either
+//! * `wild_ranks` play on anything and declare the suit to follow
+//! * `effects` attach a [`RankEffect`] (skip, reverse, draw penalty, play again) to ranks,
+//!   with optional stacking of draw penalties
+//! * [`DrawMode`] chooses between drawing a limited number of cards or drawing until playable
+//! * penalty points for the cards left in hand accumulate toward a target score
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::cards::shedding_game::{SheddingDraw, SheddingGame, SheddingRules};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut game = SheddingGame::new(&["Ann", "Ben"], SheddingRules::crazy_eights())?;
+//! for _ in 0..20 {
+//!     if let Some(&index) = game.legal_plays().first() {
+//!         let suit = game.suggest_suit();
+//!         game.play(index, Some(suit))?;
+//!     } else if let SheddingDraw::Drew { playable: true, .. } = game.draw()? {
+//!         game.pass()?;
+//!     }
+//!     if game.round_winner().is_some() {
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::std_playing_cards::{Rank, StandardCard, Suit, piquet_deck, standard_52};
+use crate::cards::table::{self, Table};
+use crate::cards::{AddCard, Card, CardCollection, Hand, Pile};
+use crate::{GameResult, SheddingError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which attributes of a card must agree with the top of the discard pile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MatchRule {
+    /// Match either the suit or the rank (Crazy Eights, Mau-Mau).
+    SuitOrRank,
+    /// Match the suit only.
+    Suit,
+    /// Match the rank only.
+    Rank,
+}
+
+/// An effect triggered when a card of a particular rank is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RankEffect {
+    /// The next player loses their turn.
+    Skip,
+    /// The direction of play changes (acts as a skip with two players).
+    Reverse,
+    /// The next player draws this many cards and loses their turn, unless stacking lets
+    /// them answer with another draw card.
+    Draw(usize),
+    /// The same player plays again.
+    PlayAgain,
+}
+
+/// How many cards a player who cannot (or will not) play draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DrawMode {
+    /// Draw one card at a time, up to this many, stopping early on a playable card.
+    UpTo(usize),
+    /// Keep drawing until a playable card turns up.
+    UntilPlayable,
+}
+
+/// Configuration for a shedding game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SheddingRules {
+    /// Card faces shuffled for each round.
+    pub deck: Vec<StandardCard>,
+    /// Cards dealt to each player.
+    pub hand_size: usize,
+    /// What a card must share with the top discard to be played.
+    pub match_rule: MatchRule,
+    /// Ranks that play on anything and declare the suit to follow.
+    pub wild_ranks: Vec<Rank>,
+    /// Effects triggered by playing particular ranks.
+    pub effects: Vec<(Rank, RankEffect)>,
+    /// Draw penalties may be answered with another draw card, passing the total along.
+    pub stacking: bool,
+    /// How drawing works when a player cannot play.
+    pub draw_mode: DrawMode,
+    /// Penalty points for a wild card left in hand.
+    pub wild_points: usize,
+    /// Per-rank penalty points that replace the defaults (pip value, 10 for J/Q/K, 1 for A).
+    pub point_overrides: Vec<(Rank, usize)>,
+    /// Penalty total at which the game ends; the lowest total wins.
+    pub target_score: usize,
+}
+
+impl SheddingRules {
+    /// Crazy Eights: 52 cards, 7-card hands, eights are wild,
+    /// draw up to three, and 100 penalty points end the game.
+    #[must_use]
+    pub fn crazy_eights() -> Self {
+        Self {
+            deck: standard_52(),
+            hand_size: 7,
+            match_rule: MatchRule::SuitOrRank,
+            wild_ranks: vec![Rank::Eight],
+            effects: Vec::new(),
+            stacking: false,
+            draw_mode: DrawMode::UpTo(3),
+            wild_points: 50,
+            point_overrides: Vec::new(),
+            target_score: 100,
+        }
+    }
+
+    /// Mau-Mau: the 32-card piquet deck, 5-card hands, jacks are wild, sevens make the
+    /// next player draw two (stackable), eights skip, and aces play again.
+    #[must_use]
+    pub fn mau_mau() -> Self {
+        Self {
+            deck: piquet_deck(),
+            hand_size: 5,
+            match_rule: MatchRule::SuitOrRank,
+            wild_ranks: vec![Rank::Jack],
+            effects: vec![
+                (Rank::Seven, RankEffect::Draw(2)),
+                (Rank::Eight, RankEffect::Skip),
+                (Rank::Ace, RankEffect::PlayAgain),
+            ],
+            stacking: true,
+            draw_mode: DrawMode::UpTo(1),
+            wild_points: 20,
+            point_overrides: vec![(Rank::Ace, 11)],
+            target_score: 100,
+        }
+    }
+
+    /// Returns `true` if cards of `rank` are wild under these rules.
+    #[must_use]
+    pub fn is_wild(&self, rank: Rank) -> bool {
+        self.wild_ranks.contains(&rank)
+    }
+
+    /// The effect, if any, triggered by playing a card of `rank`.
+    #[must_use]
+    pub fn effect_of(&self, rank: Rank) -> Option<RankEffect> {
+        self.effects
+            .iter()
+            .find(|(r, _)| *r == rank)
+            .map(|(_, effect)| *effect)
+    }
+
+    /// Penalty points for holding `card` at the end of a round.
+    #[must_use]
+    pub fn card_points(&self, card: &StandardCard) -> usize {
+        if let Some((_, points)) = self.point_overrides.iter().find(|(r, _)| *r == card.rank) {
+            return *points;
+        }
+        if self.is_wild(card.rank) || card.rank == Rank::Joker {
+            return self.wild_points;
+        }
+        match card.rank {
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            Rank::Ace => 1,
+            rank => rank as usize,
+        }
+    }
+
+    /// Returns `true` if `card` may be played on `top`, given any suit declared by a wild.
+    ///
+    /// ```
+    /// use gametools::cards::shedding_game::SheddingRules;
+    /// use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit};
+    ///
+    /// let rules = SheddingRules::crazy_eights();
+    /// let top = StandardCard::new_card(Rank::Four, Suit::Clubs);
+    /// assert!(rules.plays_on(&StandardCard::new_card(Rank::Four, Suit::Hearts), &top, None));
+    /// assert!(rules.plays_on(&StandardCard::new_card(Rank::Eight, Suit::Hearts), &top, None));
+    /// assert!(!rules.plays_on(&StandardCard::new_card(Rank::Nine, Suit::Hearts), &top, None));
+    /// ```
+    #[must_use]
+    pub fn plays_on(
+        &self,
+        card: &StandardCard,
+        top: &StandardCard,
+        declared_suit: Option<Suit>,
+    ) -> bool {
+        if self.is_wild(card.rank) {
+            return true;
+        }
+        if let Some(suit) = declared_suit {
+            return card.suit == suit;
+        }
+        match self.match_rule {
+            MatchRule::SuitOrRank => card.suit == top.suit || card.rank == top.rank,
+            MatchRule::Suit => card.suit == top.suit,
+            MatchRule::Rank => card.rank == top.rank,
+        }
+    }
+}
+
+impl Default for SheddingRules {
+    fn default() -> Self {
+        Self::crazy_eights()
+    }
+}
+
+/// The result of a successful play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SheddingOutcome {
+    /// The round continues.
+    Continue,
+    /// The player at seat `player` went out; `penalties` lists the points each seat took.
+    RoundWon {
+        player: usize,
+        penalties: Vec<usize>,
+    },
+}
+
+/// The result of the current player drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SheddingDraw {
+    /// A pending draw penalty of this many cards was taken and the turn passed.
+    Penalty(usize),
+    /// `count` cards were drawn. When `playable` is true the last card drawn may be played
+    /// (or the player may [`pass`](SheddingGame::pass)); otherwise the turn has passed.
+    Drew { count: usize, playable: bool },
+}
+
+/// The full state of a shedding game across rounds.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SheddingGame {
+    rules: SheddingRules,
+    hands: Vec<Hand<StandardCard>>,
+    scores: Vec<usize>,
+    draw_pile: Pile<StandardCard>,
+    discard: Pile<StandardCard>,
+    dealer: usize,
+    current: usize,
+    reversed: bool,
+    declared_suit: Option<Suit>,
+    pending_draw: usize,
+    drawn_card: Option<uuid::Uuid>,
+    round_winner: Option<usize>,
+}
+
+impl SheddingGame {
+    /// Start a new game and deal the first round.
+    ///
+    /// # Errors
+    /// - [`SheddingError::InvalidPlayerCount`] unless there are 2-10 players.
+    /// - [`SheddingError::DeckTooSmall`] if the deck can't deal every hand and leave a
+    ///   starting card.
+    pub fn new(players: &[&str], rules: SheddingRules) -> GameResult<Self> {
+        if !(2..=10).contains(&players.len()) {
+            return Err(SheddingError::InvalidPlayerCount(players.len()).into());
+        }
+        let needed = players.len() * rules.hand_size + 1;
+        let available = rules.deck.len();
+        if available < needed {
+            return Err(SheddingError::DeckTooSmall { needed, available }.into());
+        }
+        let mut game = Self {
+            rules,
+            hands: players.iter().map(|name| Hand::new(name)).collect(),
+            scores: vec![0; players.len()],
+            draw_pile: Pile::new_pile("stock"),
+            discard: Pile::new_pile("discard"),
+            dealer: 0,
+            current: 0,
+            reversed: false,
+            declared_suit: None,
+            pending_draw: 0,
+            drawn_card: None,
+            round_winner: None,
+        };
+        game.deal_round();
+        Ok(game)
+    }
+
+    /// Deal the next round once the current one has been won.
+    ///
+    /// # Errors
+    /// - [`SheddingError::RoundInProgress`] if nobody has gone out yet.
+    /// - [`SheddingError::GameOver`] if a player has reached the target score.
+    pub fn next_round(&mut self) -> GameResult<()> {
+        if self.round_winner.is_none() {
+            return Err(SheddingError::RoundInProgress.into());
+        }
+        if self.game_winner().is_some() {
+            return Err(SheddingError::GameOver.into());
+        }
+        self.dealer = (self.dealer + 1) % self.hands.len();
+        self.deal_round();
+        Ok(())
+    }
+
+    fn deal_round(&mut self) {
+        let names: Vec<String> = self.hands.iter().map(|h| h.player.clone()).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        // start the discard pile with a non-wild card when possible; effects of the
+        // starting card are not applied
+        let table = Table::deal(
+            &names,
+            self.rules.deck.clone(),
+            self.rules.hand_size,
+            "stock",
+            |card: &StandardCard| self.rules.is_wild(card.rank),
+        );
+        self.hands = table.hands;
+        self.draw_pile = table.draw_pile;
+        self.discard = table.discard;
+
+        self.reversed = false;
+        self.declared_suit = None;
+        self.pending_draw = 0;
+        self.drawn_card = None;
+        self.round_winner = None;
+        self.current = (self.dealer + 1) % self.hands.len();
+    }
+
+    /// The rules this game is played under.
+    #[must_use]
+    pub fn rules(&self) -> &SheddingRules {
+        &self.rules
+    }
+
+    /// Seat index of the player whose turn it is.
+    #[must_use]
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Returns `true` while play is running in reverse.
+    #[must_use]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// The hand held by the player at `seat`.
+    ///
+    /// # Errors
+    /// - [`SheddingError::PlayerNotFound`] if `seat` is out of range.
+    pub fn hand(&self, seat: usize) -> GameResult<&Hand<StandardCard>> {
+        self.hands
+            .get(seat)
+            .ok_or_else(|| SheddingError::PlayerNotFound(seat).into())
+    }
+
+    /// All hands, in seat order.
+    #[must_use]
+    pub fn hands(&self) -> &[Hand<StandardCard>] {
+        &self.hands
+    }
+
+    /// Accumulated penalty points for each seat.
+    #[must_use]
+    pub fn scores(&self) -> &[usize] {
+        &self.scores
+    }
+
+    /// The card on top of the discard pile.
+    #[must_use]
+    pub fn top_card(&self) -> &Card<StandardCard> {
+        // `new` checks the deck leaves a starting card after the deal, and reshuffling
+        // always keeps the top discard in place
+        self.discard
+            .check_top_card()
+            .expect("a starting card is always turned up")
+    }
+
+    /// The suit declared by the most recent wild, while it is on top of the discard pile.
+    #[must_use]
+    pub fn declared_suit(&self) -> Option<Suit> {
+        self.declared_suit
+    }
+
+    /// Cards owed by the current player under a stacked draw penalty.
+    #[must_use]
+    pub fn pending_draw(&self) -> usize {
+        self.pending_draw
+    }
+
+    /// Number of cards left in the stock.
+    #[must_use]
+    pub fn draw_pile_size(&self) -> usize {
+        self.draw_pile.size()
+    }
+
+    /// Seat of the player who went out this round, once the round has ended.
+    #[must_use]
+    pub fn round_winner(&self) -> Option<usize> {
+        self.round_winner
+    }
+
+    /// Once anyone reaches the target score, the seat with the fewest penalty points.
+    #[must_use]
+    pub fn game_winner(&self) -> Option<usize> {
+        if !self.scores.iter().any(|s| *s >= self.rules.target_score) {
+            return None;
+        }
+        self.scores
+            .iter()
+            .enumerate()
+            .min_by_key(|(seat, score)| (**score, *seat))
+            .map(|(seat, _)| seat)
+    }
+
+    /// Indices of the cards in the current player's hand that may legally be played now.
+    #[must_use]
+    pub fn legal_plays(&self) -> Vec<usize> {
+        if self.round_winner.is_some() {
+            return Vec::new();
+        }
+        self.hands[self.current]
+            .cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| self.drawn_card.is_none_or(|uuid| card.uuid == uuid))
+            .filter(|(_, card)| self.can_answer_penalty(&card.faces))
+            .filter(|(_, card)| self.plays_on(&card.faces))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The suit the current player holds the most of, handy when declaring a wild.
+    #[must_use]
+    pub fn suggest_suit(&self) -> Suit {
+        self.hands[self.current]
+            .suit_map()
+            .into_iter()
+            .filter(|(suit, _)| *suit != Suit::Wild)
+            .max_by_key(|(_, count)| *count)
+            .map_or(Suit::Spades, |(suit, _)| suit)
+    }
+
+    /// Play the card at `card_index` from the current player's hand.
+    ///
+    /// `declared` is required for wild ranks and ignored otherwise.
+    ///
+    /// # Errors
+    /// - [`SheddingError::CardNotFound`] if the index is out of range.
+    /// - [`SheddingError::IllegalPlay`] if the card does not play on the discard.
+    /// - [`SheddingError::SuitNotDeclared`] if a wild is played without a standard suit.
+    /// - [`SheddingError::PenaltyPending`] if a stacked penalty must be answered first.
+    /// - [`SheddingError::OnlyDrawnCardPlayable`] after drawing, for any other card.
+    /// - [`SheddingError::RoundOver`] if the round has already been won.
+    pub fn play(
+        &mut self,
+        card_index: usize,
+        declared: Option<Suit>,
+    ) -> GameResult<SheddingOutcome> {
+        if self.round_winner.is_some() {
+            return Err(SheddingError::RoundOver.into());
+        }
+        let card = self.hands[self.current]
+            .cards()
+            .get(card_index)
+            .ok_or(SheddingError::CardNotFound(card_index))?;
+        if let Some(uuid) = self.drawn_card
+            && card.uuid != uuid
+        {
+            return Err(SheddingError::OnlyDrawnCardPlayable.into());
+        }
+        if !self.can_answer_penalty(&card.faces) {
+            return Err(SheddingError::PenaltyPending(self.pending_draw).into());
+        }
+        if !self.plays_on(&card.faces) {
+            return Err(SheddingError::IllegalPlay.into());
+        }
+        let wild = self.rules.is_wild(card.faces.rank);
+        if wild && !declared.is_some_and(|s| Suit::normal_suits().contains(&s)) {
+            return Err(SheddingError::SuitNotDeclared.into());
+        }
+
+        let seat = self.current;
+        let uuid = card.uuid;
+        let card = self.hands[seat]
+            .take_where(|c| c.uuid == uuid)
+            .expect("validated card is in the hand");
+        let rank = card.faces.rank;
+        self.declared_suit = if wild { declared } else { None };
+        self.discard.add_card(card);
+        self.drawn_card = None;
+
+        let mut steps = 1;
+        match self.rules.effect_of(rank) {
+            Some(RankEffect::Skip) => steps = 2,
+            Some(RankEffect::Reverse) => {
+                self.reversed = !self.reversed;
+                if self.hands.len() == 2 {
+                    steps = 2;
+                }
+            }
+            Some(RankEffect::Draw(count)) => self.pending_draw += count,
+            Some(RankEffect::PlayAgain) => steps = 0,
+            None => {}
+        }
+
+        let went_out = self.hands[seat].size() == 0;
+        if self.pending_draw > 0 && (!self.rules.stacking || went_out) {
+            let victim = self.seat_after(seat, 1);
+            let penalty = std::mem::take(&mut self.pending_draw);
+            self.draw_cards(victim, penalty);
+            steps = 2;
+        }
+
+        if went_out {
+            let penalties: Vec<usize> = self
+                .hands
+                .iter()
+                .map(|hand| {
+                    hand.cards()
+                        .iter()
+                        .map(|c| self.rules.card_points(&c.faces))
+                        .sum()
+                })
+                .collect();
+            for (score, penalty) in self.scores.iter_mut().zip(&penalties) {
+                *score += penalty;
+            }
+            self.round_winner = Some(seat);
+            return Ok(SheddingOutcome::RoundWon {
+                player: seat,
+                penalties,
+            });
+        }
+
+        self.current = self.seat_after(seat, steps);
+        Ok(SheddingOutcome::Continue)
+    }
+
+    /// Draw for the current player according to the [`DrawMode`].
+    ///
+    /// A pending stacked penalty is taken in full and the turn passes. Otherwise cards
+    /// are drawn one at a time until a playable card arrives or the draw limit is hit;
+    /// if the stock and discard are both exhausted the turn simply passes.
+    ///
+    /// # Errors
+    /// - [`SheddingError::DrawLimitReached`] if a playable card has already been drawn.
+    /// - [`SheddingError::RoundOver`] if the round has already been won.
+    pub fn draw(&mut self) -> GameResult<SheddingDraw> {
+        if self.round_winner.is_some() {
+            return Err(SheddingError::RoundOver.into());
+        }
+        if self.drawn_card.is_some() {
+            return Err(SheddingError::DrawLimitReached.into());
+        }
+        if self.pending_draw > 0 {
+            let penalty = std::mem::take(&mut self.pending_draw);
+            self.draw_cards(self.current, penalty);
+            self.end_turn();
+            return Ok(SheddingDraw::Penalty(penalty));
+        }
+
+        let limit = match self.rules.draw_mode {
+            DrawMode::UpTo(limit) => limit,
+            DrawMode::UntilPlayable => usize::MAX,
+        };
+        let mut count = 0;
+        while count < limit {
+            let Some(uuid) = self.draw_cards(self.current, 1) else {
+                break;
+            };
+            count += 1;
+            let card = self.hands[self.current]
+                .cards()
+                .last()
+                .expect("a card was just drawn");
+            if self.plays_on(&card.faces) {
+                self.drawn_card = Some(uuid);
+                return Ok(SheddingDraw::Drew {
+                    count,
+                    playable: true,
+                });
+            }
+        }
+        self.end_turn();
+        Ok(SheddingDraw::Drew {
+            count,
+            playable: false,
+        })
+    }
+
+    /// Keep a playable card that was just drawn and end the turn.
+    ///
+    /// # Errors
+    /// - [`SheddingError::MustDrawFirst`] unless a playable card was just drawn.
+    pub fn pass(&mut self) -> GameResult<()> {
+        if self.round_winner.is_some() {
+            return Err(SheddingError::RoundOver.into());
+        }
+        if self.drawn_card.is_none() {
+            return Err(SheddingError::MustDrawFirst.into());
+        }
+        self.end_turn();
+        Ok(())
+    }
+
+    fn plays_on(&self, card: &StandardCard) -> bool {
+        self.rules
+            .plays_on(card, &self.top_card().faces, self.declared_suit)
+    }
+
+    fn can_answer_penalty(&self, card: &StandardCard) -> bool {
+        self.pending_draw == 0
+            || matches!(self.rules.effect_of(card.rank), Some(RankEffect::Draw(_)))
+    }
+
+    fn end_turn(&mut self) {
+        self.drawn_card = None;
+        self.current = self.seat_after(self.current, 1);
+    }
+
+    fn seat_after(&self, seat: usize, steps: usize) -> usize {
+        table::seat_after(self.hands.len(), seat, steps, self.reversed)
+    }
+
+    /// Draw up to `count` cards into the hand at `seat`; see [`table::draw_into`].
+    fn draw_cards(&mut self, seat: usize, count: usize) -> Option<uuid::Uuid> {
+        table::draw_into(
+            &mut self.hands[seat],
+            &mut self.draw_pile,
+            &mut self.discard,
+            count,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::seat_names;
+
+    fn sc(rank: Rank, suit: Suit) -> StandardCard {
+        StandardCard::new_card(rank, suit)
+    }
+
+    fn rigged(
+        hands: Vec<Vec<StandardCard>>,
+        top: StandardCard,
+        rules: SheddingRules,
+    ) -> SheddingGame {
+        let names = seat_names(hands.len());
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut game = SheddingGame::new(&names, rules).unwrap();
+        let filler = sc(Rank::Two, Suit::Diamonds);
+        let table = Table::rigged(hands, top, &filler, 10, "stock");
+        game.hands = table.hands;
+        game.draw_pile = table.draw_pile;
+        game.discard = table.discard;
+        game.current = 0;
+        game
+    }
+
+    #[test]
+    fn match_rules_control_legality() {
+        let top = sc(Rank::Nine, Suit::Clubs);
+        let same_suit = sc(Rank::Two, Suit::Clubs);
+        let same_rank = sc(Rank::Nine, Suit::Hearts);
+        let mut rules = SheddingRules::crazy_eights();
+
+        assert!(rules.plays_on(&same_suit, &top, None));
+        assert!(rules.plays_on(&same_rank, &top, None));
+        rules.match_rule = MatchRule::Suit;
+        assert!(!rules.plays_on(&same_rank, &top, None));
+        rules.match_rule = MatchRule::Rank;
+        assert!(!rules.plays_on(&same_suit, &top, None));
+
+        let rules = SheddingRules::crazy_eights();
+        assert!(rules.plays_on(&same_suit, &top, Some(Suit::Clubs)));
+        assert!(!rules.plays_on(&same_rank, &top, Some(Suit::Clubs)));
+    }
+
+    #[test]
+    fn wild_rank_requires_declared_suit() {
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Eight, Suit::Spades), sc(Rank::Four, Suit::Clubs)],
+                vec![sc(Rank::King, Suit::Hearts), sc(Rank::Five, Suit::Clubs)],
+            ],
+            sc(Rank::Nine, Suit::Diamonds),
+            SheddingRules::crazy_eights(),
+        );
+        assert_eq!(
+            game.play(0, None),
+            Err(SheddingError::SuitNotDeclared.into())
+        );
+        game.play(0, Some(Suit::Hearts)).unwrap();
+        assert_eq!(game.declared_suit(), Some(Suit::Hearts));
+        assert_eq!(game.legal_plays(), vec![0]);
+    }
+
+    #[test]
+    fn play_removes_the_chosen_copy_of_a_duplicate_face() {
+        let c = Suit::Clubs;
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Four, c), sc(Rank::Four, c), sc(Rank::Six, c)],
+                vec![sc(Rank::King, c), sc(Rank::Five, c)],
+            ],
+            sc(Rank::Nine, c),
+            SheddingRules::crazy_eights(),
+        );
+        let kept = game.hand(0).unwrap().cards()[0].uuid;
+        let played = game.hand(0).unwrap().cards()[1].uuid;
+        game.play(1, None).unwrap();
+        assert_eq!(game.top_card().uuid, played);
+        assert_eq!(game.hand(0).unwrap().cards()[0].uuid, kept);
+    }
+
+    #[test]
+    fn rank_effects_skip_reverse_and_play_again() {
+        let mut rules = SheddingRules::crazy_eights();
+        rules.effects = vec![
+            (Rank::Eight, RankEffect::Skip),
+            (Rank::Queen, RankEffect::Reverse),
+            (Rank::Ace, RankEffect::PlayAgain),
+        ];
+        rules.wild_ranks = vec![Rank::Joker];
+        let h = Suit::Hearts;
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Ace, h), sc(Rank::Eight, h), sc(Rank::Two, h)],
+                vec![sc(Rank::Three, h), sc(Rank::Four, h)],
+                vec![sc(Rank::Queen, h), sc(Rank::Five, h)],
+            ],
+            sc(Rank::Nine, h),
+            rules,
+        );
+        game.play(0, None).unwrap();
+        assert_eq!(game.current_player(), 0);
+        game.play(0, None).unwrap();
+        assert_eq!(game.current_player(), 2);
+        game.play(0, None).unwrap();
+        assert!(game.is_reversed());
+        assert_eq!(game.current_player(), 1);
+    }
+
+    #[test]
+    fn stacked_draw_penalties_pass_along() {
+        let c = Suit::Clubs;
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Seven, c), sc(Rank::Nine, c)],
+                vec![sc(Rank::Seven, Suit::Hearts), sc(Rank::Ten, c)],
+                vec![sc(Rank::King, c), sc(Rank::Queen, c)],
+            ],
+            sc(Rank::Nine, Suit::Clubs),
+            SheddingRules::mau_mau(),
+        );
+        game.play(0, None).unwrap();
+        assert_eq!(game.pending_draw(), 2);
+        assert_eq!(game.legal_plays(), vec![0]);
+        game.play(0, None).unwrap();
+        assert_eq!(
+            game.play(0, None),
+            Err(SheddingError::PenaltyPending(4).into())
+        );
+        assert_eq!(game.draw().unwrap(), SheddingDraw::Penalty(4));
+        assert_eq!(game.hand(2).unwrap().size(), 6);
+        assert_eq!(game.current_player(), 0);
+    }
+
+    #[test]
+    fn draw_modes_limit_drawing() {
+        let s = Suit::Spades;
+        let mut rules = SheddingRules::crazy_eights();
+        rules.draw_mode = DrawMode::UpTo(3);
+        let mut game = rigged(
+            vec![vec![sc(Rank::Nine, s)], vec![sc(Rank::Ten, s)]],
+            sc(Rank::King, Suit::Clubs),
+            rules.clone(),
+        );
+        assert_eq!(
+            game.draw().unwrap(),
+            SheddingDraw::Drew {
+                count: 3,
+                playable: false
+            }
+        );
+        assert_eq!(game.current_player(), 1);
+
+        rules.draw_mode = DrawMode::UntilPlayable;
+        let mut game = rigged(
+            vec![vec![sc(Rank::Nine, s)], vec![sc(Rank::Ten, s)]],
+            sc(Rank::King, Suit::Clubs),
+            rules,
+        );
+        game.draw_pile
+            .add_card(Card::new_card(sc(Rank::Three, Suit::Clubs)));
+        game.draw_pile
+            .add_card(Card::new_card(sc(Rank::Four, Suit::Hearts)));
+        assert_eq!(
+            game.draw().unwrap(),
+            SheddingDraw::Drew {
+                count: 2,
+                playable: true
+            }
+        );
+        assert_eq!(
+            game.play(0, None),
+            Err(SheddingError::OnlyDrawnCardPlayable.into())
+        );
+        game.pass().unwrap();
+        assert_eq!(game.current_player(), 1);
+    }
+
+    #[test]
+    fn going_out_scores_penalty_points() {
+        let d = Suit::Diamonds;
+        let mut rules = SheddingRules::crazy_eights();
+        rules.target_score = 60;
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Five, d)],
+                vec![sc(Rank::Eight, Suit::Clubs), sc(Rank::King, d)],
+                vec![sc(Rank::Ace, d), sc(Rank::Three, d)],
+            ],
+            sc(Rank::Nine, d),
+            rules,
+        );
+        let outcome = game.play(0, None).unwrap();
+        assert_eq!(
+            outcome,
+            SheddingOutcome::RoundWon {
+                player: 0,
+                penalties: vec![0, 60, 4]
+            }
+        );
+        assert_eq!(game.game_winner(), Some(0));
+        assert_eq!(game.next_round(), Err(SheddingError::GameOver.into()));
+    }
+
+    #[test]
+    fn stock_is_rebuilt_from_discard() {
+        let mut game = rigged(
+            vec![
+                vec![sc(Rank::Nine, Suit::Spades)],
+                vec![sc(Rank::Ten, Suit::Spades)],
+            ],
+            sc(Rank::King, Suit::Clubs),
+            SheddingRules::crazy_eights(),
+        );
+        game.draw_pile = Pile::new_pile("stock");
+        game.discard
+            .add_card(Card::new_card(sc(Rank::Queen, Suit::Hearts)));
+        let outcome = game.draw().unwrap();
+        assert_eq!(
+            outcome,
+            SheddingDraw::Drew {
+                count: 1,
+                playable: false
+            }
+        );
+        assert_eq!(game.top_card().faces, sc(Rank::Queen, Suit::Hearts));
+        assert_eq!(game.hand(0).unwrap().size(), 2);
+    }
+
+    #[test]
+    fn new_game_deals_from_configured_deck() {
+        let game = SheddingGame::new(&["a", "b", "c"], SheddingRules::mau_mau()).unwrap();
+        let dealt: usize = game.hands().iter().map(CardCollection::size).sum();
+        assert_eq!(dealt, 15);
+        assert_eq!(dealt + game.draw_pile_size() + 1, 32);
+        assert!(!game.rules().is_wild(game.top_card().faces.rank));
+        assert!(SheddingGame::new(&["solo"], SheddingRules::mau_mau()).is_err());
+    }
+
+    #[test]
+    fn undersized_decks_are_rejected() {
+        let names = ["a", "b", "c", "d", "e", "f", "g"];
+        assert_eq!(
+            SheddingGame::new(&names, SheddingRules::mau_mau()).unwrap_err(),
+            SheddingError::DeckTooSmall {
+                needed: 36,
+                available: 32
+            }
+            .into()
+        );
+        let game = SheddingGame::new(&names[..6], SheddingRules::mau_mau()).unwrap();
+        assert_eq!(game.draw_pile_size(), 1);
+    }
+}
@@ -0,0 +1,1138 @@
+//! # Solitaire
+//!
+//! A tableau engine for patience games played with [`StandardCard`]s. A [`Solitaire`]
+//! layout is made of zones — tableau columns, foundations, free cells, a stock, and a
+//! waste pile — each held as a [`Pile`], with [`Card::face_up`] tracking which tableau
+//! cards are still hidden. [`SolitaireRules`] describes how a particular game deals and
+//! which moves are legal:
+//!
+//! * [`BuildRule`]s for stacking on the tableau and for moving runs as a unit
+//! * free cells, with FreeCell's limit on how many cards may move at once
+//! * what may fill an empty column
+//! * how the stock is dealt — to a waste pile, with or without redeals, or across the columns
+//! * foundations built up by suit, or filled with complete King-to-Ace runs as in Spider
+//!
+//! Klondike, FreeCell, and Spider ship as presets. Every move and draw can be undone.
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::cards::solitaire::{Solitaire, SolitaireRules};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut game = Solitaire::new(SolitaireRules::klondike(1))?;
+//! assert_eq!(game.columns().len(), 7);
+//!
+//! // make the first legal move, if any, then take it back
+//! if let Some(&mv) = game.legal_moves().first() {
+//!     game.apply(mv)?;
+//!     game.undo()?;
+//! }
+//! game.auto_move();
+//! assert!(!game.is_won());
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::std_playing_cards::{Rank, StandardCard, Suit, standard_52};
+use crate::cards::{AddCard, Card, CardCollection, Deck, Pile, TakeCard};
+use crate::{GameResult, SolitaireError};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How a card must relate to the card it is placed on: always one rank lower, plus a
+/// color or suit requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BuildRule {
+    /// Red on black, black on red (Klondike, FreeCell).
+    AlternatingColors,
+    /// Same suit only.
+    SameSuit,
+    /// Any suit.
+    AnySuit,
+}
+
+impl BuildRule {
+    /// Returns `true` if `card` may be placed on `under`.
+    ///
+    /// ```
+    /// use gametools::cards::solitaire::BuildRule;
+    /// use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit};
+    ///
+    /// let black_nine = StandardCard::new_card(Rank::Nine, Suit::Spades);
+    /// let red_eight = StandardCard::new_card(Rank::Eight, Suit::Hearts);
+    /// assert!(BuildRule::AlternatingColors.allows(&red_eight, &black_nine));
+    /// assert!(!BuildRule::SameSuit.allows(&red_eight, &black_nine));
+    /// ```
+    #[must_use]
+    pub fn allows(self, card: &StandardCard, under: &StandardCard) -> bool {
+        if solitaire_value(card.rank) + 1 != solitaire_value(under.rank) {
+            return false;
+        }
+        match self {
+            BuildRule::AlternatingColors => is_red(card.suit) != is_red(under.suit),
+            BuildRule::SameSuit => card.suit == under.suit,
+            BuildRule::AnySuit => true,
+        }
+    }
+}
+
+/// What may be moved into an empty tableau column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmptyColumnRule {
+    /// Any card or run.
+    AnyCard,
+    /// Only a King, or a run headed by a King.
+    KingsOnly,
+}
+
+/// How foundations are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FoundationRule {
+    /// Built up one card at a time by suit, starting from the Ace.
+    SuitFromAce,
+    /// A complete King-to-Ace run of one suit is lifted off the tableau as soon as it forms.
+    CompleteRuns,
+}
+
+/// How the stock is dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StockRule {
+    /// There is no stock; every card is dealt to the tableau.
+    NoStock,
+    /// Turn `draw` cards onto the waste pile. Once the stock is empty the waste may be
+    /// turned back over `redeals` times (`None` for unlimited).
+    ToWaste { draw: usize, redeals: Option<usize> },
+    /// Deal one card face up onto every column; not allowed while a column is empty.
+    ToColumns,
+}
+
+/// Configuration for a solitaire game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolitaireRules {
+    /// Card faces shuffled for the deal.
+    pub deck: Vec<StandardCard>,
+    /// `(face_down, face_up)` cards dealt to each tableau column.
+    pub columns: Vec<(usize, usize)>,
+    /// Number of free cells, each holding a single card.
+    pub free_cells: usize,
+    /// Number of foundation piles.
+    pub foundations: usize,
+    /// How foundations are filled.
+    pub foundation_rule: FoundationRule,
+    /// How a card must relate to the tableau card it is placed on.
+    pub build_rule: BuildRule,
+    /// How the cards of a run must relate to move together as a unit.
+    pub move_rule: BuildRule,
+    /// What may fill an empty column.
+    pub empty_column: EmptyColumnRule,
+    /// Limit run moves to what could be done one card at a time through free cells and
+    /// empty columns, as in FreeCell.
+    pub limit_by_free_cells: bool,
+    /// How the stock is dealt.
+    pub stock: StockRule,
+    /// Cards may be moved from a foundation back onto the tableau (never to another
+    /// foundation or a free cell).
+    pub foundation_to_tableau: bool,
+}
+
+impl SolitaireRules {
+    /// Klondike: seven columns dealt 1 to 7 cards deep with only the top card showing,
+    /// alternating colors, Kings into empty columns, and a stock turned `draw` cards at a
+    /// time with unlimited redeals.
+    #[must_use]
+    pub fn klondike(draw: usize) -> Self {
+        Self {
+            deck: standard_52(),
+            columns: (0..7).map(|down| (down, 1)).collect(),
+            free_cells: 0,
+            foundations: 4,
+            foundation_rule: FoundationRule::SuitFromAce,
+            build_rule: BuildRule::AlternatingColors,
+            move_rule: BuildRule::AlternatingColors,
+            empty_column: EmptyColumnRule::KingsOnly,
+            limit_by_free_cells: false,
+            stock: StockRule::ToWaste {
+                draw: draw.max(1),
+                redeals: None,
+            },
+            foundation_to_tableau: true,
+        }
+    }
+
+    /// FreeCell: the whole deck dealt face up into eight columns, four free cells,
+    /// alternating colors, and any card into an empty column.
+    #[must_use]
+    pub fn freecell() -> Self {
+        Self {
+            deck: standard_52(),
+            columns: [7, 7, 7, 7, 6, 6, 6, 6].iter().map(|up| (0, *up)).collect(),
+            free_cells: 4,
+            foundations: 4,
+            foundation_rule: FoundationRule::SuitFromAce,
+            build_rule: BuildRule::AlternatingColors,
+            move_rule: BuildRule::AlternatingColors,
+            empty_column: EmptyColumnRule::AnyCard,
+            limit_by_free_cells: true,
+            stock: StockRule::NoStock,
+            foundation_to_tableau: false,
+        }
+    }
+
+    /// Spider with one, two, or four suits: 104 cards in ten columns, building down in any
+    /// suit but moving only same-suit runs, with complete runs lifted to the foundations.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::InvalidSuitCount`] unless `suits` is 1, 2, or 4.
+    pub fn spider(suits: usize) -> GameResult<Self> {
+        Ok(Self {
+            deck: spider_deck(suits)?,
+            columns: (0..10)
+                .map(|col| (if col < 4 { 5 } else { 4 }, 1))
+                .collect(),
+            free_cells: 0,
+            foundations: 8,
+            foundation_rule: FoundationRule::CompleteRuns,
+            build_rule: BuildRule::AnySuit,
+            move_rule: BuildRule::SameSuit,
+            empty_column: EmptyColumnRule::AnyCard,
+            limit_by_free_cells: false,
+            stock: StockRule::ToColumns,
+            foundation_to_tableau: false,
+        })
+    }
+
+    /// Total cards dealt to the tableau.
+    #[must_use]
+    pub fn tableau_cards(&self) -> usize {
+        self.columns.iter().map(|(down, up)| down + up).sum()
+    }
+}
+
+impl Default for SolitaireRules {
+    fn default() -> Self {
+        Self::klondike(1)
+    }
+}
+
+/// The 104 cards of a Spider deck using one (spades), two (spades and hearts), or four suits.
+///
+/// ```
+/// use gametools::cards::solitaire::spider_deck;
+///
+/// assert_eq!(spider_deck(2).unwrap().len(), 104);
+/// assert!(spider_deck(3).is_err());
+/// ```
+///
+/// # Errors
+/// - [`SolitaireError::InvalidSuitCount`] unless `suits` is 1, 2, or 4.
+pub fn spider_deck(suits: usize) -> GameResult<Vec<StandardCard>> {
+    let suits = match suits {
+        1 => vec![Suit::Spades],
+        2 => vec![Suit::Spades, Suit::Hearts],
+        4 => Suit::normal_suits(),
+        _ => return Err(SolitaireError::InvalidSuitCount(suits).into()),
+    };
+    let copies = 8 / suits.len();
+    let mut deck = Vec::with_capacity(104);
+    for _ in 0..copies {
+        for suit in &suits {
+            for rank in Rank::normal_ranks() {
+                deck.push(StandardCard::new_card(rank, *suit));
+            }
+        }
+    }
+    Ok(deck)
+}
+
+/// The rank of a card counted Ace-low, as solitaire builds: Ace is 1 and King is 13.
+#[must_use]
+pub fn solitaire_value(rank: Rank) -> u8 {
+    match rank {
+        Rank::Ace => 1,
+        Rank::Joker => 0,
+        rank => rank as u8,
+    }
+}
+
+fn is_red(suit: Suit) -> bool {
+    matches!(suit, Suit::Hearts | Suit::Diamonds)
+}
+
+/// The faces of a lone card being moved from a cell, foundation, or the waste.
+fn single_card(card: Option<&Card<StandardCard>>, count: usize) -> GameResult<&StandardCard> {
+    match card {
+        Some(card) if count == 1 => Ok(&card.faces),
+        _ => Err(SolitaireError::NotEnoughCards(count).into()),
+    }
+}
+
+/// A place cards can be moved from or to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Zone {
+    /// A tableau column.
+    Column(usize),
+    /// A foundation pile.
+    Foundation(usize),
+    /// A free cell.
+    Cell(usize),
+    /// The waste pile the stock is turned onto.
+    Waste,
+}
+
+impl std::fmt::Display for Zone {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Zone::Column(idx) => write!(f, "column {idx}"),
+            Zone::Foundation(idx) => write!(f, "foundation {idx}"),
+            Zone::Cell(idx) => write!(f, "cell {idx}"),
+            Zone::Waste => write!(f, "waste"),
+        }
+    }
+}
+
+/// Move the top `count` cards of `from` onto `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolitaireMove {
+    pub from: Zone,
+    pub to: Zone,
+    pub count: usize,
+}
+
+impl SolitaireMove {
+    /// A move of `count` cards from one zone to another.
+    #[must_use]
+    pub fn new(from: Zone, to: Zone, count: usize) -> Self {
+        Self { from, to, count }
+    }
+}
+
+/// Every zone of the layout, kept together so a move can be undone by restoring it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Layout {
+    columns: Vec<Pile<StandardCard>>,
+    foundations: Vec<Pile<StandardCard>>,
+    cells: Vec<Option<Card<StandardCard>>>,
+    stock: Pile<StandardCard>,
+    waste: Pile<StandardCard>,
+    redeals: usize,
+}
+
+/// A solitaire game in progress.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Solitaire {
+    rules: SolitaireRules,
+    layout: Layout,
+    history: Vec<Layout>,
+}
+
+impl Solitaire {
+    /// Shuffle the rules' deck and deal a new game.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if the deck cannot fill the tableau.
+    pub fn new(rules: SolitaireRules) -> GameResult<Self> {
+        let mut deck = Deck::from_faces("solitaire", rules.deck.clone());
+        deck.shuffle();
+        Self::from_deck(rules, deck)
+    }
+
+    /// Deal the game identified by `seed`. The same seed and rules always produce the same
+    /// deal (for a given version of the `rand` crate), so deals can be shared or replayed.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if the deck cannot fill the tableau.
+    ///
+    /// ```
+    /// use gametools::cards::solitaire::{Solitaire, SolitaireRules};
+    ///
+    /// let a = Solitaire::from_seed(SolitaireRules::freecell(), 42).unwrap();
+    /// let b = Solitaire::from_seed(SolitaireRules::freecell(), 42).unwrap();
+    /// assert_eq!(a.columns()[0].cards()[0].faces, b.columns()[0].cards()[0].faces);
+    /// ```
+    pub fn from_seed(rules: SolitaireRules, seed: u64) -> GameResult<Self> {
+        let mut faces = rules.deck.clone();
+        faces.shuffle(&mut StdRng::seed_from_u64(seed));
+        Self::from_order(rules, faces)
+    }
+
+    /// Deal a game without shuffling: the first card of `faces` is dealt first, row by row
+    /// across the columns, and whatever is left becomes the stock with the next card to be
+    /// drawn on top.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if `faces` cannot fill the tableau.
+    pub fn from_order(rules: SolitaireRules, faces: Vec<StandardCard>) -> GameResult<Self> {
+        let deck = Deck::from_faces("solitaire", faces.into_iter().rev());
+        Self::from_deck(rules, deck)
+    }
+
+    /// Deal a game from `deck` as it stands, taking cards from the top of the deck.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if the deck cannot fill the tableau.
+    pub fn from_deck(rules: SolitaireRules, mut deck: Deck<StandardCard>) -> GameResult<Self> {
+        let needed = rules.tableau_cards();
+        if deck.size() < needed {
+            return Err(SolitaireError::DeckTooSmall {
+                needed,
+                available: deck.size(),
+            }
+            .into());
+        }
+        let mut columns: Vec<Pile<StandardCard>> = (0..rules.columns.len())
+            .map(|idx| Pile::new_pile(&format!("column {idx}")))
+            .collect();
+        let depth = rules
+            .columns
+            .iter()
+            .map(|(down, up)| down + up)
+            .max()
+            .unwrap_or(0);
+        for row in 0..depth {
+            for (column, (down, up)) in columns.iter_mut().zip(&rules.columns) {
+                if row >= down + up {
+                    continue;
+                }
+                let mut card = deck.take_card().expect("deck size was checked");
+                card.face_up = row >= *down;
+                column.add_card(card);
+            }
+        }
+
+        let mut stock = Pile::new_pile("stock");
+        let mut rest = deck.take_cards(deck.size());
+        rest.reverse();
+        for mut card in rest {
+            card.face_up = false;
+            stock.add_card(card);
+        }
+
+        let layout = Layout {
+            columns,
+            foundations: (0..rules.foundations)
+                .map(|idx| Pile::new_pile(&format!("foundation {idx}")))
+                .collect(),
+            cells: vec![None; rules.free_cells],
+            stock,
+            waste: Pile::new_pile("waste"),
+            redeals: 0,
+        };
+        Ok(Self {
+            rules,
+            layout,
+            history: Vec::new(),
+        })
+    }
+
+    /// The rules this game is played under.
+    #[must_use]
+    pub fn rules(&self) -> &SolitaireRules {
+        &self.rules
+    }
+
+    /// The tableau columns, each with its top card last.
+    #[must_use]
+    pub fn columns(&self) -> &[Pile<StandardCard>] {
+        &self.layout.columns
+    }
+
+    /// The foundation piles.
+    #[must_use]
+    pub fn foundations(&self) -> &[Pile<StandardCard>] {
+        &self.layout.foundations
+    }
+
+    /// The free cells.
+    #[must_use]
+    pub fn cells(&self) -> &[Option<Card<StandardCard>>] {
+        &self.layout.cells
+    }
+
+    /// The undealt stock, with the next card to be drawn on top.
+    #[must_use]
+    pub fn stock(&self) -> &Pile<StandardCard> {
+        &self.layout.stock
+    }
+
+    /// The waste pile.
+    #[must_use]
+    pub fn waste(&self) -> &Pile<StandardCard> {
+        &self.layout.waste
+    }
+
+    /// Times the waste has been turned back into the stock.
+    #[must_use]
+    pub fn redeals_used(&self) -> usize {
+        self.layout.redeals
+    }
+
+    /// Moves and draws made so far that can still be undone.
+    #[must_use]
+    pub fn moves_made(&self) -> usize {
+        self.history.len()
+    }
+
+    /// The most cards that may move as one unit, given the free cells and empty columns
+    /// available. Unlimited unless [`SolitaireRules::limit_by_free_cells`] is set.
+    #[must_use]
+    pub fn max_run_move(&self, to_empty_column: bool) -> usize {
+        if !self.rules.limit_by_free_cells {
+            return usize::MAX;
+        }
+        let free = self.layout.cells.iter().filter(|c| c.is_none()).count();
+        let empty = self
+            .layout
+            .columns
+            .iter()
+            .filter(|c| c.size() == 0)
+            .count()
+            .saturating_sub(usize::from(to_empty_column));
+        (free + 1) << empty.min(16)
+    }
+
+    /// Check a move against the rules without making it.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::ZoneNotFound`] if either zone does not exist in this layout.
+    /// - [`SolitaireError::NotEnoughCards`] if the source lacks `count` movable cards.
+    /// - [`SolitaireError::SequenceTooLong`] if the run exceeds [`max_run_move`](Self::max_run_move).
+    /// - [`SolitaireError::IllegalMove`] if the cards may not be placed on the target.
+    pub fn check_move(&self, mv: SolitaireMove) -> GameResult<()> {
+        let base = self.moving_base(mv)?;
+        if mv.from == mv.to {
+            return Err(SolitaireError::IllegalMove.into());
+        }
+        match mv.to {
+            Zone::Column(idx) => {
+                let column = self
+                    .layout
+                    .columns
+                    .get(idx)
+                    .ok_or_else(|| SolitaireError::ZoneNotFound(mv.to.to_string()))?;
+                let placeable = match column.check_top_card() {
+                    None => {
+                        self.rules.empty_column == EmptyColumnRule::AnyCard
+                            || base.rank == Rank::King
+                    }
+                    Some(top) => top.face_up && self.rules.build_rule.allows(base, &top.faces),
+                };
+                if !placeable {
+                    return Err(SolitaireError::IllegalMove.into());
+                }
+                let limit = self.max_run_move(column.size() == 0);
+                if mv.count > limit {
+                    return Err(SolitaireError::SequenceTooLong {
+                        requested: mv.count,
+                        limit,
+                    }
+                    .into());
+                }
+            }
+            Zone::Foundation(idx) => {
+                let foundation = self
+                    .layout
+                    .foundations
+                    .get(idx)
+                    .ok_or_else(|| SolitaireError::ZoneNotFound(mv.to.to_string()))?;
+                let placeable = self.rules.foundation_rule == FoundationRule::SuitFromAce
+                    && mv.count == 1
+                    && match foundation.check_top_card() {
+                        None => base.rank == Rank::Ace,
+                        Some(top) => {
+                            top.faces.suit == base.suit
+                                && solitaire_value(top.faces.rank) + 1 == solitaire_value(base.rank)
+                        }
+                    };
+                if !placeable {
+                    return Err(SolitaireError::IllegalMove.into());
+                }
+            }
+            Zone::Cell(idx) => {
+                let cell = self
+                    .layout
+                    .cells
+                    .get(idx)
+                    .ok_or_else(|| SolitaireError::ZoneNotFound(mv.to.to_string()))?;
+                if mv.count != 1 || cell.is_some() {
+                    return Err(SolitaireError::IllegalMove.into());
+                }
+            }
+            Zone::Waste => return Err(SolitaireError::IllegalMove.into()),
+        }
+        Ok(())
+    }
+
+    /// The bottom card of the cards that would move out of `mv.from`, after checking
+    /// that they may move together.
+    fn moving_base(&self, mv: SolitaireMove) -> GameResult<&StandardCard> {
+        let SolitaireMove { from, count, .. } = mv;
+        let missing = || SolitaireError::ZoneNotFound(from.to_string());
+        if count == 0 {
+            return Err(SolitaireError::NotEnoughCards(count).into());
+        }
+        match from {
+            Zone::Column(idx) => {
+                let cards = self.layout.columns.get(idx).ok_or_else(missing)?.cards();
+                if cards.len() < count {
+                    return Err(SolitaireError::NotEnoughCards(count).into());
+                }
+                let run = &cards[cards.len() - count..];
+                if run.iter().any(|card| !card.face_up) {
+                    return Err(SolitaireError::NotEnoughCards(count).into());
+                }
+                if !run
+                    .windows(2)
+                    .all(|pair| self.rules.move_rule.allows(&pair[1].faces, &pair[0].faces))
+                {
+                    return Err(SolitaireError::IllegalMove.into());
+                }
+                Ok(&run[0].faces)
+            }
+            Zone::Foundation(idx) => {
+                let foundation = self.layout.foundations.get(idx).ok_or_else(missing)?;
+                if !self.rules.foundation_to_tableau || !matches!(mv.to, Zone::Column(_)) {
+                    return Err(SolitaireError::IllegalMove.into());
+                }
+                single_card(foundation.check_top_card(), count)
+            }
+            Zone::Cell(idx) => single_card(
+                self.layout.cells.get(idx).ok_or_else(missing)?.as_ref(),
+                count,
+            ),
+            Zone::Waste => single_card(self.layout.waste.check_top_card(), count),
+        }
+    }
+
+    /// Make a move, turning up any face-down card it uncovers.
+    ///
+    /// # Errors
+    /// Any error from [`check_move`](Self::check_move); the layout is unchanged on error.
+    pub fn apply(&mut self, mv: SolitaireMove) -> GameResult<()> {
+        self.check_move(mv)?;
+        self.history.push(self.layout.clone());
+        self.transfer(mv);
+        self.settle();
+        Ok(())
+    }
+
+    fn transfer(&mut self, mv: SolitaireMove) {
+        let layout = &mut self.layout;
+        let mut cards = match mv.from {
+            Zone::Column(idx) => layout.columns[idx].take_cards(mv.count),
+            Zone::Foundation(idx) => layout.foundations[idx].take_cards(1),
+            Zone::Cell(idx) => layout.cells[idx].take().into_iter().collect(),
+            Zone::Waste => layout.waste.take_cards(1),
+        };
+        // take_cards hands back the top card first
+        cards.reverse();
+        match mv.to {
+            Zone::Column(idx) => layout.columns[idx].add_cards(cards),
+            Zone::Foundation(idx) => layout.foundations[idx].add_cards(cards),
+            Zone::Cell(idx) => layout.cells[idx] = cards.pop(),
+            Zone::Waste => unreachable!("moves to the waste are rejected by check_move"),
+        }
+    }
+
+    /// Turn up uncovered cards and lift any complete runs to the foundations.
+    fn settle(&mut self) {
+        loop {
+            for column in &mut self.layout.columns {
+                let cards = column.take_cards(1);
+                for mut card in cards {
+                    card.face_up = true;
+                    column.add_card(card);
+                }
+            }
+            if self.rules.foundation_rule != FoundationRule::CompleteRuns {
+                return;
+            }
+            let Some(column) = self
+                .layout
+                .columns
+                .iter()
+                .position(|column| Self::ends_in_complete_run(column.cards()))
+            else {
+                return;
+            };
+            let Some(foundation) = self
+                .layout
+                .foundations
+                .iter()
+                .position(|pile| pile.size() == 0)
+            else {
+                return;
+            };
+            let mut run = self.layout.columns[column].take_cards(13);
+            run.reverse();
+            self.layout.foundations[foundation].add_cards(run);
+        }
+    }
+
+    fn ends_in_complete_run(cards: &[Card<StandardCard>]) -> bool {
+        if cards.len() < 13 {
+            return false;
+        }
+        let run = &cards[cards.len() - 13..];
+        run[0].faces.rank == Rank::King
+            && run.iter().all(|card| card.face_up)
+            && run
+                .windows(2)
+                .all(|pair| BuildRule::SameSuit.allows(&pair[1].faces, &pair[0].faces))
+    }
+
+    /// Returns `true` if [`draw`](Self::draw) would succeed.
+    #[must_use]
+    pub fn can_draw(&self) -> bool {
+        let layout = &self.layout;
+        match self.rules.stock {
+            StockRule::NoStock => false,
+            StockRule::ToWaste { redeals, .. } => {
+                layout.stock.size() > 0
+                    || (layout.waste.size() > 0 && redeals.is_none_or(|max| layout.redeals < max))
+            }
+            StockRule::ToColumns => {
+                layout.stock.size() > 0 && layout.columns.iter().all(|c| c.size() > 0)
+            }
+        }
+    }
+
+    /// Deal from the stock as the rules direct. With [`StockRule::ToWaste`] an empty stock is
+    /// refilled from the waste, counting as a redeal.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::StockEmpty`] if there is nothing to deal and no redeal is left.
+    /// - [`SolitaireError::EmptyColumn`] if dealing to the columns while one is empty.
+    pub fn draw(&mut self) -> GameResult<()> {
+        if !self.can_draw() {
+            if self.rules.stock == StockRule::ToColumns && self.layout.stock.size() > 0 {
+                return Err(SolitaireError::EmptyColumn.into());
+            }
+            return Err(SolitaireError::StockEmpty.into());
+        }
+        self.history.push(self.layout.clone());
+        let layout = &mut self.layout;
+        match self.rules.stock {
+            StockRule::NoStock => unreachable!("can_draw is false without a stock"),
+            StockRule::ToWaste { draw, .. } => {
+                if layout.stock.size() == 0 {
+                    while let Some(mut card) = layout.waste.take_card() {
+                        card.face_up = false;
+                        layout.stock.add_card(card);
+                    }
+                    layout.redeals += 1;
+                } else {
+                    for mut card in layout.stock.take_cards(draw) {
+                        card.face_up = true;
+                        layout.waste.add_card(card);
+                    }
+                }
+            }
+            StockRule::ToColumns => {
+                for column in &mut layout.columns {
+                    if let Some(mut card) = layout.stock.take_card() {
+                        card.face_up = true;
+                        column.add_card(card);
+                    }
+                }
+            }
+        }
+        self.settle();
+        Ok(())
+    }
+
+    /// Take back the last move or draw.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::NothingToUndo`] at the start of the game.
+    pub fn undo(&mut self) -> GameResult<()> {
+        self.layout = self.history.pop().ok_or(SolitaireError::NothingToUndo)?;
+        Ok(())
+    }
+
+    /// Every legal move in the current layout, not counting [`draw`](Self::draw).
+    #[must_use]
+    pub fn legal_moves(&self) -> Vec<SolitaireMove> {
+        let layout = &self.layout;
+        let mut sources: Vec<(Zone, usize)> = Vec::new();
+        for (idx, column) in layout.columns.iter().enumerate() {
+            let face_up = column
+                .cards()
+                .iter()
+                .rev()
+                .take_while(|c| c.face_up)
+                .count();
+            sources.extend((1..=face_up).map(|count| (Zone::Column(idx), count)));
+        }
+        sources.extend((0..layout.cells.len()).map(|idx| (Zone::Cell(idx), 1)));
+        sources.extend((0..layout.foundations.len()).map(|idx| (Zone::Foundation(idx), 1)));
+        sources.push((Zone::Waste, 1));
+
+        let targets: Vec<Zone> = (0..layout.columns.len())
+            .map(Zone::Column)
+            .chain((0..layout.foundations.len()).map(Zone::Foundation))
+            .chain((0..layout.cells.len()).map(Zone::Cell))
+            .collect();
+
+        sources
+            .into_iter()
+            .flat_map(|(from, count)| {
+                targets
+                    .iter()
+                    .map(move |to| SolitaireMove::new(from, *to, count))
+            })
+            .filter(|mv| self.check_move(*mv).is_ok())
+            .collect()
+    }
+
+    /// Move every card that can go to a foundation there, repeating until none can.
+    /// All the cards moved are undone together. Returns how many cards moved.
+    pub fn auto_move(&mut self) -> usize {
+        let before = self.layout.clone();
+        let mut moved = 0;
+        'search: loop {
+            let sources = (0..self.layout.columns.len())
+                .map(Zone::Column)
+                .chain((0..self.layout.cells.len()).map(Zone::Cell))
+                .chain(std::iter::once(Zone::Waste));
+            for from in sources {
+                for idx in 0..self.layout.foundations.len() {
+                    let mv = SolitaireMove::new(from, Zone::Foundation(idx), 1);
+                    if self.check_move(mv).is_ok() {
+                        self.transfer(mv);
+                        self.settle();
+                        moved += 1;
+                        continue 'search;
+                    }
+                }
+            }
+            break;
+        }
+        if moved > 0 {
+            self.history.push(before);
+        }
+        moved
+    }
+
+    /// Returns `true` once every card has reached the foundations.
+    #[must_use]
+    pub fn is_won(&self) -> bool {
+        let layout = &self.layout;
+        layout.stock.size() == 0
+            && layout.waste.size() == 0
+            && layout.cells.iter().all(Option::is_none)
+            && layout.columns.iter().all(|c| c.size() == 0)
+    }
+
+    /// Returns `true` if the game is lost: nothing can be drawn and no move makes progress.
+    ///
+    /// Moves that only shuffle cards between equivalent positions — lifting a run off a card
+    /// it already builds on, moving a whole column into an empty one, or taking a card back
+    /// off a foundation — are not counted as progress.
+    #[must_use]
+    pub fn is_dead_end(&self) -> bool {
+        !self.is_won()
+            && !self.can_draw()
+            && !self
+                .legal_moves()
+                .into_iter()
+                .any(|mv| self.makes_progress(mv))
+    }
+
+    fn makes_progress(&self, mv: SolitaireMove) -> bool {
+        match mv.from {
+            Zone::Foundation(_) => false,
+            Zone::Waste => true,
+            Zone::Cell(_) => !matches!(mv.to, Zone::Cell(_)),
+            Zone::Column(idx) => {
+                if matches!(mv.to, Zone::Foundation(_)) {
+                    return true;
+                }
+                let cards = self.layout.columns[idx].cards();
+                let rest = cards.len() - mv.count;
+                let Some(under) = rest.checked_sub(1).map(|i| &cards[i]) else {
+                    // emptying a column only helps if the cards land somewhere non-empty
+                    return matches!(mv.to, Zone::Column(to) if self.layout.columns[to].size() > 0);
+                };
+                !under.face_up
+                    || !self
+                        .rules
+                        .build_rule
+                        .allows(&cards[rest].faces, &under.faces)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::pile_of;
+
+    fn sc(rank: Rank, suit: Suit) -> StandardCard {
+        StandardCard::new_card(rank, suit)
+    }
+
+    fn card(rank: Rank, suit: Suit, face_up: bool) -> Card<StandardCard> {
+        let mut card = Card::new_card(sc(rank, suit));
+        card.face_up = face_up;
+        card
+    }
+
+    /// A game with the given rules whose columns are replaced by `columns`.
+    fn rigged(rules: SolitaireRules, columns: Vec<Vec<Card<StandardCard>>>) -> Solitaire {
+        let mut game = Solitaire::new(rules).unwrap();
+        game.layout.columns = columns
+            .into_iter()
+            .map(|cards| pile_of("column", cards))
+            .collect();
+        game.layout.stock = pile_of("stock", Vec::new());
+        game
+    }
+
+    #[test]
+    fn presets_deal_expected_layouts() {
+        let klondike = Solitaire::new(SolitaireRules::klondike(3)).unwrap();
+        let sizes: Vec<usize> = klondike
+            .columns()
+            .iter()
+            .map(CardCollection::size)
+            .collect();
+        assert_eq!(sizes, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(klondike.stock().size(), 24);
+        assert!(!klondike.columns()[6].cards()[0].face_up);
+        assert!(klondike.columns()[6].cards()[6].face_up);
+
+        let freecell = Solitaire::new(SolitaireRules::freecell()).unwrap();
+        assert_eq!(freecell.stock().size(), 0);
+        assert!(
+            freecell
+                .columns()
+                .iter()
+                .all(|c| c.cards().iter().all(|c| c.face_up))
+        );
+
+        let spider = Solitaire::new(SolitaireRules::spider(1).unwrap()).unwrap();
+        assert_eq!(spider.rules().tableau_cards(), 54);
+        assert_eq!(spider.stock().size(), 50);
+    }
+
+    #[test]
+    fn from_order_deals_rows_in_sequence() {
+        let faces = standard_52();
+        let game = Solitaire::from_order(SolitaireRules::klondike(1), faces.clone()).unwrap();
+        assert_eq!(game.columns()[0].cards()[0].faces, faces[0]);
+        assert_eq!(game.columns()[1].cards()[0].faces, faces[1]);
+        assert_eq!(game.columns()[1].cards()[1].faces, faces[7]);
+        assert_eq!(game.stock().check_top_card().unwrap().faces, faces[28]);
+
+        let short = Solitaire::from_order(SolitaireRules::klondike(1), faces[..10].to_vec());
+        assert_eq!(
+            short.unwrap_err(),
+            SolitaireError::DeckTooSmall {
+                needed: 28,
+                available: 10
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn runs_move_by_build_rules_and_expose_cards() {
+        let mut game = rigged(
+            SolitaireRules::klondike(1),
+            vec![
+                vec![
+                    card(Rank::Two, Suit::Clubs, false),
+                    card(Rank::Nine, Suit::Spades, true),
+                    card(Rank::Eight, Suit::Hearts, true),
+                ],
+                vec![card(Rank::Ten, Suit::Diamonds, true)],
+                vec![card(Rank::Ten, Suit::Clubs, true)],
+                vec![],
+            ],
+        );
+        let to_black = SolitaireMove::new(Zone::Column(0), Zone::Column(2), 2);
+        assert_eq!(
+            game.apply(to_black),
+            Err(SolitaireError::IllegalMove.into())
+        );
+        let to_empty = SolitaireMove::new(Zone::Column(0), Zone::Column(3), 2);
+        assert_eq!(
+            game.apply(to_empty),
+            Err(SolitaireError::IllegalMove.into())
+        );
+
+        game.apply(SolitaireMove::new(Zone::Column(0), Zone::Column(1), 2))
+            .unwrap();
+        assert_eq!(game.columns()[1].size(), 3);
+        assert!(game.columns()[0].cards()[0].face_up);
+
+        game.undo().unwrap();
+        assert!(!game.columns()[0].cards()[0].face_up);
+        assert_eq!(game.undo(), Err(SolitaireError::NothingToUndo.into()));
+    }
+
+    #[test]
+    fn freecell_limits_run_length() {
+        let h = |rank| card(rank, Suit::Hearts, true);
+        let s = |rank| card(rank, Suit::Spades, true);
+        let mut game = rigged(
+            SolitaireRules::freecell(),
+            vec![
+                vec![s(Rank::Nine), h(Rank::Eight), s(Rank::Seven), h(Rank::Six)],
+                vec![card(Rank::Ten, Suit::Diamonds, true)],
+            ],
+        );
+        for idx in 0..3 {
+            game.layout.cells[idx] = Some(card(Rank::Two, Suit::Clubs, true));
+        }
+        assert_eq!(game.max_run_move(false), 2);
+        let mv = SolitaireMove::new(Zone::Column(0), Zone::Column(1), 4);
+        assert_eq!(
+            game.check_move(mv),
+            Err(SolitaireError::SequenceTooLong {
+                requested: 4,
+                limit: 2
+            }
+            .into())
+        );
+        game.layout.cells[0] = None;
+        game.layout.columns.push(Pile::new_pile("empty"));
+        assert_eq!(game.max_run_move(false), 6);
+        game.apply(mv).unwrap();
+    }
+
+    #[test]
+    fn auto_move_fills_foundations_and_wins() {
+        let c = |rank| card(rank, Suit::Clubs, true);
+        let mut game = rigged(
+            SolitaireRules::klondike(1),
+            vec![vec![c(Rank::Two), c(Rank::Three)], vec![c(Rank::Ace)]],
+        );
+        game.layout.waste = Pile::new_pile("waste");
+        assert_eq!(game.auto_move(), 1);
+        assert!(!game.is_won());
+        game.apply(SolitaireMove::new(Zone::Column(0), Zone::Cell(0), 1))
+            .unwrap_err();
+        game.apply(SolitaireMove::new(Zone::Column(0), Zone::Column(1), 1))
+            .unwrap_err();
+        game.layout.columns[0] = pile_of("column", vec![c(Rank::Three), c(Rank::Two)]);
+        assert_eq!(game.auto_move(), 2);
+        assert!(game.is_won());
+        game.undo().unwrap();
+        assert_eq!(game.foundations()[0].size(), 1);
+    }
+
+    #[test]
+    fn foundation_cards_only_return_to_columns() {
+        let h = |rank| card(rank, Suit::Hearts, true);
+        let mut game = rigged(
+            SolitaireRules::klondike(1),
+            vec![vec![card(Rank::Three, Suit::Spades, true)]],
+        );
+        game.layout.waste = Pile::new_pile("waste");
+        game.layout.foundations[0] = pile_of("foundation", vec![h(Rank::Ace), h(Rank::Two)]);
+        let sideways = SolitaireMove::new(Zone::Foundation(0), Zone::Foundation(1), 1);
+        assert_eq!(
+            game.check_move(sideways),
+            Err(SolitaireError::IllegalMove.into())
+        );
+        let back = SolitaireMove::new(Zone::Foundation(0), Zone::Column(0), 1);
+        assert_eq!(game.legal_moves(), vec![back]);
+
+        let mut rules = SolitaireRules::freecell();
+        rules.foundation_to_tableau = true;
+        let mut game = rigged(rules, vec![vec![]]);
+        game.layout.foundations[0] = pile_of("foundation", vec![h(Rank::Ace)]);
+        let to_cell = SolitaireMove::new(Zone::Foundation(0), Zone::Cell(0), 1);
+        assert_eq!(
+            game.check_move(to_cell),
+            Err(SolitaireError::IllegalMove.into())
+        );
+        game.apply(SolitaireMove::new(Zone::Foundation(0), Zone::Column(0), 1))
+            .unwrap();
+    }
+
+    #[test]
+    fn stock_draws_and_redeals() {
+        let mut rules = SolitaireRules::klondike(3);
+        rules.stock = StockRule::ToWaste {
+            draw: 3,
+            redeals: Some(1),
+        };
+        let mut game = Solitaire::from_order(rules, standard_52()).unwrap();
+        for _ in 0..8 {
+            game.draw().unwrap();
+        }
+        assert_eq!(game.waste().size(), 24);
+        assert!(game.waste().check_top_card().unwrap().face_up);
+        game.draw().unwrap();
+        assert_eq!((game.stock().size(), game.redeals_used()), (24, 1));
+        assert_eq!(
+            game.stock().check_top_card().unwrap().faces,
+            standard_52()[28]
+        );
+        for _ in 0..8 {
+            game.draw().unwrap();
+        }
+        assert_eq!(game.draw(), Err(SolitaireError::StockEmpty.into()));
+    }
+
+    #[test]
+    fn spider_deals_rows_and_lifts_complete_runs() {
+        let mut game = rigged(SolitaireRules::spider(1).unwrap(), vec![vec![]; 10]);
+        let mut ranks = Rank::normal_ranks();
+        ranks.rotate_right(1);
+        ranks.reverse();
+        let run: Vec<_> = ranks[..12]
+            .iter()
+            .map(|rank| card(*rank, Suit::Spades, true))
+            .collect();
+        game.layout.columns[0].add_card(card(Rank::Five, Suit::Hearts, false));
+        game.layout.columns[0].add_cards(run);
+        game.layout.columns[1].add_card(card(Rank::Ace, Suit::Spades, true));
+        assert_eq!(game.draw(), Err(SolitaireError::StockEmpty.into()));
+        game.layout
+            .stock
+            .add_card(card(Rank::Four, Suit::Spades, false));
+        assert_eq!(game.draw(), Err(SolitaireError::EmptyColumn.into()));
+
+        game.apply(SolitaireMove::new(Zone::Column(1), Zone::Column(0), 1))
+            .unwrap();
+        assert_eq!(game.foundations()[0].size(), 13);
+        assert_eq!(game.columns()[0].size(), 1);
+        assert!(game.columns()[0].cards()[0].face_up);
+    }
+
+    #[test]
+    fn dead_end_ignores_shuffling_moves() {
+        let mut game = rigged(
+            SolitaireRules::freecell(),
+            vec![
+                vec![
+                    card(Rank::Nine, Suit::Spades, true),
+                    card(Rank::Eight, Suit::Hearts, true),
+                ],
+                vec![card(Rank::Ten, Suit::Diamonds, true)],
+            ],
+        );
+        game.layout.cells = vec![Some(card(Rank::Two, Suit::Clubs, true)); 3];
+        game.layout.cells.push(None);
+        assert!(!game.is_dead_end());
+        game.layout.columns[1] = pile_of("column", vec![card(Rank::Ten, Suit::Spades, true)]);
+        assert!(game.is_dead_end());
+    }
+}
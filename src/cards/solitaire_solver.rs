@@ -0,0 +1,876 @@
+//! # Solitaire Solver
+//!
+//! Plays [`Solitaire`] deals automatically to decide whether they can be won.
+//! [`SolitaireSolver`] copies a layout into a compact state model of its own — each card
+//! packed into a byte, columns as byte strings — and runs a weighted A* search over it,
+//! hashing states so that equivalent layouts (columns in a different order, cards in
+//! different free cells) are only explored once. Cards that can never be needed on the
+//! tableau again are moved to the foundations automatically, which keeps the search small.
+//!
+//! The search is bounded by a [`SolverBudget`] of expanded nodes and, optionally, wall
+//! time. A deal reported [`Unwinnable`](SolveOutcome::Unwinnable) was searched
+//! exhaustively; one reported [`OutOfBudget`](SolveOutcome::OutOfBudget) may still be
+//! winnable. Solutions are lists of [`SolverStep`]s that replay directly on a
+//! [`Solitaire`] dealt the same way, so winnable deals can be curated ahead of time by seed.
+//!
+//! The solver handles Klondike- and FreeCell-style rules: foundations built up by suit
+//! from the Ace, a stock turned onto a waste pile (or none), and a deck without duplicate
+//! cards. Cards come back off the foundations only when the rules'
+//! `foundation_to_tableau` allows it.
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::cards::solitaire::{Solitaire, SolitaireRules};
+//! use gametools::cards::solitaire_solver::{SolitaireSolver, SolveOutcome};
+//! # fn main() -> GameResult<()> {
+//!
+//! let solver = SolitaireSolver::new(SolitaireRules::freecell())?;
+//! let report = solver.solve_seed(7)?;
+//! if let SolveOutcome::Solved(steps) = &report.outcome {
+//!     let mut game = Solitaire::from_seed(SolitaireRules::freecell(), 7)?;
+//!     for step in steps {
+//!         step.apply_to(&mut game)?;
+//!     }
+//!     assert!(game.is_won());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::solitaire::{
+    BuildRule, EmptyColumnRule, FoundationRule, Solitaire, SolitaireMove, SolitaireRules,
+    StockRule, Zone, solitaire_value,
+};
+use crate::cards::std_playing_cards::{StandardCard, Suit};
+use crate::cards::{Card, Deck, Pile};
+use crate::{GameResult, SolitaireError};
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One step of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SolverStep {
+    /// Move cards between zones with [`Solitaire::apply`].
+    Move(SolitaireMove),
+    /// Deal from the stock with [`Solitaire::draw`].
+    Draw,
+}
+
+impl SolverStep {
+    /// Play this step on `game`.
+    ///
+    /// # Errors
+    /// Any error from [`Solitaire::apply`] or [`Solitaire::draw`], which means `game` is not
+    /// in the position the step was found from.
+    pub fn apply_to(&self, game: &mut Solitaire) -> GameResult<()> {
+        match self {
+            SolverStep::Move(mv) => game.apply(*mv),
+            SolverStep::Draw => game.draw(),
+        }
+    }
+}
+
+/// Limits on how much work a single search may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolverBudget {
+    /// Maximum number of states to expand.
+    pub max_nodes: usize,
+    /// Maximum wall time, if any.
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for SolverBudget {
+    /// 200,000 nodes with no time limit.
+    fn default() -> Self {
+        Self {
+            max_nodes: 200_000,
+            time_limit: None,
+        }
+    }
+}
+
+/// How a search ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SolveOutcome {
+    /// The deal can be won by playing these steps in order.
+    Solved(Vec<SolverStep>),
+    /// Every reachable position was searched without finding a win.
+    Unwinnable,
+    /// The budget ran out before the search finished.
+    OutOfBudget,
+}
+
+/// The outcome of a search together with the work it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolveReport {
+    /// How the search ended.
+    pub outcome: SolveOutcome,
+    /// Number of states expanded.
+    pub nodes: usize,
+    /// Wall time spent searching.
+    pub elapsed: Duration,
+}
+
+impl SolveReport {
+    /// The winning steps, if a solution was found.
+    #[must_use]
+    pub fn solution(&self) -> Option<&[SolverStep]> {
+        match &self.outcome {
+            SolveOutcome::Solved(steps) => Some(steps),
+            _ => None,
+        }
+    }
+}
+
+/// Searches solitaire deals for a win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolitaireSolver {
+    rules: SolitaireRules,
+    budget: SolverBudget,
+}
+
+impl SolitaireSolver {
+    /// Create a solver for deals made with `rules`, using the default budget.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::UnsupportedRules`] for rules outside the solver's scope.
+    pub fn new(rules: SolitaireRules) -> GameResult<Self> {
+        check_supported(&rules)?;
+        Ok(Self {
+            rules,
+            budget: SolverBudget::default(),
+        })
+    }
+
+    /// Replace the search budget.
+    #[must_use]
+    pub fn with_budget(mut self, budget: SolverBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// The rules deals are made with.
+    #[must_use]
+    pub fn rules(&self) -> &SolitaireRules {
+        &self.rules
+    }
+
+    /// The current search budget.
+    #[must_use]
+    pub fn budget(&self) -> SolverBudget {
+        self.budget
+    }
+
+    /// Solve the deal [`Solitaire::from_seed`] makes from `seed`.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if the rules' deck cannot fill the tableau.
+    pub fn solve_seed(&self, seed: u64) -> GameResult<SolveReport> {
+        self.solve(&Solitaire::from_seed(self.rules.clone(), seed)?)
+    }
+
+    /// Solve the deal [`Solitaire::from_deck`] makes from `deck`.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if `deck` cannot fill the tableau.
+    /// - [`SolitaireError::UnsupportedRules`] if `deck` holds duplicate cards.
+    pub fn solve_deck(&self, deck: Deck<StandardCard>) -> GameResult<SolveReport> {
+        self.solve(&Solitaire::from_deck(self.rules.clone(), deck)?)
+    }
+
+    /// Solve `game` from its current position, under its own rules. The solver sees the
+    /// face-down cards, so a win it finds may rely on knowledge a player would not have.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::UnsupportedRules`] for rules outside the solver's scope, or a
+    ///   layout holding duplicate cards.
+    pub fn solve(&self, game: &Solitaire) -> GameResult<SolveReport> {
+        check_supported(game.rules())?;
+        let root = State::from_game(game)?;
+        Ok(Search::new(game.rules(), self.budget).run(root))
+    }
+
+    /// Try each seed in turn, collecting up to `count` that are proven winnable along with
+    /// their solutions. Seeds that run out of budget are skipped.
+    ///
+    /// # Errors
+    /// - [`SolitaireError::DeckTooSmall`] if the rules' deck cannot fill the tableau.
+    pub fn winnable_seeds(
+        &self,
+        seeds: impl IntoIterator<Item = u64>,
+        count: usize,
+    ) -> GameResult<Vec<(u64, Vec<SolverStep>)>> {
+        let mut found = Vec::new();
+        for seed in seeds {
+            if found.len() >= count {
+                break;
+            }
+            if let SolveOutcome::Solved(steps) = self.solve_seed(seed)?.outcome {
+                found.push((seed, steps));
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn check_supported(rules: &SolitaireRules) -> GameResult<()> {
+    if rules.foundation_rule != FoundationRule::SuitFromAce {
+        return Err(SolitaireError::UnsupportedRules("complete-run foundations".into()).into());
+    }
+    if rules.stock == StockRule::ToColumns {
+        return Err(SolitaireError::UnsupportedRules("dealing the stock to columns".into()).into());
+    }
+    let mut seen = HashSet::new();
+    if !rules.deck.iter().all(|card| seen.insert(encode(card))) {
+        return Err(SolitaireError::UnsupportedRules("duplicate cards".into()).into());
+    }
+    Ok(())
+}
+
+/// Pack a card into one byte: suit in the high bits, Ace-low rank in the low four.
+fn encode(card: &StandardCard) -> u8 {
+    let suit = match card.suit {
+        Suit::Clubs => 0,
+        Suit::Hearts => 1,
+        Suit::Diamonds => 2,
+        Suit::Spades => 3,
+        Suit::Wild => 4,
+    };
+    (suit << 4) | solitaire_value(card.rank)
+}
+
+fn value(code: u8) -> u8 {
+    code & 0x0f
+}
+
+fn suit(code: u8) -> u8 {
+    code >> 4
+}
+
+fn is_red(code: u8) -> bool {
+    matches!(suit(code), 1 | 2)
+}
+
+fn builds(rule: BuildRule, card: u8, under: u8) -> bool {
+    value(card) + 1 == value(under)
+        && match rule {
+            BuildRule::AlternatingColors => is_red(card) != is_red(under),
+            BuildRule::SameSuit => suit(card) == suit(under),
+            BuildRule::AnySuit => true,
+        }
+}
+
+/// The solver's own compact copy of a layout. A card code of 0 marks an empty cell or
+/// foundation; the first `hidden[c]` cards of column `c` are face down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    columns: Vec<Vec<u8>>,
+    hidden: Vec<usize>,
+    foundations: Vec<u8>,
+    cells: Vec<u8>,
+    stock: Vec<u8>,
+    waste: Vec<u8>,
+    redeals: usize,
+}
+
+impl State {
+    fn from_game(game: &Solitaire) -> GameResult<Self> {
+        let codes = |pile: &Pile<StandardCard>| -> Vec<u8> {
+            pile.cards()
+                .iter()
+                .map(|card| encode(&card.faces))
+                .collect()
+        };
+        let state = Self {
+            columns: game.columns().iter().map(codes).collect(),
+            hidden: game
+                .columns()
+                .iter()
+                .map(|pile| pile.cards().iter().take_while(|card| !card.face_up).count())
+                .collect(),
+            foundations: game
+                .foundations()
+                .iter()
+                .map(|pile| pile.check_top_card().map_or(0, |card| encode(&card.faces)))
+                .collect(),
+            cells: game
+                .cells()
+                .iter()
+                .map(|cell| {
+                    cell.as_ref()
+                        .map_or(0, |card: &Card<_>| encode(&card.faces))
+                })
+                .collect(),
+            stock: codes(game.stock()),
+            waste: codes(game.waste()),
+            redeals: game.redeals_used(),
+        };
+        let mut seen = HashSet::new();
+        let unique = state
+            .columns
+            .iter()
+            .flatten()
+            .chain(&state.cells)
+            .chain(&state.stock)
+            .chain(&state.waste)
+            .filter(|code| **code != 0)
+            .all(|code| seen.insert(*code));
+        if !unique {
+            return Err(SolitaireError::UnsupportedRules("duplicate cards".into()).into());
+        }
+        Ok(state)
+    }
+
+    fn is_won(&self) -> bool {
+        self.columns.iter().all(Vec::is_empty)
+            && self.cells.iter().all(|cell| *cell == 0)
+            && self.stock.is_empty()
+            && self.waste.is_empty()
+    }
+
+    /// Highest value on the foundations for each suit.
+    fn suit_progress(&self) -> [u8; 5] {
+        let mut progress = [0; 5];
+        for top in self.foundations.iter().filter(|top| **top != 0) {
+            let slot = &mut progress[usize::from(suit(*top))];
+            *slot = (*slot).max(value(*top));
+        }
+        progress
+    }
+
+    /// Estimated steps to a win: cards still off the foundations plus hidden cards.
+    fn heuristic(&self) -> usize {
+        let off = self.columns.iter().map(Vec::len).sum::<usize>()
+            + self.cells.iter().filter(|cell| **cell != 0).count()
+            + self.stock.len()
+            + self.waste.len();
+        // cards resting on a lower card of their own suit must move before it can
+        let blocked: usize = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut lowest = [u8::MAX; 5];
+                let mut count = 0;
+                for card in column {
+                    let low = &mut lowest[usize::from(suit(*card))];
+                    if value(*card) > *low {
+                        count += 1;
+                    }
+                    *low = (*low).min(value(*card));
+                }
+                count
+            })
+            .sum();
+        off + self.hidden.iter().sum::<usize>() + blocked
+    }
+
+    /// A canonical copy of the state that ignores the order of columns, cells, and
+    /// foundations, so equivalent layouts compare equal. Redeals only count toward the key
+    /// up to `redeal_limit`; with unlimited redeals (`None`) they are left out, so
+    /// recycling the stock returns to states already seen.
+    fn key(&self, redeal_limit: Option<usize>) -> StateKey {
+        let mut columns: Vec<(usize, Vec<u8>)> = self
+            .hidden
+            .iter()
+            .copied()
+            .zip(self.columns.iter().cloned())
+            .collect();
+        columns.sort_unstable();
+        let mut cells = self.cells.clone();
+        cells.sort_unstable();
+        StateKey {
+            columns,
+            cells,
+            progress: self.suit_progress(),
+            stock: self.stock.clone(),
+            waste: self.waste.clone(),
+            redeals: redeal_limit.map_or(0, |max| self.redeals.min(max)),
+        }
+    }
+
+    fn top(&self, zone: Zone) -> u8 {
+        match zone {
+            Zone::Column(idx) => self.columns[idx].last().copied().unwrap_or(0),
+            Zone::Cell(idx) => self.cells[idx],
+            Zone::Waste => self.waste.last().copied().unwrap_or(0),
+            Zone::Foundation(idx) => self.foundations[idx],
+        }
+    }
+}
+
+/// The full canonical form of a [`State`], stored whole so distinct states never collide.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct StateKey {
+    columns: Vec<(usize, Vec<u8>)>,
+    cells: Vec<u8>,
+    progress: [u8; 5],
+    stock: Vec<u8>,
+    waste: Vec<u8>,
+    redeals: usize,
+}
+
+struct Search<'a> {
+    rules: &'a SolitaireRules,
+    budget: SolverBudget,
+}
+
+impl<'a> Search<'a> {
+    /// Weight on the heuristic; above 1 trades optimal solutions for faster searches.
+    const WEIGHT: usize = 3;
+
+    fn new(rules: &'a SolitaireRules, budget: SolverBudget) -> Self {
+        Self { rules, budget }
+    }
+
+    /// How many times the waste may be turned back into the stock, or `None` if there is
+    /// no limit (or no waste).
+    fn redeal_limit(&self) -> Option<usize> {
+        match self.rules.stock {
+            StockRule::ToWaste { redeals, .. } => redeals,
+            _ => None,
+        }
+    }
+
+    fn run(&self, mut root: State) -> SolveReport {
+        let start = Instant::now();
+        let report = |outcome, nodes| SolveReport {
+            outcome,
+            nodes,
+            elapsed: start.elapsed(),
+        };
+
+        let mut first = Vec::new();
+        self.auto_move(&mut root, &mut first);
+        // (parent, steps from parent, cost so far) for every state generated
+        let mut arena: Vec<(usize, Vec<SolverStep>, usize)> = vec![(usize::MAX, first, 0)];
+        let mut states: Vec<Option<State>> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut open = BinaryHeap::new();
+        seen.insert(root.key(self.redeal_limit()));
+        open.push(Reverse((
+            Self::WEIGHT * root.heuristic(),
+            root.heuristic(),
+            0,
+        )));
+        states.push(Some(root));
+
+        let mut nodes = 0;
+        while let Some(Reverse((_, _, id))) = open.pop() {
+            let state = states[id].take().expect("each state is expanded once");
+            if state.is_won() {
+                return report(SolveOutcome::Solved(Self::path(&arena, id)), nodes);
+            }
+            nodes += 1;
+            let out_of_time = nodes % 256 == 0
+                && self
+                    .budget
+                    .time_limit
+                    .is_some_and(|limit| start.elapsed() >= limit);
+            if nodes > self.budget.max_nodes || out_of_time {
+                return report(SolveOutcome::OutOfBudget, nodes);
+            }
+
+            let cost = arena[id].2;
+            for step in self.steps(&state) {
+                let mut next = state.clone();
+                self.apply(&mut next, step);
+                let mut steps = vec![step];
+                self.auto_move(&mut next, &mut steps);
+                if !seen.insert(next.key(self.redeal_limit())) {
+                    continue;
+                }
+                let g = cost + steps.len();
+                let h = next.heuristic();
+                arena.push((id, steps, g));
+                open.push(Reverse((g + Self::WEIGHT * h, h, states.len())));
+                states.push(Some(next));
+            }
+        }
+        report(SolveOutcome::Unwinnable, nodes)
+    }
+
+    fn path(arena: &[(usize, Vec<SolverStep>, usize)], mut id: usize) -> Vec<SolverStep> {
+        let mut segments = Vec::new();
+        while id != usize::MAX {
+            segments.push(&arena[id].1);
+            id = arena[id].0;
+        }
+        segments.into_iter().rev().flatten().copied().collect()
+    }
+
+    /// The foundation a card may be played to, if any.
+    fn foundation_for(state: &State, card: u8) -> Option<usize> {
+        if value(card) == 1 {
+            return state.foundations.iter().position(|top| *top == 0);
+        }
+        state
+            .foundations
+            .iter()
+            .position(|top| *top != 0 && suit(*top) == suit(card) && value(*top) + 1 == value(card))
+    }
+
+    /// Returns `true` if no card left off the foundations could ever be built on `card`.
+    fn is_safe(&self, state: &State, card: u8) -> bool {
+        if value(card) <= 2 {
+            return true;
+        }
+        let progress = state.suit_progress();
+        (0..4u8).all(|s| {
+            let could_build = match self.rules.build_rule {
+                BuildRule::AlternatingColors => matches!(s, 1 | 2) != is_red(card),
+                BuildRule::SameSuit => s == suit(card),
+                BuildRule::AnySuit => true,
+            };
+            !could_build || progress[usize::from(s)] + 1 >= value(card)
+        })
+    }
+
+    /// Play every card that is safe to send to the foundations.
+    fn auto_move(&self, state: &mut State, steps: &mut Vec<SolverStep>) {
+        'search: loop {
+            let sources = (0..state.columns.len())
+                .map(Zone::Column)
+                .chain((0..state.cells.len()).map(Zone::Cell))
+                .chain(std::iter::once(Zone::Waste));
+            for from in sources {
+                let card = state.top(from);
+                if card == 0 || !self.is_safe(state, card) {
+                    continue;
+                }
+                if let Some(idx) = Self::foundation_for(state, card) {
+                    let step = SolverStep::Move(SolitaireMove::new(from, Zone::Foundation(idx), 1));
+                    self.apply(state, step);
+                    steps.push(step);
+                    continue 'search;
+                }
+            }
+            return;
+        }
+    }
+
+    fn max_run(&self, state: &State, to_empty_column: bool) -> usize {
+        if !self.rules.limit_by_free_cells {
+            return usize::MAX;
+        }
+        let free = state.cells.iter().filter(|cell| **cell == 0).count();
+        let empty = state
+            .columns
+            .iter()
+            .filter(|column| column.is_empty())
+            .count()
+            .saturating_sub(usize::from(to_empty_column));
+        (free + 1) << empty.min(16)
+    }
+
+    /// Candidate steps from `state`, skipping moves equivalent to ones already listed.
+    fn steps(&self, state: &State) -> Vec<SolverStep> {
+        let mut steps = Vec::new();
+        let mv = |from, to, count| SolverStep::Move(SolitaireMove::new(from, to, count));
+        let first_empty_column = state.columns.iter().position(Vec::is_empty);
+        let first_free_cell = state.cells.iter().position(|cell| *cell == 0);
+
+        // single cards to the foundations
+        let singles = (0..state.columns.len())
+            .map(Zone::Column)
+            .chain((0..state.cells.len()).map(Zone::Cell))
+            .chain(std::iter::once(Zone::Waste));
+        for from in singles {
+            let card = state.top(from);
+            if card != 0
+                && let Some(idx) = Self::foundation_for(state, card)
+            {
+                steps.push(mv(from, Zone::Foundation(idx), 1));
+            }
+        }
+
+        // runs between columns, and single cards into columns from cells and the waste
+        for (from_idx, column) in state.columns.iter().enumerate() {
+            let face_up = column.len() - state.hidden[from_idx];
+            let mut run = usize::from(face_up > 0);
+            while run < face_up
+                && builds(
+                    self.rules.move_rule,
+                    column[column.len() - run],
+                    column[column.len() - run - 1],
+                )
+            {
+                run += 1;
+            }
+            for count in 1..=run {
+                let base = column[column.len() - count];
+                for to in self.column_targets(state, base, count, first_empty_column) {
+                    if to != from_idx && !(count == column.len() && state.columns[to].is_empty()) {
+                        steps.push(mv(Zone::Column(from_idx), Zone::Column(to), count));
+                    }
+                }
+            }
+            if run > 0
+                && let Some(cell) = first_free_cell
+            {
+                steps.push(mv(Zone::Column(from_idx), Zone::Cell(cell), 1));
+            }
+        }
+        // single cards into columns from cells, the waste, and (where the rules allow it)
+        // the foundations; the waste and foundations can also fill a free cell
+        let back_down = if self.rules.foundation_to_tableau {
+            state.foundations.len()
+        } else {
+            0
+        };
+        let singles = (0..state.cells.len())
+            .map(Zone::Cell)
+            .chain(std::iter::once(Zone::Waste))
+            .chain((0..back_down).map(Zone::Foundation));
+        for from in singles {
+            let card = state.top(from);
+            if card == 0 {
+                continue;
+            }
+            for to in self.column_targets(state, card, 1, first_empty_column) {
+                steps.push(mv(from, Zone::Column(to), 1));
+            }
+            if !matches!(from, Zone::Cell(_))
+                && let Some(cell) = first_free_cell
+            {
+                steps.push(mv(from, Zone::Cell(cell), 1));
+            }
+        }
+
+        if let StockRule::ToWaste { redeals, .. } = self.rules.stock
+            && (!state.stock.is_empty()
+                || (!state.waste.is_empty() && redeals.is_none_or(|max| state.redeals < max)))
+        {
+            steps.push(SolverStep::Draw);
+        }
+        steps
+    }
+
+    /// Columns a run of `count` cards headed by `base` may move onto.
+    fn column_targets(
+        &self,
+        state: &State,
+        base: u8,
+        count: usize,
+        first_empty_column: Option<usize>,
+    ) -> Vec<usize> {
+        let mut targets = Vec::new();
+        for (idx, column) in state.columns.iter().enumerate() {
+            let fits = match column.last() {
+                None => {
+                    Some(idx) == first_empty_column
+                        && (self.rules.empty_column == EmptyColumnRule::AnyCard
+                            || value(base) == 13)
+                        && count <= self.max_run(state, true)
+                }
+                Some(top) => {
+                    builds(self.rules.build_rule, base, *top) && count <= self.max_run(state, false)
+                }
+            };
+            if fits {
+                targets.push(idx);
+            }
+        }
+        targets
+    }
+
+    fn apply(&self, state: &mut State, step: SolverStep) {
+        let SolverStep::Move(mv) = step else {
+            let StockRule::ToWaste { draw, .. } = self.rules.stock else {
+                unreachable!("draw steps are only generated for a waste stock");
+            };
+            if state.stock.is_empty() {
+                state.stock = state.waste.drain(..).rev().collect();
+                state.redeals += 1;
+            } else {
+                for _ in 0..draw {
+                    let Some(card) = state.stock.pop() else { break };
+                    state.waste.push(card);
+                }
+            }
+            return;
+        };
+
+        let cards: Vec<u8> = match mv.from {
+            Zone::Column(idx) => {
+                let column = &mut state.columns[idx];
+                let cards = column.split_off(column.len() - mv.count);
+                if column.len() == state.hidden[idx] {
+                    state.hidden[idx] = state.hidden[idx].saturating_sub(1);
+                }
+                cards
+            }
+            Zone::Cell(idx) => vec![std::mem::take(&mut state.cells[idx])],
+            Zone::Waste => state.waste.pop().into_iter().collect(),
+            Zone::Foundation(idx) => {
+                let card = state.foundations[idx];
+                state.foundations[idx] = if value(card) == 1 { 0 } else { card - 1 };
+                vec![card]
+            }
+        };
+        match mv.to {
+            Zone::Column(idx) => state.columns[idx].extend(cards),
+            Zone::Cell(idx) => state.cells[idx] = cards[0],
+            Zone::Foundation(idx) => state.foundations[idx] = cards[0],
+            Zone::Waste => unreachable!("the solver never moves onto the waste"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::std_playing_cards::Rank;
+
+    fn replay(rules: SolitaireRules, seed: u64, steps: &[SolverStep]) -> Solitaire {
+        let mut game = Solitaire::from_seed(rules, seed).unwrap();
+        for step in steps {
+            step.apply_to(&mut game).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn freecell_solutions_replay_to_a_win() {
+        let solver = SolitaireSolver::new(SolitaireRules::freecell()).unwrap();
+        let found = solver.winnable_seeds(0..20, 2).unwrap();
+        assert_eq!(found.len(), 2);
+        for (seed, steps) in found {
+            assert!(replay(SolitaireRules::freecell(), seed, &steps).is_won());
+        }
+    }
+
+    #[test]
+    fn klondike_solutions_replay_to_a_win() {
+        let rules = SolitaireRules::klondike(1);
+        let solver = SolitaireSolver::new(rules.clone()).unwrap();
+        let (seed, steps) = solver.winnable_seeds(0..40, 1).unwrap().pop().unwrap();
+        assert!(steps.contains(&SolverStep::Draw));
+        assert!(replay(rules, seed, &steps).is_won());
+    }
+
+    #[test]
+    fn exhausted_search_is_unwinnable() {
+        let mut rules = SolitaireRules::freecell();
+        rules.deck = vec![
+            StandardCard::new_card(Rank::Ace, Suit::Clubs),
+            StandardCard::new_card(Rank::Two, Suit::Clubs),
+        ];
+        rules.columns = vec![(0, 2)];
+        rules.free_cells = 0;
+        rules.foundations = 1;
+        let solver = SolitaireSolver::new(rules.clone()).unwrap();
+        let game = Solitaire::from_order(rules.clone(), rules.deck.clone()).unwrap();
+        assert_eq!(
+            solver.solve(&game).unwrap().outcome,
+            SolveOutcome::Unwinnable
+        );
+
+        let reversed = rules.deck.iter().rev().cloned().collect();
+        let game = Solitaire::from_order(rules, reversed).unwrap();
+        let report = solver.solve(&game).unwrap();
+        assert_eq!(report.solution().map(<[SolverStep]>::len), Some(2));
+    }
+
+    #[test]
+    fn unlimited_redeals_do_not_keep_the_search_alive() {
+        let mut rules = SolitaireRules::klondike(1);
+        // no Ace, so nothing ever reaches the foundation however often the stock cycles
+        rules.deck = vec![
+            StandardCard::new_card(Rank::Two, Suit::Clubs),
+            StandardCard::new_card(Rank::Three, Suit::Clubs),
+            StandardCard::new_card(Rank::Four, Suit::Clubs),
+        ];
+        rules.columns = vec![(0, 1)];
+        rules.foundations = 1;
+        let game = Solitaire::from_order(rules.clone(), rules.deck.clone()).unwrap();
+        let report = SolitaireSolver::new(rules).unwrap().solve(&game).unwrap();
+        assert_eq!(report.outcome, SolveOutcome::Unwinnable);
+        assert!(report.nodes < 20);
+    }
+
+    #[test]
+    fn cards_come_back_off_the_foundations_when_allowed() {
+        let card = StandardCard::new_card;
+        let mut rules = SolitaireRules::freecell();
+        // columns, bottom to top: 4H 2H 3H | 5H AH 4C | 3C 2C AC. The 3H can only move
+        // onto the 4C, which must go up to the foundations to uncover the 5H first.
+        rules.deck = vec![
+            card(Rank::Four, Suit::Hearts),
+            card(Rank::Five, Suit::Hearts),
+            card(Rank::Three, Suit::Clubs),
+            card(Rank::Two, Suit::Hearts),
+            card(Rank::Ace, Suit::Hearts),
+            card(Rank::Two, Suit::Clubs),
+            card(Rank::Three, Suit::Hearts),
+            card(Rank::Four, Suit::Clubs),
+            card(Rank::Ace, Suit::Clubs),
+        ];
+        rules.columns = vec![(0, 3); 3];
+        rules.free_cells = 0;
+        rules.foundations = 2;
+        rules.empty_column = EmptyColumnRule::KingsOnly;
+
+        let game = Solitaire::from_order(rules.clone(), rules.deck.clone()).unwrap();
+        let solver = SolitaireSolver::new(rules.clone()).unwrap();
+        assert_eq!(
+            solver.solve(&game).unwrap().outcome,
+            SolveOutcome::Unwinnable
+        );
+
+        rules.foundation_to_tableau = true;
+        let mut game = Solitaire::from_order(rules.clone(), rules.deck.clone()).unwrap();
+        let report = SolitaireSolver::new(rules).unwrap().solve(&game).unwrap();
+        let steps = report.solution().unwrap();
+        assert!(steps.iter().any(|step| matches!(
+            step,
+            SolverStep::Move(mv) if matches!(mv.from, Zone::Foundation(_))
+        )));
+        for step in steps {
+            step.apply_to(&mut game).unwrap();
+        }
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn budget_and_rule_limits_are_reported() {
+        let solver = SolitaireSolver::new(SolitaireRules::klondike(3))
+            .unwrap()
+            .with_budget(SolverBudget {
+                max_nodes: 5,
+                time_limit: None,
+            });
+        let report = solver.solve_seed(1).unwrap();
+        assert!(report.nodes <= 6);
+        assert!(matches!(
+            report.outcome,
+            SolveOutcome::OutOfBudget | SolveOutcome::Solved(_)
+        ));
+
+        assert!(matches!(
+            SolitaireSolver::new(SolitaireRules::spider(1).unwrap()),
+            Err(crate::GameError::SolitaireError(
+                SolitaireError::UnsupportedRules(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn state_key_ignores_column_order() {
+        let game = Solitaire::from_seed(SolitaireRules::freecell(), 3).unwrap();
+        let state = State::from_game(&game).unwrap();
+        let mut swapped = state.clone();
+        swapped.columns.swap(0, 5);
+        swapped.hidden.swap(0, 5);
+        assert_eq!(state.key(None), swapped.key(None));
+        swapped.cells[0] = swapped.columns[1].pop().unwrap();
+        assert_ne!(state.key(None), swapped.key(None));
+    }
+}
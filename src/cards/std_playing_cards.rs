@@ -24,7 +24,7 @@
 //! assert_eq!(ace_spades.suit, Suit::Spades);
 //! ```
 use crate::{
-    CardCollection as _,
+    AddCard as _, CardCollection as _, CardError, GameResult,
     cards::{Card, CardFaces, Hand},
 };
 use std::collections::BTreeMap;
@@ -230,6 +230,325 @@ impl Suit {
     }
 }
 
+/*
+ *
+ *  PARSING AND FORMATTING CARDS AS TEXT
+ *
+ */
+
+impl Rank {
+    /// The rank's full English name, e.g. "Queen".
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+            Rank::Joker => "Joker",
+        }
+    }
+
+    /// The one-character code used in short card codes, with `T` for ten.
+    #[must_use]
+    pub fn code(&self) -> char {
+        match self {
+            Rank::Ten => 'T',
+            Rank::Joker => '*',
+            rank => rank
+                .to_string()
+                .chars()
+                .next()
+                .expect("rank display is never empty"),
+        }
+    }
+}
+
+/// Reads ranks as digits ("2".."10"), letters ("T", "J", "Q", "K", "A"), or names ("queen"),
+/// ignoring case. Jokers are "*", "Jk", or "Joker".
+///
+/// ```
+/// use gametools::cards::std_playing_cards::Rank;
+///
+/// assert_eq!("10".parse::<Rank>(), Ok(Rank::Ten));
+/// assert_eq!("t".parse::<Rank>(), Ok(Rank::Ten));
+/// assert_eq!("Queen".parse::<Rank>(), Ok(Rank::Queen));
+/// ```
+impl std::str::FromStr for Rank {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_lowercase();
+        let rank = match text.as_str() {
+            "2" | "two" => Rank::Two,
+            "3" | "three" => Rank::Three,
+            "4" | "four" => Rank::Four,
+            "5" | "five" => Rank::Five,
+            "6" | "six" => Rank::Six,
+            "7" | "seven" => Rank::Seven,
+            "8" | "eight" => Rank::Eight,
+            "9" | "nine" => Rank::Nine,
+            "10" | "t" | "ten" => Rank::Ten,
+            "j" | "jack" => Rank::Jack,
+            "q" | "queen" => Rank::Queen,
+            "k" | "king" => Rank::King,
+            "a" | "1" | "ace" => Rank::Ace,
+            "*" | "jk" | "joker" => Rank::Joker,
+            _ => return Err(CardError::InvalidCardText(s.to_string())),
+        };
+        Ok(rank)
+    }
+}
+
+impl Suit {
+    /// The suit's full English name, e.g. "Hearts".
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Suit::Clubs => "Clubs",
+            Suit::Hearts => "Hearts",
+            Suit::Diamonds => "Diamonds",
+            Suit::Spades => "Spades",
+            Suit::Wild => "Wild",
+        }
+    }
+
+    /// The one-letter code used in short card codes.
+    #[must_use]
+    pub fn code(&self) -> char {
+        match self {
+            Suit::Clubs => 'C',
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Spades => 'S',
+            Suit::Wild => '*',
+        }
+    }
+}
+
+/// Reads suits as letters ("S"), names ("spades" or "spade"), or Unicode symbols, filled
+/// or outlined ("♠", "♤"), ignoring case.
+///
+/// ```
+/// use gametools::cards::std_playing_cards::Suit;
+///
+/// assert_eq!("h".parse::<Suit>(), Ok(Suit::Hearts));
+/// assert_eq!("♢".parse::<Suit>(), Ok(Suit::Diamonds));
+/// assert_eq!("Clubs".parse::<Suit>(), Ok(Suit::Clubs));
+/// ```
+impl std::str::FromStr for Suit {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_lowercase();
+        let suit = match text.as_str() {
+            "c" | "club" | "clubs" | "♣" | "♧" => Suit::Clubs,
+            "h" | "heart" | "hearts" | "♥" | "♡" => Suit::Hearts,
+            "d" | "diamond" | "diamonds" | "♦" | "♢" => Suit::Diamonds,
+            "s" | "spade" | "spades" | "♠" | "♤" => Suit::Spades,
+            "?" | "wild" => Suit::Wild,
+            _ => return Err(CardError::InvalidCardText(s.to_string())),
+        };
+        Ok(suit)
+    }
+}
+
+/// Text formats for writing out a [`StandardCard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CardFormat {
+    /// Rank and suit codes: "AS", "TH", "Jk" for a joker.
+    #[default]
+    Short,
+    /// Rank with a suit symbol: "A♠", "10♥", "🃏" for a joker.
+    Symbol,
+    /// Full name: "Ace of Spades", "Joker".
+    Long,
+    /// A single character from the Unicode Playing Cards block: "🂡".
+    Glyph,
+}
+
+/// First code point of each suit's run in the Unicode Playing Cards block.
+const GLYPH_SUITS: [(Suit, u32); 4] = [
+    (Suit::Spades, 0x1F0A0),
+    (Suit::Hearts, 0x1F0B0),
+    (Suit::Diamonds, 0x1F0C0),
+    (Suit::Clubs, 0x1F0D0),
+];
+/// The Unicode "playing card black joker".
+const GLYPH_JOKER: char = '\u{1F0CF}';
+
+impl StandardCard {
+    /// Write the card out in the given format.
+    ///
+    /// ```
+    /// use gametools::cards::std_playing_cards::{CardFormat, Rank, StandardCard, Suit};
+    ///
+    /// let card = StandardCard::new_card(Rank::Queen, Suit::Hearts);
+    /// assert_eq!(card.format(CardFormat::Short), "QH");
+    /// assert_eq!(card.format(CardFormat::Symbol), "Q♥");
+    /// assert_eq!(card.format(CardFormat::Long), "Queen of Hearts");
+    /// assert_eq!(card.format(CardFormat::Glyph), "🂽");
+    /// ```
+    #[must_use]
+    pub fn format(&self, format: CardFormat) -> String {
+        if self.rank == Rank::Joker {
+            return match format {
+                CardFormat::Short => "Jk".to_string(),
+                CardFormat::Long => "Joker".to_string(),
+                CardFormat::Symbol | CardFormat::Glyph => GLYPH_JOKER.to_string(),
+            };
+        }
+        match format {
+            CardFormat::Short => format!("{}{}", self.rank.code(), self.suit.code()),
+            CardFormat::Symbol => format!("{}{}", self.rank, self.suit),
+            CardFormat::Long => format!("{} of {}", self.rank.name(), self.suit.name()),
+            CardFormat::Glyph => self.glyph().map_or_else(|| "?".to_string(), String::from),
+        }
+    }
+
+    fn glyph(&self) -> Option<char> {
+        let (_, base) = GLYPH_SUITS.iter().find(|(suit, _)| *suit == self.suit)?;
+        // the block has a Knight between Jack and Queen
+        let offset = match self.rank {
+            Rank::Ace => 1,
+            Rank::Queen | Rank::King => self.rank as u32 + 1,
+            rank => rank as u32,
+        };
+        char::from_u32(base + offset)
+    }
+
+    fn from_glyph(glyph: char) -> Option<Self> {
+        let code = u32::from(glyph);
+        if matches!(code, 0x1F0BF | 0x1F0CF | 0x1F0DF) {
+            return Some(Self::new_card(Rank::Joker, Suit::Wild));
+        }
+        let (suit, base) = GLYPH_SUITS
+            .iter()
+            .find(|(_, base)| (base + 1..=base + 14).contains(&code))?;
+        let rank = match code - base {
+            1 => Rank::Ace,
+            12 => return None, // knight
+            13 => Rank::Queen,
+            14 => Rank::King,
+            value => Rank::from_value(u8::try_from(value).ok()?)?,
+        };
+        Some(Self::new_card(rank, *suit))
+    }
+}
+
+/// Reads a card from a short code ("AS", "10h", "Q♠", "Q.♥"), a long name
+/// ("Queen of Hearts"), or a Unicode playing-card glyph, ignoring case. Jokers are "Jk",
+/// "Joker", "*", or a joker glyph.
+///
+/// ```
+/// use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit};
+///
+/// let queen = StandardCard::new_card(Rank::Queen, Suit::Spades);
+/// assert_eq!("QS".parse::<StandardCard>(), Ok(queen));
+/// assert_eq!("q♠".parse::<StandardCard>(), Ok(queen));
+/// assert_eq!("Queen of Spades".parse::<StandardCard>(), Ok(queen));
+/// assert_eq!("Jk".parse::<StandardCard>().unwrap().rank, Rank::Joker);
+/// ```
+impl std::str::FromStr for StandardCard {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CardError::InvalidCardText(s.to_string());
+        let text = s.trim();
+        let lower = text.to_lowercase();
+
+        let mut chars = text.chars();
+        if let (Some(only), None) = (chars.next(), chars.next())
+            && let Some(card) = Self::from_glyph(only)
+        {
+            return Ok(card);
+        }
+        if matches!(
+            lower.as_str(),
+            "jk" | "joker" | "*" | "black joker" | "red joker" | "white joker"
+        ) {
+            return Ok(Self::new_card(Rank::Joker, Suit::Wild));
+        }
+        if let Some((rank, suit)) = lower.split_once(" of ") {
+            return Ok(Self::new_card(
+                rank.parse().map_err(|_| invalid())?,
+                suit.parse().map_err(|_| invalid())?,
+            ));
+        }
+
+        let (split, _) = text.char_indices().last().ok_or_else(invalid)?;
+        let (rank, suit) = text.split_at(split);
+        let rank = rank.trim_end_matches(['.', '-', ' ']);
+        let rank: Rank = rank.parse().map_err(|_| invalid())?;
+        let suit: Suit = suit.parse().map_err(|_| invalid())?;
+        if rank == Rank::Joker || suit == Suit::Wild {
+            return Err(invalid());
+        }
+        Ok(Self::new_card(rank, suit))
+    }
+}
+
+/// Read a list of cards separated by spaces and/or commas.
+///
+/// # Errors
+/// - [`CardError::InvalidCardText`] naming the first entry that is not a card.
+///
+/// ```
+/// use gametools::cards::std_playing_cards::{Rank, parse_cards};
+///
+/// let royal = parse_cards("AS KS QS JS TS").unwrap();
+/// assert_eq!(royal.len(), 5);
+/// assert_eq!(royal[4].rank, Rank::Ten);
+/// assert_eq!(parse_cards("10h, 9♥,8h").unwrap().len(), 3);
+/// ```
+pub fn parse_cards(text: &str) -> GameResult<Vec<StandardCard>> {
+    // long names contain spaces, so split on commas first when there are any
+    let entries: Vec<&str> = if text.to_lowercase().contains(" of ") {
+        text.split(',').collect()
+    } else {
+        text.split([',', ' ', '\t', '\n']).collect()
+    };
+    entries
+        .into_iter()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.parse().map_err(Into::into))
+        .collect()
+}
+
+/// Write out a list of cards in the given format, separated by spaces.
+///
+/// ```
+/// use gametools::cards::std_playing_cards::{CardFormat, format_cards, parse_cards};
+///
+/// let cards = parse_cards("AS 10H").unwrap();
+/// assert_eq!(format_cards(&cards, CardFormat::Short), "AS TH");
+/// assert_eq!(format_cards(&cards, CardFormat::Symbol), "A♠ 10♥");
+/// ```
+#[must_use]
+pub fn format_cards(cards: &[StandardCard], format: CardFormat) -> String {
+    let separator = if format == CardFormat::Long {
+        ", "
+    } else {
+        " "
+    };
+    cards
+        .iter()
+        .map(|card| card.format(format))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
 /*
  *
  *  DEFINITION OF COMMON DECK SETUPS WITH STANDARD CARDS TO SIMPLIFY DECK BUILDING
@@ -348,6 +667,45 @@ pub fn euchre_deck() -> Vec<StandardCard> {
 }
 
 impl Hand<StandardCard> {
+    /// Build a hand for `player` from a list of cards in text form; see [`parse_cards`].
+    ///
+    /// # Errors
+    /// - [`CardError::InvalidCardText`] naming the first entry that is not a card.
+    ///
+    /// ```
+    /// use gametools::{CardCollection, Hand};
+    /// use gametools::cards::std_playing_cards::{Rank, StandardCard, Suit};
+    ///
+    /// let hand = Hand::<StandardCard>::from_text("ann", "AS, KS, QS").unwrap();
+    /// assert_eq!(hand.size(), 3);
+    /// assert!(hand.contains(Rank::King, Suit::Spades));
+    /// ```
+    pub fn from_text(player: &str, text: &str) -> GameResult<Self> {
+        let mut hand = Self::new(player);
+        hand.add_cards(parse_cards(text)?.into_iter().map(Card::new_card).collect());
+        Ok(hand)
+    }
+
+    /// Sort the hand by suit, then by rank within each suit.
+    ///
+    /// ```
+    /// use gametools::Hand;
+    /// use gametools::cards::std_playing_cards::{format_cards, CardFormat, StandardCard};
+    ///
+    /// let mut hand = Hand::<StandardCard>::from_text("ann", "KH 2S 9H 2C").unwrap();
+    /// hand.sort_by_suit();
+    /// let faces: Vec<StandardCard> = hand.cards().iter().map(|c| c.faces).collect();
+    /// assert_eq!(format_cards(&faces, CardFormat::Short), "2C 9H KH 2S");
+    /// ```
+    pub fn sort_by_suit(&mut self) {
+        self.sort_by_key(|card| (card.faces.suit, card.faces.rank));
+    }
+
+    /// Sort the hand by rank, then by suit among cards of the same rank.
+    pub fn sort_by_rank(&mut self) {
+        self.sort_by_key(|card| (card.faces.rank, card.faces.suit));
+    }
+
     /// Check whether a card matching a rank and suit is in the `Hand`.
     ///
     /// ```
@@ -818,4 +1176,65 @@ mod tests {
         assert_eq!(wild_trio.len(), 3);
         assert!(wild_trio.iter().all(|card| card.rank == Rank::Joker));
     }
+
+    #[test]
+    fn every_card_round_trips_through_each_format() {
+        for card in standard_52_with_jokers() {
+            for format in [
+                CardFormat::Short,
+                CardFormat::Symbol,
+                CardFormat::Long,
+                CardFormat::Glyph,
+            ] {
+                let text = card.format(format);
+                assert_eq!(text.parse::<StandardCard>(), Ok(card), "{text}");
+            }
+            let front = card.display_front();
+            if card.rank != Rank::Joker {
+                assert_eq!(front.parse::<StandardCard>(), Ok(card), "{front}");
+            }
+        }
+    }
+
+    #[test]
+    fn parsing_accepts_varied_spellings() {
+        let ten = StandardCard::new_card(Rank::Ten, Suit::Hearts);
+        for text in [
+            "10h",
+            "TH",
+            " t♡ ",
+            "10-H",
+            "ten of hearts",
+            "Ten Of Hearts",
+        ] {
+            assert_eq!(text.parse::<StandardCard>(), Ok(ten), "{text}");
+        }
+        assert_eq!("Spade".parse::<Suit>(), Ok(Suit::Spades));
+        assert_eq!("1".parse::<Rank>(), Ok(Rank::Ace));
+        assert_eq!(
+            "\u{1F0AC}".parse::<StandardCard>(),
+            Err(CardError::InvalidCardText("\u{1F0AC}".to_string()))
+        );
+        for bad in ["", "ZZ", "1O", "*S", "AW", "Queen of Stars"] {
+            assert!(bad.parse::<StandardCard>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn card_lists_parse_and_format() {
+        let cards = parse_cards("AS KS, QS,JS  TS").unwrap();
+        assert_eq!(format_cards(&cards, CardFormat::Short), "AS KS QS JS TS");
+        let named = parse_cards("Ace of Spades, Jk, King of Hearts").unwrap();
+        assert_eq!(
+            format_cards(&named, CardFormat::Long),
+            "Ace of Spades, Joker, King of Hearts"
+        );
+        assert_eq!(
+            parse_cards("AS XX"),
+            Err(CardError::InvalidCardText("XX".to_string()).into())
+        );
+
+        let hand = Hand::<StandardCard>::from_text("p1", "2c 2d 2h").unwrap();
+        assert_eq!(hand.count_rank(Rank::Two), 3);
+    }
 }
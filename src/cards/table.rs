@@ -0,0 +1,118 @@
+//! Shared table mechanics for the shedding engines in [`uno_game`](crate::cards::uno_game)
+//! and [`shedding_game`](crate::cards::shedding_game): seat order, dealing with a
+//! turned-up starting card, and drawing with discard reshuffles.
+use crate::cards::{AddCard, CardCollection, CardFaces, Deck, Hand, Pile, TakeCard};
+
+#[cfg(test)]
+use crate::cards::Card;
+#[cfg(test)]
+use crate::test_support::{hand_of, pile_of};
+
+/// Hands, draw pile, and discard pile for one round.
+pub(crate) struct Table<T: CardFaces> {
+    pub hands: Vec<Hand<T>>,
+    pub draw_pile: Pile<T>,
+    pub discard: Pile<T>,
+}
+
+impl<T: CardFaces + Clone> Table<T> {
+    /// Shuffle `faces`, deal `hand_size` cards to each player, and turn up a starting card.
+    ///
+    /// Starters that `bury` rejects go back into the draw pile, which is reshuffled before
+    /// the next flip; after one flip per card the next card is accepted regardless, so a
+    /// deck made only of buried cards still starts.
+    pub fn deal(
+        names: &[&str],
+        faces: impl IntoIterator<Item = T>,
+        hand_size: usize,
+        draw_label: &str,
+        bury: impl Fn(&T) -> bool,
+    ) -> Self {
+        let mut deck = Deck::from_faces(draw_label, faces);
+        deck.shuffle();
+        let hands = deck.deal(names, hand_size);
+        let mut draw_pile = Pile::new_pile(draw_label);
+        draw_pile.add_cards(deck.take_cards(deck.size()));
+        let mut discard = Pile::new_pile("discard");
+
+        let attempts = draw_pile.size();
+        for attempt in 0..=attempts {
+            let Some(starter) = draw_pile.take_card() else {
+                break;
+            };
+            if bury(&starter.faces) && attempt < attempts {
+                draw_pile.add_card(starter);
+                draw_pile.shuffle();
+                continue;
+            }
+            discard.add_card(starter);
+            break;
+        }
+        Self {
+            hands,
+            draw_pile,
+            discard,
+        }
+    }
+
+    /// A table with fixed hands for seats `p0`, `p1`, ..., `top` alone on the discard
+    /// pile, and `stock` copies of `filler` in the draw pile.
+    #[cfg(test)]
+    pub fn rigged(hands: Vec<Vec<T>>, top: T, filler: &T, stock: usize, draw_label: &str) -> Self {
+        let stock = (0..stock).map(|_| Card::new_card(filler.clone())).collect();
+        Self {
+            hands: hands
+                .into_iter()
+                .enumerate()
+                .map(|(seat, faces)| hand_of(seat, faces))
+                .collect(),
+            draw_pile: pile_of(draw_label, stock),
+            discard: pile_of("discard", vec![Card::new_card(top)]),
+        }
+    }
+}
+
+/// The seat `steps` places after `seat` around a table of `players`, counterclockwise
+/// when `reversed`.
+pub(crate) fn seat_after(players: usize, seat: usize, steps: usize, reversed: bool) -> usize {
+    let steps = steps % players;
+    if reversed {
+        (seat + players - steps) % players
+    } else {
+        (seat + steps) % players
+    }
+}
+
+/// Draw up to `count` cards into `hand`, turning the discard pile (minus its top card)
+/// into a fresh draw pile when needed. Returns the uuid of the last card drawn, or `None`
+/// if nothing could be drawn.
+pub(crate) fn draw_into<T: CardFaces>(
+    hand: &mut Hand<T>,
+    draw_pile: &mut Pile<T>,
+    discard: &mut Pile<T>,
+    count: usize,
+) -> Option<uuid::Uuid> {
+    let mut last = None;
+    for _ in 0..count {
+        if draw_pile.size() == 0 {
+            reshuffle_discard(draw_pile, discard);
+        }
+        let Some(card) = draw_pile.take_card() else {
+            break;
+        };
+        last = Some(card.uuid);
+        hand.add_card(card);
+    }
+    last
+}
+
+/// Shuffle everything under the top discard back into the draw pile.
+fn reshuffle_discard<T: CardFaces>(draw_pile: &mut Pile<T>, discard: &mut Pile<T>) {
+    let Some(top) = discard.take_card() else {
+        return;
+    };
+    let count = discard.size();
+    draw_pile.add_cards(discard.take_cards(count));
+    draw_pile.shuffle();
+    discard.add_card(top);
+}
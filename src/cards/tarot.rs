@@ -0,0 +1,757 @@
+//! # Tarot Cards
+//!
+//! A 78-card tarot face type: the 22 major arcana plus 56 minor arcana in four suits of
+//! Ace through Ten, Page, Knight, Queen, and King. Cards carry an upright/reversed
+//! orientation that is normally decided as each card is drawn, and [`Spread`]s lay drawn
+//! cards out into named positions for readings.
+//!
+//! The same deck plays French Tarot, where the numbered majors are trumps, the Fool is the
+//! Excuse, and face cards outrank the pips. [`french_trick_winner`], [`french_legal_plays`],
+//! and [`french_card_points`] cover that game's trick and scoring rules.
+//!
+//! ```
+//! use gametools::{CardCollection, Deck};
+//! use gametools::cards::tarot::{Spread, tarot_deck};
+//!
+//! let mut deck = Deck::from_faces("tarot", tarot_deck());
+//! assert_eq!(deck.size(), 78);
+//! deck.shuffle();
+//!
+//! let reading = deck.deal_spread(&Spread::three_card(), 0.5).unwrap();
+//! assert_eq!(reading.placements().len(), 3);
+//! assert!(reading.card_at("Future").is_some());
+//! assert_eq!(deck.size(), 75);
+//! ```
+use crate::{
+    CardError, GameResult,
+    cards::{Card, CardFaces, Deck, TakeCard as _},
+};
+use rand::Rng;
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The 22 major arcana, numbered 0 (The Fool) through 21 (The World).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MajorArcana {
+    Fool = 0,
+    Magician,
+    HighPriestess,
+    Empress,
+    Emperor,
+    Hierophant,
+    Lovers,
+    Chariot,
+    Strength,
+    Hermit,
+    WheelOfFortune,
+    Justice,
+    HangedMan,
+    Death,
+    Temperance,
+    Devil,
+    Tower,
+    Star,
+    Moon,
+    Sun,
+    Judgement,
+    World,
+}
+impl MajorArcana {
+    /// All 22 major arcana in numbered order.
+    #[must_use]
+    pub fn all() -> Vec<MajorArcana> {
+        (0..=21).filter_map(MajorArcana::from_number).collect()
+    }
+
+    /// The major arcana with the given number, if it is 0 through 21.
+    #[must_use]
+    pub fn from_number(number: u8) -> Option<MajorArcana> {
+        use MajorArcana::*;
+        let card = match number {
+            0 => Fool,
+            1 => Magician,
+            2 => HighPriestess,
+            3 => Empress,
+            4 => Emperor,
+            5 => Hierophant,
+            6 => Lovers,
+            7 => Chariot,
+            8 => Strength,
+            9 => Hermit,
+            10 => WheelOfFortune,
+            11 => Justice,
+            12 => HangedMan,
+            13 => Death,
+            14 => Temperance,
+            15 => Devil,
+            16 => Tower,
+            17 => Star,
+            18 => Moon,
+            19 => Sun,
+            20 => Judgement,
+            21 => World,
+            _ => return None,
+        };
+        Some(card)
+    }
+
+    /// The card's number, 0 through 21.
+    #[must_use]
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The card's traditional title, e.g. "The High Priestess".
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        use MajorArcana::*;
+        match self {
+            Fool => "The Fool",
+            Magician => "The Magician",
+            HighPriestess => "The High Priestess",
+            Empress => "The Empress",
+            Emperor => "The Emperor",
+            Hierophant => "The Hierophant",
+            Lovers => "The Lovers",
+            Chariot => "The Chariot",
+            Strength => "Strength",
+            Hermit => "The Hermit",
+            WheelOfFortune => "Wheel of Fortune",
+            Justice => "Justice",
+            HangedMan => "The Hanged Man",
+            Death => "Death",
+            Temperance => "Temperance",
+            Devil => "The Devil",
+            Tower => "The Tower",
+            Star => "The Star",
+            Moon => "The Moon",
+            Sun => "The Sun",
+            Judgement => "Judgement",
+            World => "The World",
+        }
+    }
+}
+
+/// The four minor arcana suits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TarotSuit {
+    Wands,
+    Cups,
+    Swords,
+    Pentacles,
+}
+impl TarotSuit {
+    /// All four suits.
+    #[must_use]
+    pub fn all() -> Vec<TarotSuit> {
+        vec![
+            TarotSuit::Wands,
+            TarotSuit::Cups,
+            TarotSuit::Swords,
+            TarotSuit::Pentacles,
+        ]
+    }
+}
+impl std::fmt::Display for TarotSuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TarotSuit::Wands => "Wands",
+            TarotSuit::Cups => "Cups",
+            TarotSuit::Swords => "Swords",
+            TarotSuit::Pentacles => "Pentacles",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Minor arcana ranks, Ace (1) through Ten plus the four court cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TarotRank {
+    Ace = 1,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Page,
+    Knight,
+    Queen,
+    King,
+}
+impl TarotRank {
+    /// All fourteen ranks from Ace up to King.
+    #[must_use]
+    pub fn all() -> Vec<TarotRank> {
+        use TarotRank::*;
+        vec![
+            Ace, Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Page, Knight, Queen, King,
+        ]
+    }
+
+    /// Whether this is a Page, Knight, Queen, or King.
+    #[must_use]
+    pub fn is_court(&self) -> bool {
+        *self >= TarotRank::Page
+    }
+}
+impl std::fmt::Display for TarotRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TarotRank::*;
+        let name = match self {
+            Ace => "Ace",
+            Two => "Two",
+            Three => "Three",
+            Four => "Four",
+            Five => "Five",
+            Six => "Six",
+            Seven => "Seven",
+            Eight => "Eight",
+            Nine => "Nine",
+            Ten => "Ten",
+            Page => "Page",
+            Knight => "Knight",
+            Queen => "Queen",
+            King => "King",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Which arcana a card belongs to. Minor cards sort below all majors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Arcana {
+    Minor(TarotRank, TarotSuit),
+    Major(MajorArcana),
+}
+
+/// A tarot card face and its orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TarotCard {
+    /// The card's identity.
+    pub arcana: Arcana,
+    /// Whether the card was drawn upside down.
+    pub reversed: bool,
+}
+impl TarotCard {
+    /// An upright major arcana card.
+    #[must_use]
+    pub fn major(card: MajorArcana) -> Self {
+        Self {
+            arcana: Arcana::Major(card),
+            reversed: false,
+        }
+    }
+
+    /// An upright minor arcana card.
+    #[must_use]
+    pub fn minor(rank: TarotRank, suit: TarotSuit) -> Self {
+        Self {
+            arcana: Arcana::Minor(rank, suit),
+            reversed: false,
+        }
+    }
+
+    /// A copy of this card with the given orientation.
+    #[must_use]
+    pub fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// The card's name without orientation, e.g. "Knight of Swords" or "The Tower".
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self.arcana {
+            Arcana::Major(card) => card.name().to_string(),
+            Arcana::Minor(rank, suit) => format!("{rank} of {suit}"),
+        }
+    }
+
+    /// The suit of a minor arcana card; `None` for majors.
+    #[must_use]
+    pub fn suit(&self) -> Option<TarotSuit> {
+        match self.arcana {
+            Arcana::Minor(_, suit) => Some(suit),
+            Arcana::Major(_) => None,
+        }
+    }
+
+    /// The trump number (1-21) in French Tarot; `None` for minors and the Excuse.
+    #[must_use]
+    pub fn trump_number(&self) -> Option<u8> {
+        match self.arcana {
+            Arcana::Major(MajorArcana::Fool) | Arcana::Minor(..) => None,
+            Arcana::Major(card) => Some(card.number()),
+        }
+    }
+
+    /// Whether this is the Fool, which French Tarot plays as the Excuse.
+    #[must_use]
+    pub fn is_excuse(&self) -> bool {
+        self.arcana == Arcana::Major(MajorArcana::Fool)
+    }
+
+    /// Whether this is one of the three French Tarot oudlers: trump 1, trump 21, or the Excuse.
+    #[must_use]
+    pub fn is_oudler(&self) -> bool {
+        self.is_excuse() || matches!(self.trump_number(), Some(1 | 21))
+    }
+}
+impl CardFaces for TarotCard {
+    fn display_front(&self) -> String {
+        if self.reversed {
+            format!("{} (reversed)", self.name())
+        } else {
+            self.name()
+        }
+    }
+
+    fn display_back(&self) -> Option<String> {
+        None
+    }
+
+    /// Cards match by identity; orientation is ignored.
+    fn matches(&self, other: &Self) -> bool {
+        self.arcana == other.arcana
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        self.arcana.cmp(&other.arcana)
+    }
+}
+
+/// Create the full 78-card tarot deck, all upright: majors 0-21, then each suit Ace to King.
+#[must_use]
+pub fn tarot_deck() -> Vec<TarotCard> {
+    let mut cards: Vec<TarotCard> = MajorArcana::all()
+        .into_iter()
+        .map(TarotCard::major)
+        .collect();
+    for suit in TarotSuit::all() {
+        cards.extend(
+            TarotRank::all()
+                .into_iter()
+                .map(|rank| TarotCard::minor(rank, suit)),
+        );
+    }
+    cards
+}
+
+/// One named position in a [`Spread`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpreadPosition {
+    /// Short label, e.g. "Outcome".
+    pub name: String,
+    /// What a card in this position speaks to.
+    pub meaning: String,
+}
+
+/// An ordered layout of named positions that drawn cards are placed into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spread {
+    /// Name of the spread.
+    pub name: String,
+    /// Positions in the order cards are dealt into them.
+    pub positions: Vec<SpreadPosition>,
+}
+impl Spread {
+    /// Build a spread from `(name, meaning)` pairs in dealing order.
+    #[must_use]
+    pub fn custom(name: &str, positions: &[(&str, &str)]) -> Self {
+        Self {
+            name: name.to_string(),
+            positions: positions
+                .iter()
+                .map(|(name, meaning)| SpreadPosition {
+                    name: (*name).to_string(),
+                    meaning: (*meaning).to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Past, Present, Future.
+    #[must_use]
+    pub fn three_card() -> Self {
+        Self::custom(
+            "Three Card",
+            &[
+                ("Past", "influences that led here"),
+                ("Present", "the situation as it stands"),
+                ("Future", "where things are heading"),
+            ],
+        )
+    }
+
+    /// The ten-card Celtic Cross.
+    #[must_use]
+    pub fn celtic_cross() -> Self {
+        Self::custom(
+            "Celtic Cross",
+            &[
+                ("Present", "the heart of the matter"),
+                ("Challenge", "what crosses the querent"),
+                ("Foundation", "the root beneath the situation"),
+                ("Recent Past", "what is passing away"),
+                ("Crown", "the best that can be achieved"),
+                ("Near Future", "what is coming next"),
+                ("Self", "the querent's attitude"),
+                ("Environment", "outside influences"),
+                ("Hopes and Fears", "what the querent hopes or dreads"),
+                ("Outcome", "the likely result"),
+            ],
+        )
+    }
+
+    /// Number of cards the spread needs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether the spread has no positions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// Cards dealt into the positions of a [`Spread`].
+#[derive(Debug, Clone)]
+pub struct Reading {
+    spread: Spread,
+    cards: Vec<Card<TarotCard>>,
+}
+impl Reading {
+    /// The spread this reading was dealt into.
+    #[must_use]
+    pub fn spread(&self) -> &Spread {
+        &self.spread
+    }
+
+    /// Each position paired with the card dealt into it, in dealing order.
+    #[must_use]
+    pub fn placements(&self) -> Vec<(&SpreadPosition, &Card<TarotCard>)> {
+        self.spread.positions.iter().zip(&self.cards).collect()
+    }
+
+    /// The card in the position with the given name, ignoring case.
+    #[must_use]
+    pub fn card_at(&self, position: &str) -> Option<&Card<TarotCard>> {
+        self.spread
+            .positions
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(position))
+            .map(|i| &self.cards[i])
+    }
+
+    /// Give the dealt cards back, in dealing order.
+    #[must_use]
+    pub fn into_cards(self) -> Vec<Card<TarotCard>> {
+        self.cards
+    }
+}
+
+impl Deck<TarotCard> {
+    /// Draw the top card, reversing it with probability `reversal_chance` (0.0-1.0).
+    pub fn draw_oriented(&mut self, reversal_chance: f64) -> Option<Card<TarotCard>> {
+        self.draw_oriented_with(reversal_chance, &mut rand::rng())
+    }
+
+    /// Like [`draw_oriented`](Self::draw_oriented), using the given random source.
+    pub fn draw_oriented_with<R: Rng + ?Sized>(
+        &mut self,
+        reversal_chance: f64,
+        rng: &mut R,
+    ) -> Option<Card<TarotCard>> {
+        let mut card = self.take_card()?;
+        card.faces.reversed = rng.random_bool(reversal_chance.clamp(0.0, 1.0));
+        Some(card)
+    }
+
+    /// Deal one oriented card into each position of `spread`.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if the deck holds fewer cards than the spread needs;
+    ///   no cards are drawn in that case.
+    pub fn deal_spread(&mut self, spread: &Spread, reversal_chance: f64) -> GameResult<Reading> {
+        self.deal_spread_with(spread, reversal_chance, &mut rand::rng())
+    }
+
+    /// Like [`deal_spread`](Self::deal_spread), using the given random source.
+    ///
+    /// # Errors
+    /// - [`CardError::StackTooSmall`] if the deck holds fewer cards than the spread needs.
+    pub fn deal_spread_with<R: Rng + ?Sized>(
+        &mut self,
+        spread: &Spread,
+        reversal_chance: f64,
+        rng: &mut R,
+    ) -> GameResult<Reading> {
+        if self.cards().len() < spread.len() {
+            return Err(CardError::StackTooSmall(self.name.clone()).into());
+        }
+        let cards = (0..spread.len())
+            .filter_map(|_| self.draw_oriented_with(reversal_chance, rng))
+            .collect();
+        Ok(Reading {
+            spread: spread.clone(),
+            cards,
+        })
+    }
+}
+
+/// Card points in French Tarot: oudlers and Kings 4.5, Queens 3.5, Knights 2.5, Pages 1.5,
+/// everything else 0.5. A full deck totals 91.
+#[must_use]
+pub fn french_card_points(card: &TarotCard) -> f64 {
+    if card.is_oudler() {
+        return 4.5;
+    }
+    match card.arcana {
+        Arcana::Minor(TarotRank::King, _) => 4.5,
+        Arcana::Minor(TarotRank::Queen, _) => 3.5,
+        Arcana::Minor(TarotRank::Knight, _) => 2.5,
+        Arcana::Minor(TarotRank::Page, _) => 1.5,
+        _ => 0.5,
+    }
+}
+
+/// Points the taker needs in French Tarot given how many oudlers they won: 56, 51, 41, or 36.
+#[must_use]
+pub fn french_contract_target(oudlers: usize) -> f64 {
+    match oudlers {
+        0 => 56.0,
+        1 => 51.0,
+        2 => 41.0,
+        _ => 36.0,
+    }
+}
+
+/// The suit led in a French Tarot trick: the first card that isn't the Excuse.
+/// `Some(None)` means trumps were led; `None` means nothing but the Excuse has been played.
+fn led_suit(trick: &[TarotCard]) -> Option<Option<TarotSuit>> {
+    trick
+        .iter()
+        .find(|card| !card.is_excuse())
+        .map(TarotCard::suit)
+}
+
+/// Index of the card winning a French Tarot trick so far, with cards in play order.
+///
+/// The highest trump wins; without trumps, the highest card of the led suit wins. The
+/// Excuse never wins. Returns `None` for an empty trick or one holding only the Excuse.
+///
+/// ```
+/// use gametools::cards::tarot::{MajorArcana, TarotCard, TarotRank, TarotSuit, french_trick_winner};
+///
+/// let trick = [
+///     TarotCard::minor(TarotRank::King, TarotSuit::Cups),
+///     TarotCard::major(MajorArcana::Magician),
+///     TarotCard::minor(TarotRank::Ace, TarotSuit::Cups),
+/// ];
+/// assert_eq!(french_trick_winner(&trick), Some(1));
+/// ```
+#[must_use]
+pub fn french_trick_winner(trick: &[TarotCard]) -> Option<usize> {
+    let led = led_suit(trick)?;
+    let strength = |card: &TarotCard| match (card.trump_number(), card.arcana) {
+        (Some(trump), _) => Some(100 + trump),
+        (None, Arcana::Minor(rank, suit)) if Some(suit) == led => Some(rank as u8),
+        _ => None,
+    };
+    trick
+        .iter()
+        .enumerate()
+        .filter_map(|(i, card)| strength(card).map(|s| (s, i)))
+        .max()
+        .map(|(_, i)| i)
+}
+
+/// Indexes of the cards in `hand` that may legally be played to a French Tarot trick.
+///
+/// Players must follow the led suit; if void they must trump, and when playing a trump
+/// they must beat the highest trump already in the trick if they can. The Excuse may
+/// always be played.
+#[must_use]
+pub fn french_legal_plays(hand: &[TarotCard], trick: &[TarotCard]) -> Vec<usize> {
+    let all: Vec<usize> = (0..hand.len()).collect();
+    let Some(led) = led_suit(trick) else {
+        return all;
+    };
+    let best_trump = trick.iter().filter_map(TarotCard::trump_number).max();
+    let trump_plays = || {
+        let trumps: Vec<usize> = all
+            .iter()
+            .copied()
+            .filter(|&i| hand[i].trump_number().is_some())
+            .collect();
+        let over: Vec<usize> = trumps
+            .iter()
+            .copied()
+            .filter(|&i| hand[i].trump_number() > best_trump)
+            .collect();
+        if over.is_empty() { trumps } else { over }
+    };
+    let mut plays = match led {
+        Some(suit) => {
+            let following: Vec<usize> = all
+                .iter()
+                .copied()
+                .filter(|&i| hand[i].suit() == Some(suit))
+                .collect();
+            if following.is_empty() {
+                trump_plays()
+            } else {
+                following
+            }
+        }
+        None => trump_plays(),
+    };
+    if plays.is_empty() {
+        plays = all
+            .iter()
+            .copied()
+            .filter(|&i| !hand[i].is_excuse())
+            .collect();
+    }
+    plays.extend(all.iter().copied().filter(|&i| hand[i].is_excuse()));
+    plays.sort_unstable();
+    plays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardCollection as _;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn minor(rank: TarotRank, suit: TarotSuit) -> TarotCard {
+        TarotCard::minor(rank, suit)
+    }
+
+    fn trump(number: u8) -> TarotCard {
+        TarotCard::major(MajorArcana::from_number(number).unwrap())
+    }
+
+    #[test]
+    fn deck_has_all_seventy_eight_distinct_cards() {
+        let deck = tarot_deck();
+        assert_eq!(deck.len(), 78);
+        assert_eq!(deck.iter().filter(|c| c.suit().is_none()).count(), 22);
+        for (i, a) in deck.iter().enumerate() {
+            assert!(deck[i + 1..].iter().all(|b| !a.matches(b)));
+        }
+        assert_eq!(deck[0].name(), "The Fool");
+        assert_eq!(deck[21].display_front(), "The World");
+        assert_eq!(deck[22].name(), "Ace of Wands");
+        assert!(deck.iter().all(|c| !c.reversed));
+    }
+
+    #[test]
+    fn orientation_shows_in_display_but_not_matching() {
+        let card = minor(TarotRank::Knight, TarotSuit::Swords);
+        let flipped = card.with_reversed(true);
+        assert_eq!(flipped.display_front(), "Knight of Swords (reversed)");
+        assert!(card.matches(&flipped));
+        assert_eq!(
+            trump(1).compare(&minor(TarotRank::King, TarotSuit::Cups)),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn draws_set_orientation_at_draw_time() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut deck = Deck::from_faces("tarot", tarot_deck());
+        let upright = deck.draw_oriented_with(0.0, &mut rng).unwrap();
+        assert!(!upright.faces.reversed);
+        let reversed = deck.draw_oriented_with(1.0, &mut rng).unwrap();
+        assert!(reversed.faces.reversed);
+        let mixed: Vec<bool> = (0..40)
+            .filter_map(|_| deck.draw_oriented_with(0.5, &mut rng))
+            .map(|c| c.faces.reversed)
+            .collect();
+        assert!(mixed.contains(&true) && mixed.contains(&false));
+    }
+
+    #[test]
+    fn celtic_cross_fills_named_positions() {
+        let mut deck = Deck::from_faces("tarot", tarot_deck());
+        let reading = deck.deal_spread(&Spread::celtic_cross(), 0.0).unwrap();
+        assert_eq!(reading.placements().len(), 10);
+        assert_eq!(deck.size(), 68);
+        // the deck deals from the end, so the first position gets the last card
+        let present = reading.card_at("present").unwrap();
+        assert_eq!(present.faces.name(), "King of Pentacles");
+        assert_eq!(
+            reading.card_at("Outcome").unwrap().faces.name(),
+            "Five of Pentacles"
+        );
+
+        let mut small = Deck::from_faces("short", tarot_deck().into_iter().take(2));
+        assert!(small.deal_spread(&Spread::three_card(), 0.0).is_err());
+        assert_eq!(small.size(), 2);
+    }
+
+    #[test]
+    fn french_points_total_ninety_one() {
+        let total: f64 = tarot_deck().iter().map(french_card_points).sum();
+        assert!((total - 91.0).abs() < f64::EPSILON);
+        assert_eq!(tarot_deck().iter().filter(|c| c.is_oudler()).count(), 3);
+        assert!((french_contract_target(2) - 41.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn french_trick_ranking_and_excuse() {
+        let excuse = trump(0);
+        let trick = [
+            excuse,
+            minor(TarotRank::Ten, TarotSuit::Wands),
+            minor(TarotRank::Page, TarotSuit::Wands),
+            minor(TarotRank::King, TarotSuit::Cups),
+        ];
+        assert_eq!(french_trick_winner(&trick), Some(2));
+        assert_eq!(
+            french_trick_winner(&[trick[0], trick[1], trump(2), trump(21)]),
+            Some(3)
+        );
+        assert_eq!(french_trick_winner(&[excuse]), None);
+    }
+
+    #[test]
+    fn french_legal_plays_follow_trump_and_overtrump() {
+        let hand = [
+            minor(TarotRank::Two, TarotSuit::Cups),
+            trump(5),
+            trump(15),
+            trump(0),
+        ];
+        // can follow suit
+        let led_cups = [minor(TarotRank::King, TarotSuit::Cups)];
+        assert_eq!(french_legal_plays(&hand, &led_cups), vec![0, 3]);
+        // void in swords: must trump, and overtrump the 10 when able
+        let led_swords = [minor(TarotRank::Ace, TarotSuit::Swords), trump(10)];
+        assert_eq!(french_legal_plays(&hand, &led_swords), vec![2, 3]);
+        // can't overtrump the 20, so any trump will do
+        let high = [trump(20)];
+        assert_eq!(french_legal_plays(&hand, &high), vec![1, 2, 3]);
+        // no trumps and void: anything goes
+        let short = [minor(TarotRank::Two, TarotSuit::Cups)];
+        assert_eq!(french_legal_plays(&short, &led_swords), vec![0]);
+    }
+}
@@ -1,6 +1,12 @@
 //! Uno Card Module
+//!
+//! Card faces, deck builders, and matching rules for Uno and its common variants.
+//! The classic 108-card deck comes from [`full_uno_set`], while [`UnoDeckRecipe`] describes
+//! custom deck compositions and [`uno_flip_set`] builds the double-sided Uno Flip deck.
+//! Legality checks can be swapped out by implementing [`PlayRule`].
 
 use crate::{Card, cards::CardFaces};
+use rand::seq::SliceRandom;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -42,8 +48,7 @@ impl UnoCard {
     /// Returns `true` if this card can be legally played on the `other` card.
     #[must_use]
     pub fn plays_on(&self, other: &UnoCard, declared_color: Option<UnoColor>) -> bool {
-        use UnoAction::{DrawTwo, Reverse, Skip};
-        use UnoCardKind::{Action, Number, Wild, WildDrawFour};
+        use UnoCardKind::{Action, Number};
         if let Some(declared) = declared_color
             && self.color == declared
         {
@@ -54,7 +59,7 @@ impl UnoCard {
             return true;
         }
         match self.kind {
-            Wild | WildDrawFour => true,
+            kind if kind.is_wild() => true,
             Number(x) => {
                 if let Number(other) = other.kind {
                     x == other
@@ -62,22 +67,101 @@ impl UnoCard {
                     false
                 }
             }
-            Action(uno_action) => match uno_action {
-                DrawTwo => matches!(other.kind, Action(DrawTwo)),
-                Skip => matches!(other.kind, Action(Skip)),
-                Reverse => matches!(other.kind, Action(Reverse)),
-            },
+            Action(uno_action) => other.kind == Action(uno_action),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this card can be played on the `other` card under a custom [`PlayRule`].
+    ///
+    /// ```
+    /// use gametools::cards::uno_cards::{DosRule, UnoCard, UnoCardKind, UnoColor};
+    ///
+    /// let red_four = UnoCard { color: UnoColor::Red, kind: UnoCardKind::Number(4) };
+    /// let blue_four = UnoCard { color: UnoColor::Blue, kind: UnoCardKind::Number(4) };
+    /// let red_five = UnoCard { color: UnoColor::Red, kind: UnoCardKind::Number(5) };
+    ///
+    /// assert!(red_four.plays_on_with(&blue_four, None, &DosRule));
+    /// assert!(!red_four.plays_on_with(&red_five, None, &DosRule)); // Dos ignores color
+    /// ```
+    #[must_use]
+    pub fn plays_on_with(
+        &self,
+        other: &UnoCard,
+        declared_color: Option<UnoColor>,
+        rule: &impl PlayRule,
+    ) -> bool {
+        rule.plays_on(self, other, declared_color)
+    }
+}
+
+/// Decides whether one Uno card may legally be played on another.
+///
+/// Implement this to change matching without forking the module; [`ClassicRule`] reproduces
+/// [`UnoCard::plays_on`] and [`DosRule`] matches on numbers only.
+pub trait PlayRule: std::fmt::Debug + Send + Sync {
+    /// Returns `true` if `card` may be played on `top`, given any color declared by a wild.
+    fn plays_on(&self, card: &UnoCard, top: &UnoCard, declared_color: Option<UnoColor>) -> bool;
+}
+
+/// The standard Uno rule: match color, number, or action, or play any wild.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClassicRule;
+
+impl PlayRule for ClassicRule {
+    fn plays_on(&self, card: &UnoCard, top: &UnoCard, declared_color: Option<UnoColor>) -> bool {
+        card.plays_on(top, declared_color)
+    }
+}
+
+/// Dos-style matching: number cards match on number alone, regardless of color.
+///
+/// Wilds still play on anything, a wild on top is followed by any card of the declared
+/// color, and action cards still match the same action. Dos also
+/// allows two cards whose numbers sum to the target to be played together; see
+/// [`DosRule::pair_plays_on`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DosRule;
+
+impl DosRule {
+    /// Returns `true` if two number cards together sum to the number on `top`.
+    #[must_use]
+    pub fn pair_plays_on(first: &UnoCard, second: &UnoCard, top: &UnoCard) -> bool {
+        match (first.kind, second.kind, top.kind) {
+            (UnoCardKind::Number(a), UnoCardKind::Number(b), UnoCardKind::Number(target)) => {
+                u16::from(a) + u16::from(b) == u16::from(target)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PlayRule for DosRule {
+    fn plays_on(&self, card: &UnoCard, top: &UnoCard, declared_color: Option<UnoColor>) -> bool {
+        match (card.kind, top.kind) {
+            (kind, _) if kind.is_wild() => true,
+            (_, top) if top.is_wild() => declared_color == Some(card.color),
+            (UnoCardKind::Number(a), UnoCardKind::Number(b)) => a == b,
+            (UnoCardKind::Action(a), UnoCardKind::Action(b)) => a == b,
+            _ => false,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum UnoColor {
     Red,
     Blue,
     Green,
     Yellow,
+    Pink,
+    Teal,
+    Orange,
+    Purple,
     Black,
 }
 impl std::fmt::Display for UnoColor {
@@ -87,6 +171,10 @@ impl std::fmt::Display for UnoColor {
             UnoColor::Blue => write!(f, "Blue"),
             UnoColor::Green => write!(f, "Green"),
             UnoColor::Yellow => write!(f, "Yellow"),
+            UnoColor::Pink => write!(f, "Pink"),
+            UnoColor::Teal => write!(f, "Teal"),
+            UnoColor::Orange => write!(f, "Orange"),
+            UnoColor::Purple => write!(f, "Purple"),
             UnoColor::Black => write!(f, "Black"),
         }
     }
@@ -94,17 +182,67 @@ impl std::fmt::Display for UnoColor {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum UnoCardKind {
     Number(u8),
     Action(UnoAction),
     Wild,
     WildDrawFour,
+    /// Uno Flip light-side wild: the next player draws two.
+    WildDrawTwo,
+    /// Uno Flip dark-side wild: the next player draws until they find the declared color.
+    WildDrawColor,
+    /// Gathers every hand, shuffles, and deals the cards back out.
+    WildShuffleHands,
 }
 impl UnoCardKind {
     /// Returns true if the card is a wild card.
     #[must_use]
     pub fn is_wild(&self) -> bool {
-        matches!(self, Self::Wild | Self::WildDrawFour)
+        matches!(
+            self,
+            Self::Wild
+                | Self::WildDrawFour
+                | Self::WildDrawTwo
+                | Self::WildDrawColor
+                | Self::WildShuffleHands
+        )
+    }
+
+    /// Number of cards the next player must draw, or 0 for cards without a fixed penalty.
+    #[must_use]
+    pub fn draw_penalty(&self) -> usize {
+        match self {
+            Self::Action(UnoAction::DrawOne) => 1,
+            Self::Action(UnoAction::DrawTwo) | Self::WildDrawTwo => 2,
+            Self::WildDrawFour => 4,
+            Self::Action(UnoAction::DrawFive) => 5,
+            _ => 0,
+        }
+    }
+
+    /// Points scored for this card when it is left in an opponent's hand.
+    #[must_use]
+    pub fn points(&self) -> usize {
+        match self {
+            Self::Number(face_value) => *face_value as usize,
+            Self::Action(UnoAction::DrawOne) => 10,
+            Self::Action(UnoAction::SkipEveryone) => 30,
+            Self::Action(_) => 20,
+            Self::WildShuffleHands => 40,
+            Self::Wild | Self::WildDrawFour | Self::WildDrawTwo => 50,
+            Self::WildDrawColor => 60,
+        }
+    }
+
+    /// Points scored for this card under Uno Flip, where a plain wild is worth 40 rather
+    /// than 50.
+    #[must_use]
+    pub fn flip_points(&self) -> usize {
+        match self {
+            Self::Wild => 40,
+            other => other.points(),
+        }
     }
 }
 impl std::fmt::Display for UnoCardKind {
@@ -114,16 +252,28 @@ impl std::fmt::Display for UnoCardKind {
             UnoCardKind::Action(action) => write!(f, "{action}"),
             UnoCardKind::Wild => write!(f, "Wild"),
             UnoCardKind::WildDrawFour => write!(f, "Wild + Draw 4"),
+            UnoCardKind::WildDrawTwo => write!(f, "Wild + Draw 2"),
+            UnoCardKind::WildDrawColor => write!(f, "Wild Draw Color"),
+            UnoCardKind::WildShuffleHands => write!(f, "Wild Shuffle Hands"),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum UnoAction {
     DrawTwo,
     Skip,
     Reverse,
+    /// Uno Flip light side: the next player draws one.
+    DrawOne,
+    /// Uno Flip dark side: the next player draws five.
+    DrawFive,
+    /// Every other player is skipped and the same player goes again.
+    SkipEveryone,
+    /// Uno Flip: every card in play turns over to its other side.
+    Flip,
 }
 impl std::fmt::Display for UnoAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -131,6 +281,10 @@ impl std::fmt::Display for UnoAction {
             UnoAction::DrawTwo => write!(f, "Draw Two"),
             UnoAction::Skip => write!(f, "Skip"),
             UnoAction::Reverse => write!(f, "Reverse"),
+            UnoAction::DrawOne => write!(f, "Draw One"),
+            UnoAction::DrawFive => write!(f, "Draw Five"),
+            UnoAction::SkipEveryone => write!(f, "Skip Everyone"),
+            UnoAction::Flip => write!(f, "Flip"),
         }
     }
 }
@@ -143,15 +297,205 @@ pub const MAIN_UNO_COLORS: &[UnoColor] = &[
     UnoColor::Green,
     UnoColor::Yellow,
 ];
+/// The four colors on the dark side of an Uno Flip deck.
+pub const DARK_UNO_COLORS: &[UnoColor] = &[
+    UnoColor::Pink,
+    UnoColor::Teal,
+    UnoColor::Orange,
+    UnoColor::Purple,
+];
 
 /// Create a full set of 108 Uno cards
 #[must_use]
 pub fn full_uno_set() -> Vec<UnoCard> {
-    let mut cards = Vec::new();
-    cards.extend(uno_number_cards());
-    cards.extend(uno_action_cards());
-    cards.extend(uno_wild_cards());
-    cards
+    UnoDeckRecipe::classic().build()
+}
+
+/// Describes the composition of an Uno deck so variants can be built without custom code.
+///
+/// Number and action cards are created once per color in `colors`; wild cards are
+/// created in [`UnoColor::Black`].
+///
+/// ```
+/// use gametools::cards::uno_cards::{UnoAction, UnoCardKind, UnoDeckRecipe};
+///
+/// // a party deck: classic cards plus Draw Five and Wild Shuffle Hands
+/// let mut recipe = UnoDeckRecipe::classic();
+/// recipe.actions.push((UnoAction::DrawFive, 1));
+/// recipe.wilds.push((UnoCardKind::WildShuffleHands, 2));
+/// assert_eq!(recipe.build().len(), 108 + 4 + 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnoDeckRecipe {
+    /// Colors that receive number and action cards.
+    pub colors: Vec<UnoColor>,
+    /// Copies of each number card per color, indexed by number.
+    pub number_counts: Vec<u8>,
+    /// Copies of each action card per color.
+    pub actions: Vec<(UnoAction, u8)>,
+    /// Copies of each wild card kind in the whole deck.
+    pub wilds: Vec<(UnoCardKind, u8)>,
+}
+
+impl UnoDeckRecipe {
+    /// The classic 108-card deck.
+    #[must_use]
+    pub fn classic() -> Self {
+        Self {
+            colors: MAIN_UNO_COLORS.to_vec(),
+            number_counts: UNO_NUMBER_CARD_COUNTS.to_vec(),
+            actions: vec![
+                (UnoAction::DrawTwo, 2),
+                (UnoAction::Skip, 2),
+                (UnoAction::Reverse, 2),
+            ],
+            wilds: vec![(UnoCardKind::Wild, 4), (UnoCardKind::WildDrawFour, 4)],
+        }
+    }
+
+    /// The light side of an Uno Flip deck (112 cards).
+    ///
+    /// This is one side of the double-sided deck built by [`uno_flip_set`]; `UnoGame`
+    /// has no flip handling and rejects recipes that contain Flip cards.
+    #[must_use]
+    pub fn flip_light() -> Self {
+        Self {
+            colors: MAIN_UNO_COLORS.to_vec(),
+            number_counts: vec![0, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+            actions: vec![
+                (UnoAction::DrawOne, 2),
+                (UnoAction::Reverse, 2),
+                (UnoAction::Skip, 2),
+                (UnoAction::Flip, 2),
+            ],
+            wilds: vec![(UnoCardKind::Wild, 4), (UnoCardKind::WildDrawTwo, 4)],
+        }
+    }
+
+    /// The dark side of an Uno Flip deck (112 cards).
+    ///
+    /// Like [`flip_light`](Self::flip_light), this only describes faces for
+    /// [`uno_flip_set`] and can't be dealt by `UnoGame`.
+    #[must_use]
+    pub fn flip_dark() -> Self {
+        Self {
+            colors: DARK_UNO_COLORS.to_vec(),
+            number_counts: vec![0, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+            actions: vec![
+                (UnoAction::DrawFive, 2),
+                (UnoAction::Reverse, 2),
+                (UnoAction::SkipEveryone, 2),
+                (UnoAction::Flip, 2),
+            ],
+            wilds: vec![(UnoCardKind::Wild, 4), (UnoCardKind::WildDrawColor, 4)],
+        }
+    }
+
+    /// Create every card face described by the recipe.
+    #[must_use]
+    pub fn build(&self) -> Vec<UnoCard> {
+        let mut cards = Vec::new();
+        for color in &self.colors {
+            for (number, count) in self.number_counts.iter().enumerate() {
+                #[allow(clippy::cast_possible_truncation)]
+                let kind = UnoCardKind::Number(number as u8);
+                cards.extend((0..*count).map(|_| UnoCard {
+                    color: *color,
+                    kind,
+                }));
+            }
+        }
+        for color in &self.colors {
+            for (action, count) in &self.actions {
+                cards.extend((0..*count).map(|_| UnoCard {
+                    color: *color,
+                    kind: UnoCardKind::Action(*action),
+                }));
+            }
+        }
+        for (kind, count) in &self.wilds {
+            cards.extend((0..*count).map(|_| UnoCard {
+                color: UnoColor::Black,
+                kind: *kind,
+            }));
+        }
+        cards
+    }
+}
+
+impl Default for UnoDeckRecipe {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+/// A double-sided Uno Flip card.
+///
+/// The light side is the card's front and the dark side its back, so [`Card::flip`]
+/// (or `show_faces` / `show_backs` on a collection) switches which side is in play.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnoFlipCard {
+    pub light: UnoCard,
+    pub dark: UnoCard,
+}
+
+impl CardFaces for UnoFlipCard {
+    fn display_front(&self) -> String {
+        self.light.display_front()
+    }
+
+    fn display_back(&self) -> Option<String> {
+        Some(self.dark.display_front())
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.light.matches(&other.light) && self.dark.matches(&other.dark)
+    }
+
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        self.light.compare(&other.light)
+    }
+}
+
+impl UnoFlipCard {
+    /// The side in play: the light side when `light_up`, otherwise the dark side.
+    #[must_use]
+    pub fn side(&self, light_up: bool) -> &UnoCard {
+        if light_up { &self.light } else { &self.dark }
+    }
+}
+
+impl Card<UnoFlipCard> {
+    /// The side of this Uno Flip card that is currently showing.
+    ///
+    /// ```
+    /// use gametools::Card;
+    /// use gametools::cards::uno_cards::uno_flip_set;
+    ///
+    /// let mut card = Card::new_card(uno_flip_set()[0]);
+    /// assert_eq!(*card.showing(), card.faces.light);
+    /// card.flip();
+    /// assert_eq!(*card.showing(), card.faces.dark);
+    /// ```
+    #[must_use]
+    pub fn showing(&self) -> &UnoCard {
+        self.faces.side(self.face_up)
+    }
+}
+
+/// Create a 112-card Uno Flip deck, pairing light and dark sides at random.
+#[must_use]
+pub fn uno_flip_set() -> Vec<UnoFlipCard> {
+    let light = UnoDeckRecipe::flip_light().build();
+    let mut dark = UnoDeckRecipe::flip_dark().build();
+    dark.shuffle(&mut rand::rng());
+    light
+        .into_iter()
+        .zip(dark)
+        .map(|(light, dark)| UnoFlipCard { light, dark })
+        .collect()
 }
 
 /// Create all of the number card faces for a standard Uno deck
@@ -232,18 +576,54 @@ impl super::Hand<UnoCard> {
         }
         playable
     }
+    /// Like [`playable_on`](Self::playable_on), but using a custom [`PlayRule`].
+    #[must_use]
+    pub fn playable_on_with(
+        &self,
+        top: &Card<UnoCard>,
+        declared_color: Option<UnoColor>,
+        rule: &impl PlayRule,
+    ) -> Vec<(usize, &Card<UnoCard>)> {
+        self.cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| rule.plays_on(&card.faces, &top.faces, declared_color))
+            .collect()
+    }
     /// Determine the number of points this hand is currently worth.
     #[must_use]
     pub fn points(&self) -> usize {
-        let mut pts = 0usize;
-        for card in self.cards() {
-            match card.faces.kind {
-                UnoCardKind::Number(face_value) => pts += face_value as usize,
-                UnoCardKind::Action(_) => pts += 20,
-                UnoCardKind::Wild | UnoCardKind::WildDrawFour => pts += 50,
-            }
-        }
-        pts
+        self.cards()
+            .iter()
+            .map(|card| card.faces.kind.points())
+            .sum()
+    }
+}
+
+impl super::Hand<UnoFlipCard> {
+    /// Return the cards in this hand whose showing side can be played on the showing
+    /// side of `top` under `rule`.
+    #[must_use]
+    pub fn playable_on_with(
+        &self,
+        top: &Card<UnoFlipCard>,
+        declared_color: Option<UnoColor>,
+        rule: &impl PlayRule,
+    ) -> Vec<(usize, &Card<UnoFlipCard>)> {
+        self.cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| rule.plays_on(card.showing(), top.showing(), declared_color))
+            .collect()
+    }
+    /// Points for the cards in this hand under Uno Flip scoring, on whichever side is
+    /// showing.
+    #[must_use]
+    pub fn points(&self) -> usize {
+        self.cards()
+            .iter()
+            .map(|card| card.showing().kind.flip_points())
+            .sum()
     }
 }
 
@@ -421,6 +801,118 @@ mod tests {
         assert_eq!(wild_draw_four_count, 4);
     }
 
+    #[test]
+    fn new_actions_match_only_themselves_and_new_wilds_play_anywhere() {
+        let pink_five = face(UnoColor::Pink, UnoCardKind::Action(UnoAction::DrawFive));
+        let teal_five = face(UnoColor::Teal, UnoCardKind::Action(UnoAction::DrawFive));
+        let teal_skip = face(UnoColor::Teal, UnoCardKind::Action(UnoAction::SkipEveryone));
+        let shuffle = face(UnoColor::Black, UnoCardKind::WildShuffleHands);
+
+        assert!(pink_five.plays_on(&teal_five, None));
+        assert!(!pink_five.plays_on(&teal_skip, None));
+        assert!(shuffle.plays_on(&teal_skip, None));
+        assert_eq!(shuffle.kind.points(), 40);
+        assert_eq!(pink_five.kind.draw_penalty(), 5);
+    }
+
+    #[test]
+    fn custom_rules_replace_classic_matching() {
+        #[derive(Debug)]
+        struct ColorOnly;
+        impl PlayRule for ColorOnly {
+            fn plays_on(&self, card: &UnoCard, top: &UnoCard, _: Option<UnoColor>) -> bool {
+                card.color == top.color
+            }
+        }
+
+        let red_three = face(UnoColor::Red, UnoCardKind::Number(3));
+        let blue_three = face(UnoColor::Blue, UnoCardKind::Number(3));
+        let blue_one = face(UnoColor::Blue, UnoCardKind::Number(1));
+        let blue_two = face(UnoColor::Blue, UnoCardKind::Number(2));
+
+        assert!(red_three.plays_on_with(&blue_three, None, &ClassicRule));
+        assert!(!red_three.plays_on_with(&blue_three, None, &ColorOnly));
+        assert!(red_three.plays_on_with(&blue_three, None, &DosRule));
+        assert!(!blue_one.plays_on_with(&blue_three, None, &DosRule));
+        assert!(DosRule::pair_plays_on(&blue_one, &blue_two, &red_three));
+
+        let mut hand = Hand::<UnoCard>::new("dos");
+        hand.add_card(Card::new_card(blue_one));
+        hand.add_card(Card::new_card(blue_three));
+        let top = Card::new_card(red_three);
+        let indices: Vec<usize> = hand
+            .playable_on_with(&top, None, &DosRule)
+            .iter()
+            .map(|(idx, _)| *idx)
+            .collect();
+        assert_eq!(indices, vec![1]);
+    }
+
+    #[test]
+    fn deck_recipes_build_expected_sizes() {
+        assert_eq!(UnoDeckRecipe::classic().build().len(), 108);
+        assert_eq!(UnoDeckRecipe::flip_light().build().len(), 112);
+        assert_eq!(UnoDeckRecipe::flip_dark().build().len(), 112);
+
+        let tiny = UnoDeckRecipe {
+            colors: vec![UnoColor::Red],
+            number_counts: vec![1, 1],
+            actions: vec![(UnoAction::SkipEveryone, 1)],
+            wilds: vec![(UnoCardKind::WildShuffleHands, 1)],
+        };
+        assert_eq!(
+            tiny.build(),
+            vec![
+                face(UnoColor::Red, UnoCardKind::Number(0)),
+                face(UnoColor::Red, UnoCardKind::Number(1)),
+                face(UnoColor::Red, UnoCardKind::Action(UnoAction::SkipEveryone)),
+                face(UnoColor::Black, UnoCardKind::WildShuffleHands),
+            ]
+        );
+    }
+
+    #[test]
+    fn flip_cards_show_dark_side_when_flipped() {
+        let set = uno_flip_set();
+        assert_eq!(set.len(), 112);
+        assert!(
+            set.iter()
+                .all(|c| !DARK_UNO_COLORS.contains(&c.light.color))
+        );
+        assert!(set.iter().all(|c| !MAIN_UNO_COLORS.contains(&c.dark.color)));
+
+        let flip_face = UnoFlipCard {
+            light: face(UnoColor::Red, UnoCardKind::Number(2)),
+            dark: face(UnoColor::Teal, UnoCardKind::Action(UnoAction::DrawFive)),
+        };
+        let mut card = Card::new_card(flip_face);
+        assert_eq!(card.to_string(), "Red (#2)");
+        card.flip();
+        assert_eq!(card.to_string(), "Teal (Draw Five)");
+
+        let mut hand = Hand::<UnoFlipCard>::new("flip");
+        hand.add_card(card.clone());
+        assert_eq!(hand.points(), 20);
+        let top = Card::new_card(UnoFlipCard {
+            light: face(UnoColor::Red, UnoCardKind::Number(9)),
+            dark: face(UnoColor::Teal, UnoCardKind::Number(9)),
+        });
+        let mut top = top;
+        top.flip();
+        assert_eq!(hand.playable_on_with(&top, None, &ClassicRule).len(), 1);
+
+        let mut wild = Card::new_card(UnoFlipCard {
+            light: face(UnoColor::Black, UnoCardKind::Wild),
+            dark: face(UnoColor::Black, UnoCardKind::WildDrawColor),
+        });
+        hand.add_card(wild.clone());
+        assert_eq!(hand.points(), 60);
+        wild.flip();
+        hand.add_card(wild);
+        assert_eq!(hand.points(), 120);
+        assert_eq!(UnoCardKind::Wild.points(), 50);
+    }
+
     #[test]
     fn full_uno_set_contains_expected_cards() {
         let full_set = full_uno_set();
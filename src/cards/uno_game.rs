@@ -0,0 +1,1328 @@
+//! # Uno Game Engine
+//!
+//! A turn-by-turn state machine for running complete games of Uno on top of the
+//! building blocks in [`uno_cards`](crate::cards::uno_cards). [`UnoGame`] owns the
+//! draw pile, discard pile, and every player's [`Hand`], and enforces:
+//!
+//! * turn order, including [`Skip`](UnoAction::Skip) and [`Reverse`](UnoAction::Reverse)
+//! * draw-two / wild-draw-four penalties and wild color declarations
+//! * the "Uno!" call, with a penalty when another player catches a missed call
+//! * reshuffling the discard pile back into the draw pile when it runs dry
+//! * round scoring toward a target score
+//!
+//! Optional house rules are switched on through [`UnoRules`], which also carries the
+//! [`UnoDeckRecipe`] used for each deal. Matching can be changed with
+//! [`UnoGame::set_play_rule`]. Double-sided Uno Flip decks are not run by this engine, and
+//! [`UnoGame::new`] rejects any recipe that includes [`Flip`](UnoAction::Flip) cards.
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::cards::uno_game::{DrawOutcome, UnoGame, UnoOutcome, UnoRules};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut game = UnoGame::new(&["Ann", "Ben", "Cat"], UnoRules::default())?;
+//!
+//! // play the first legal card each turn, drawing when nothing fits
+//! for _ in 0..20 {
+//!     let playable = game.legal_plays();
+//!     if let Some(&index) = playable.first() {
+//!         let color = game.suggest_color();
+//!         if let UnoOutcome::RoundWon { player, points } = game.play(index, Some(color))? {
+//!             println!("{} wins the round for {points} points", game.hand(player)?.player);
+//!             break;
+//!         }
+//!     } else if let DrawOutcome::Drew { playable: true, .. } = game.draw()? {
+//!         game.pass()?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::cards::table::{self, Table};
+use crate::cards::uno_cards::{
+    ClassicRule, MAIN_UNO_COLORS, PlayRule, UnoAction, UnoCard, UnoCardKind, UnoColor,
+    UnoDeckRecipe,
+};
+use crate::cards::{AddCard, Card, CardCollection, Hand, Pile, TakeCard};
+use crate::{GameResult, UnoError};
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Optional house rules and tunable constants for a game of Uno.
+///
+/// The `Default` implementation gives the official rules: the classic 108-card deck,
+/// 7-card hands, a 500 point target, a 2 card penalty for a missed "Uno!" call, and no
+/// house rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnoRules {
+    /// Composition of the deck shuffled for each round.
+    pub deck: UnoDeckRecipe,
+    /// Cards dealt to each player at the start of a round.
+    pub hand_size: usize,
+    /// Score that ends the game once a player reaches it.
+    pub target_score: usize,
+    /// Cards drawn by a player caught without calling "Uno!".
+    pub uno_penalty: usize,
+    /// Draw cards may be stacked on a pending penalty instead of drawing it. A draw card
+    /// stacks on any draw card with an equal or smaller penalty, so a draw two stacks on a
+    /// draw two and a wild draw four stacks on either.
+    pub stacking: bool,
+    /// Playing a 7 swaps hands with a chosen player; playing a 0 passes every hand
+    /// along in the direction of play. A player who goes out on a 7 or 0 wins before any
+    /// hands move.
+    pub seven_zero: bool,
+    /// Any player holding an exact duplicate of the top discard may play it out of turn,
+    /// and play continues from them.
+    pub jump_in: bool,
+    /// A player who cannot play keeps drawing until they draw a playable card.
+    pub draw_until_playable: bool,
+}
+
+impl Default for UnoRules {
+    fn default() -> Self {
+        Self {
+            deck: UnoDeckRecipe::classic(),
+            hand_size: 7,
+            target_score: 500,
+            uno_penalty: 2,
+            stacking: false,
+            seven_zero: false,
+            jump_in: false,
+            draw_until_playable: false,
+        }
+    }
+}
+
+/// The result of a successful play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnoOutcome {
+    /// The round continues with the next player.
+    Continue,
+    /// The player at seat `player` emptied their hand and scored `points`.
+    RoundWon { player: usize, points: usize },
+}
+
+/// The result of the current player drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DrawOutcome {
+    /// A pending draw penalty of this many cards was taken and the turn passed.
+    Penalty(usize),
+    /// `count` cards were drawn. When `playable` is true the last card drawn may be
+    /// played immediately (or the player may [`pass`](UnoGame::pass)); otherwise the
+    /// turn has already passed.
+    Drew { count: usize, playable: bool },
+}
+
+/// The full state of a game of Uno, from the deal through the final round.
+///
+/// Players are identified by their seat index, which matches the order of the names
+/// supplied to [`UnoGame::new`].
+///
+/// The play rule set with [`UnoGame::set_play_rule`] is not serialized: a game loaded
+/// from saved state matches cards with [`ClassicRule`] until `set_play_rule` is called
+/// again.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnoGame {
+    rules: UnoRules,
+    hands: Vec<Hand<UnoCard>>,
+    scores: Vec<usize>,
+    draw_pile: Pile<UnoCard>,
+    discard: Pile<UnoCard>,
+    dealer: usize,
+    current: usize,
+    reversed: bool,
+    declared_color: Option<UnoColor>,
+    pending_draw: usize,
+    drawn_card: Option<uuid::Uuid>,
+    uno_called: Vec<bool>,
+    exposed: Option<usize>,
+    round_winner: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_play_rule"))]
+    play_rule: Arc<dyn PlayRule>,
+}
+
+#[cfg(feature = "serde")]
+fn default_play_rule() -> Arc<dyn PlayRule> {
+    Arc::new(ClassicRule)
+}
+
+impl UnoGame {
+    /// Start a new game and deal the first round.
+    ///
+    /// The first player is the one to the dealer's left; the dealer is seat 0 for the
+    /// first round and rotates each round after that.
+    ///
+    /// # Errors
+    /// - [`UnoError::InvalidPlayerCount`] unless there are 2-10 players.
+    /// - [`UnoError::DeckTooSmall`] if the deck recipe can't deal every hand and leave a
+    ///   starting card.
+    /// - [`UnoError::FlipUnsupported`] if the deck recipe includes Flip cards.
+    pub fn new(players: &[&str], rules: UnoRules) -> GameResult<Self> {
+        if !(2..=10).contains(&players.len()) {
+            return Err(UnoError::InvalidPlayerCount(players.len()).into());
+        }
+        if rules
+            .deck
+            .actions
+            .iter()
+            .any(|(action, count)| *action == UnoAction::Flip && *count > 0)
+        {
+            return Err(UnoError::FlipUnsupported.into());
+        }
+        let needed = players.len() * rules.hand_size + 1;
+        let available = rules.deck.build().len();
+        if available < needed {
+            return Err(UnoError::DeckTooSmall { needed, available }.into());
+        }
+        let mut game = Self {
+            rules,
+            hands: players.iter().map(|name| Hand::new(name)).collect(),
+            scores: vec![0; players.len()],
+            draw_pile: Pile::new_pile("draw"),
+            discard: Pile::new_pile("discard"),
+            dealer: 0,
+            current: 0,
+            reversed: false,
+            declared_color: None,
+            pending_draw: 0,
+            drawn_card: None,
+            uno_called: vec![false; players.len()],
+            exposed: None,
+            round_winner: None,
+            play_rule: Arc::new(ClassicRule),
+        };
+        game.deal_round();
+        Ok(game)
+    }
+
+    /// Replace the matching rule used to decide which cards may be played.
+    ///
+    /// The rule is not saved with the game. After deserializing a game, call this again
+    /// to restore anything other than [`ClassicRule`].
+    ///
+    /// ```
+    /// use gametools::GameResult;
+    /// use gametools::cards::uno_cards::DosRule;
+    /// use gametools::cards::uno_game::{UnoGame, UnoRules};
+    /// # fn main() -> GameResult<()> {
+    /// let mut game = UnoGame::new(&["Ann", "Ben"], UnoRules::default())?;
+    /// game.set_play_rule(DosRule);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_play_rule(&mut self, rule: impl PlayRule + 'static) {
+        self.play_rule = Arc::new(rule);
+    }
+
+    /// Deal the next round once the current one has been won.
+    ///
+    /// # Errors
+    /// - [`UnoError::RoundInProgress`] if nobody has won the current round.
+    /// - [`UnoError::GameOver`] if a player has already reached the target score.
+    pub fn next_round(&mut self) -> GameResult<()> {
+        if self.round_winner.is_none() {
+            return Err(UnoError::RoundInProgress.into());
+        }
+        if self.game_winner().is_some() {
+            return Err(UnoError::GameOver.into());
+        }
+        self.dealer = (self.dealer + 1) % self.hands.len();
+        self.deal_round();
+        Ok(())
+    }
+
+    fn deal_round(&mut self) {
+        let names: Vec<String> = self.hands.iter().map(|h| h.player.clone()).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        // a wild can't start the discard pile: it's buried and another card turned up
+        let table = Table::deal(
+            &names,
+            self.rules.deck.build(),
+            self.rules.hand_size,
+            "draw",
+            |card: &UnoCard| card.kind.is_wild(),
+        );
+        self.hands = table.hands;
+        self.draw_pile = table.draw_pile;
+        self.discard = table.discard;
+
+        self.reversed = false;
+        self.declared_color = None;
+        self.pending_draw = 0;
+        self.drawn_card = None;
+        self.uno_called = vec![false; self.hands.len()];
+        self.exposed = None;
+        self.round_winner = None;
+        self.current = (self.dealer + 1) % self.hands.len();
+        self.apply_starter(self.top_card().faces.kind);
+    }
+
+    /// Action cards flipped to start the discard pile affect the first player.
+    fn apply_starter(&mut self, kind: UnoCardKind) {
+        match kind {
+            UnoCardKind::Action(UnoAction::Skip | UnoAction::SkipEveryone) => self.advance(1),
+            UnoCardKind::Action(UnoAction::Reverse) => {
+                self.reversed = true;
+                self.current = if self.hands.len() == 2 {
+                    (self.dealer + 1) % 2
+                } else {
+                    self.dealer
+                };
+            }
+            kind if kind.draw_penalty() > 0 => {
+                self.draw_cards(self.current, kind.draw_penalty());
+                self.advance(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// The active house rules.
+    #[must_use]
+    pub fn rules(&self) -> &UnoRules {
+        &self.rules
+    }
+
+    /// Number of players in the game.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Seat index of the player whose turn it is.
+    #[must_use]
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Returns `true` while play is running counter-clockwise (after an odd number of reverses).
+    #[must_use]
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// The hand held by the player at `seat`.
+    ///
+    /// # Errors
+    /// - [`UnoError::PlayerNotFound`] if `seat` is out of range.
+    pub fn hand(&self, seat: usize) -> GameResult<&Hand<UnoCard>> {
+        self.hands
+            .get(seat)
+            .ok_or_else(|| UnoError::PlayerNotFound(seat).into())
+    }
+
+    /// All hands, in seat order.
+    #[must_use]
+    pub fn hands(&self) -> &[Hand<UnoCard>] {
+        &self.hands
+    }
+
+    /// Running scores for each seat.
+    #[must_use]
+    pub fn scores(&self) -> &[usize] {
+        &self.scores
+    }
+
+    /// The card on top of the discard pile.
+    #[must_use]
+    pub fn top_card(&self) -> &Card<UnoCard> {
+        // `new` checks the deck leaves a starting card after the deal, and reshuffling
+        // always keeps the top discard in place
+        self.discard
+            .check_top_card()
+            .expect("a starting card is always turned up")
+    }
+
+    /// The color declared by the most recent wild, if a wild is on top of the discard pile.
+    #[must_use]
+    pub fn declared_color(&self) -> Option<UnoColor> {
+        self.declared_color
+    }
+
+    /// Number of penalty cards waiting for the current player (only non-zero when stacking).
+    #[must_use]
+    pub fn pending_draw(&self) -> usize {
+        self.pending_draw
+    }
+
+    /// Number of cards left in the draw pile.
+    #[must_use]
+    pub fn draw_pile_size(&self) -> usize {
+        self.draw_pile.size()
+    }
+
+    /// Seat of the player who won the current round, if it has ended.
+    #[must_use]
+    pub fn round_winner(&self) -> Option<usize> {
+        self.round_winner
+    }
+
+    /// Seat of the player with the highest score once anyone reaches the target score.
+    #[must_use]
+    pub fn game_winner(&self) -> Option<usize> {
+        let (seat, best) = self
+            .scores
+            .iter()
+            .enumerate()
+            .max_by_key(|(seat, score)| (**score, std::cmp::Reverse(*seat)))?;
+        (*best >= self.rules.target_score).then_some(seat)
+    }
+
+    /// Indices of the cards in the current player's hand that may legally be played now.
+    #[must_use]
+    pub fn legal_plays(&self) -> Vec<usize> {
+        if self.round_winner.is_some() {
+            return Vec::new();
+        }
+        let hand = &self.hands[self.current];
+        hand.cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| self.drawn_card.is_none_or(|uuid| card.uuid == uuid))
+            .filter(|(_, card)| self.can_answer_penalty(&card.faces))
+            .filter(|(_, card)| self.plays_on(&card.faces))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The color the current player holds the most of, handy when declaring a wild.
+    #[must_use]
+    pub fn suggest_color(&self) -> UnoColor {
+        let cards = self.hands[self.current].cards();
+        let palette = if self.rules.deck.colors.is_empty() {
+            MAIN_UNO_COLORS
+        } else {
+            &self.rules.deck.colors
+        };
+        palette
+            .iter()
+            .copied()
+            .max_by_key(|color| cards.iter().filter(|c| c.faces.color == *color).count())
+            .unwrap_or(UnoColor::Red)
+    }
+
+    /// Play the card at `card_index` from the current player's hand.
+    ///
+    /// `declared` is required when the card is wild and ignored otherwise. Under the
+    /// 7-0 house rule, sevens must be played with [`play_seven`](Self::play_seven).
+    ///
+    /// # Errors
+    /// - [`UnoError::CardNotFound`] if the index is out of range.
+    /// - [`UnoError::IllegalPlay`] if the card does not play on the discard.
+    /// - [`UnoError::ColorNotDeclared`] if a wild is played without a main color.
+    /// - [`UnoError::PenaltyPending`] if a stacked penalty must be answered first.
+    /// - [`UnoError::OnlyDrawnCardPlayable`] after drawing, for any other card.
+    /// - [`UnoError::SwapTargetRequired`] for a seven under the 7-0 rule.
+    /// - [`UnoError::RoundOver`] if the round has already been won.
+    pub fn play(
+        &mut self,
+        card_index: usize,
+        declared: Option<UnoColor>,
+    ) -> GameResult<UnoOutcome> {
+        self.check_play(self.current, card_index, declared, None)?;
+        Ok(self.commit_play(self.current, card_index, declared, None))
+    }
+
+    /// Play a seven under the 7-0 house rule, swapping hands with the player at `swap_with`.
+    ///
+    /// # Errors
+    /// - [`UnoError::HouseRuleDisabled`] if the 7-0 rule is off.
+    /// - [`UnoError::PlayerNotFound`] if `swap_with` is not another player's seat.
+    /// - [`UnoError::IllegalPlay`] if the card is not a seven or does not play on the discard.
+    /// - any other error that [`play`](Self::play) can return.
+    pub fn play_seven(&mut self, card_index: usize, swap_with: usize) -> GameResult<UnoOutcome> {
+        if !self.rules.seven_zero {
+            return Err(UnoError::HouseRuleDisabled("seven-zero".to_string()).into());
+        }
+        if swap_with >= self.hands.len() || swap_with == self.current {
+            return Err(UnoError::PlayerNotFound(swap_with).into());
+        }
+        self.check_play(self.current, card_index, None, Some(swap_with))?;
+        Ok(self.commit_play(self.current, card_index, None, Some(swap_with)))
+    }
+
+    /// Play an exact duplicate of the top discard out of turn (jump-in house rule).
+    ///
+    /// Play continues from the player who jumped in.
+    ///
+    /// # Errors
+    /// - [`UnoError::HouseRuleDisabled`] if jump-in is off.
+    /// - [`UnoError::PlayerNotFound`] / [`UnoError::CardNotFound`] for bad indices.
+    /// - [`UnoError::IllegalPlay`] unless the card is a non-wild duplicate of the top discard.
+    pub fn jump_in(&mut self, seat: usize, card_index: usize) -> GameResult<UnoOutcome> {
+        if !self.rules.jump_in {
+            return Err(UnoError::HouseRuleDisabled("jump-in".to_string()).into());
+        }
+        self.ensure_round_active()?;
+        let card = self
+            .hand(seat)?
+            .cards()
+            .get(card_index)
+            .ok_or(UnoError::CardNotFound(card_index))?;
+        if card.faces.kind.is_wild() || card.faces != self.top_card().faces {
+            return Err(UnoError::IllegalPlay.into());
+        }
+        if self.rules.seven_zero && card.faces.kind == UnoCardKind::Number(7) {
+            return Err(UnoError::SwapTargetRequired.into());
+        }
+        self.drawn_card = None;
+        self.current = seat;
+        Ok(self.commit_play(seat, card_index, None, None))
+    }
+
+    /// Draw for the current player.
+    ///
+    /// With a stacked penalty pending, the player takes the whole penalty and loses their
+    /// turn. Otherwise they draw one card (or, with `draw_until_playable`, keep drawing
+    /// until something fits). If the last card drawn is playable the turn stays with the
+    /// player, who must then [`play`](Self::play) it or [`pass`](Self::pass).
+    ///
+    /// # Errors
+    /// - [`UnoError::AlreadyDrew`] if the player already drew this turn.
+    /// - [`UnoError::RoundOver`] if the round has already been won.
+    pub fn draw(&mut self) -> GameResult<DrawOutcome> {
+        self.ensure_round_active()?;
+        if self.drawn_card.is_some() {
+            return Err(UnoError::AlreadyDrew.into());
+        }
+        self.close_uno_window(self.current);
+
+        if self.pending_draw > 0 {
+            let penalty = std::mem::take(&mut self.pending_draw);
+            self.draw_cards(self.current, penalty);
+            self.advance(1);
+            return Ok(DrawOutcome::Penalty(penalty));
+        }
+
+        let mut count = 0;
+        while let Some(uuid) = self.draw_cards(self.current, 1) {
+            count += 1;
+            let card = self.hands[self.current]
+                .cards()
+                .last()
+                .expect("a card was just drawn");
+            if self.plays_on(&card.faces) {
+                self.drawn_card = Some(uuid);
+                return Ok(DrawOutcome::Drew {
+                    count,
+                    playable: true,
+                });
+            }
+            if !self.rules.draw_until_playable {
+                break;
+            }
+        }
+        self.advance(1);
+        Ok(DrawOutcome::Drew {
+            count,
+            playable: false,
+        })
+    }
+
+    /// Keep a playable card that was just drawn and end the turn.
+    ///
+    /// # Errors
+    /// - [`UnoError::MustDrawFirst`] unless the current player has just drawn a playable card.
+    pub fn pass(&mut self) -> GameResult<()> {
+        self.ensure_round_active()?;
+        if self.drawn_card.take().is_none() {
+            return Err(UnoError::MustDrawFirst.into());
+        }
+        self.advance(1);
+        Ok(())
+    }
+
+    /// Record that the player at `seat` has called "Uno!".
+    ///
+    /// A call is accepted when the player holds two cards (about to play their
+    /// second-to-last) or one card (before anyone catches them).
+    ///
+    /// # Errors
+    /// - [`UnoError::PlayerNotFound`] if `seat` is out of range.
+    pub fn call_uno(&mut self, seat: usize) -> GameResult<bool> {
+        let size = self.hand(seat)?.size();
+        if (1..=2).contains(&size) {
+            self.uno_called[seat] = true;
+            if self.exposed == Some(seat) {
+                self.exposed = None;
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Catch the player at `seat` for failing to call "Uno!".
+    ///
+    /// The challenge succeeds only if `seat` is down to one card without having called,
+    /// and the next player has not yet started their turn. On success the offender draws
+    /// the `uno_penalty` and `true` is returned.
+    ///
+    /// # Errors
+    /// - [`UnoError::PlayerNotFound`] if `seat` is out of range.
+    /// - [`UnoError::RoundOver`] if the round has already been won.
+    pub fn challenge_uno(&mut self, seat: usize) -> GameResult<bool> {
+        self.hand(seat)?;
+        self.ensure_round_active()?;
+        if self.exposed != Some(seat) {
+            return Ok(false);
+        }
+        self.exposed = None;
+        self.draw_cards(seat, self.rules.uno_penalty);
+        Ok(true)
+    }
+
+    fn ensure_round_active(&self) -> GameResult<()> {
+        if self.round_winner.is_some() {
+            return Err(UnoError::RoundOver.into());
+        }
+        Ok(())
+    }
+
+    fn plays_on(&self, card: &UnoCard) -> bool {
+        self.play_rule
+            .plays_on(card, &self.top_card().faces, self.declared_color)
+    }
+
+    fn can_answer_penalty(&self, card: &UnoCard) -> bool {
+        if self.pending_draw == 0 {
+            return true;
+        }
+        let penalty = card.kind.draw_penalty();
+        penalty > 0 && penalty >= self.top_card().faces.kind.draw_penalty()
+    }
+
+    fn check_play(
+        &self,
+        seat: usize,
+        card_index: usize,
+        declared: Option<UnoColor>,
+        swap_with: Option<usize>,
+    ) -> GameResult<()> {
+        self.ensure_round_active()?;
+        let card = self.hands[seat]
+            .cards()
+            .get(card_index)
+            .ok_or(UnoError::CardNotFound(card_index))?;
+        if let Some(uuid) = self.drawn_card
+            && card.uuid != uuid
+        {
+            return Err(UnoError::OnlyDrawnCardPlayable.into());
+        }
+        if !self.can_answer_penalty(&card.faces) {
+            return Err(UnoError::PenaltyPending(self.pending_draw).into());
+        }
+        if !self.plays_on(&card.faces) {
+            return Err(UnoError::IllegalPlay.into());
+        }
+        if card.faces.kind.is_wild() && declared.is_none_or(|c| c == UnoColor::Black) {
+            return Err(UnoError::ColorNotDeclared.into());
+        }
+        let is_seven = card.faces.kind == UnoCardKind::Number(7);
+        if swap_with.is_some() && !is_seven {
+            return Err(UnoError::IllegalPlay.into());
+        }
+        if self.rules.seven_zero && is_seven && swap_with.is_none() {
+            return Err(UnoError::SwapTargetRequired.into());
+        }
+        Ok(())
+    }
+
+    /// Apply an already validated play.
+    fn commit_play(
+        &mut self,
+        seat: usize,
+        card_index: usize,
+        declared: Option<UnoColor>,
+        swap_with: Option<usize>,
+    ) -> UnoOutcome {
+        self.close_uno_window(seat);
+        self.drawn_card = None;
+
+        let uuid = self.hands[seat].cards()[card_index].uuid;
+        let card = self.hands[seat]
+            .take_where(|c| c.uuid == uuid)
+            .expect("validated card is in the hand");
+        let kind = card.faces.kind;
+        self.declared_color = if kind.is_wild() { declared } else { None };
+        self.discard.add_card(card);
+
+        if self.hands[seat].size() == 1 && !self.uno_called[seat] {
+            self.exposed = Some(seat);
+        }
+
+        // going out ends the round before any hands change places
+        let round_won = self.hands[seat].size() == 0;
+        let mut skip = 1;
+        match kind {
+            UnoCardKind::Number(7) if self.rules.seven_zero && !round_won => {
+                if let Some(other) = swap_with {
+                    self.swap_hands(seat, other);
+                }
+            }
+            UnoCardKind::Number(0) if self.rules.seven_zero && !round_won => self.rotate_hands(),
+            UnoCardKind::Action(UnoAction::Skip) => skip = 2,
+            UnoCardKind::Action(UnoAction::SkipEveryone) => skip = 0,
+            UnoCardKind::WildShuffleHands if !round_won => self.shuffle_hands(seat),
+            UnoCardKind::WildDrawColor => {
+                let victim = self.seat_after(seat, 1);
+                if let Some(color) = declared {
+                    self.draw_until_color(victim, color);
+                }
+                skip = 2;
+            }
+            UnoCardKind::Action(UnoAction::Reverse) => {
+                self.reversed = !self.reversed;
+                if self.hands.len() == 2 {
+                    skip = 2;
+                }
+            }
+            kind if kind.draw_penalty() > 0 => self.pending_draw += kind.draw_penalty(),
+            _ => {}
+        }
+
+        // without stacking (or when the round ends) the penalty lands immediately
+        if self.pending_draw > 0 && (!self.rules.stacking || round_won) {
+            let victim = self.seat_after(seat, 1);
+            let penalty = std::mem::take(&mut self.pending_draw);
+            self.draw_cards(victim, penalty);
+            skip = 2;
+        }
+
+        if round_won {
+            self.exposed = None;
+            let points: usize = self.hands.iter().map(|hand| hand.points()).sum();
+            self.scores[seat] += points;
+            self.round_winner = Some(seat);
+            return UnoOutcome::RoundWon {
+                player: seat,
+                points,
+            };
+        }
+
+        self.current = self.seat_after(seat, skip);
+        UnoOutcome::Continue
+    }
+
+    /// Swap the cards (but not the names) held at two seats.
+    fn swap_hands(&mut self, a: usize, b: usize) {
+        self.hands.swap(a, b);
+        let name_a = std::mem::take(&mut self.hands[a].player);
+        self.hands[a].player = std::mem::replace(&mut self.hands[b].player, name_a);
+        self.uno_called.swap(a, b);
+        self.exposed = None;
+    }
+
+    /// Gather every hand, shuffle, and deal the cards back out starting after `seat`.
+    fn shuffle_hands(&mut self, seat: usize) {
+        let mut pool = Pile::new_pile("shuffle");
+        for hand in &mut self.hands {
+            let count = hand.size();
+            pool.add_cards(hand.take_cards(count));
+        }
+        pool.shuffle();
+        let mut receiver = self.seat_after(seat, 1);
+        while let Some(card) = pool.take_card() {
+            self.hands[receiver].add_card(card);
+            receiver = self.seat_after(receiver, 1);
+        }
+        for (seat, called) in self.uno_called.iter_mut().enumerate() {
+            *called = *called && self.hands[seat].size() == 1;
+        }
+        self.exposed = None;
+    }
+
+    /// Draw into `seat` until a card of `color` turns up or the piles run out.
+    fn draw_until_color(&mut self, seat: usize, color: UnoColor) {
+        while self.draw_cards(seat, 1).is_some() {
+            let drawn = self.hands[seat]
+                .cards()
+                .last()
+                .expect("a card was just drawn");
+            if drawn.faces.color == color {
+                break;
+            }
+        }
+    }
+
+    /// Pass every hand to the next player in the direction of play.
+    fn rotate_hands(&mut self) {
+        let names: Vec<String> = self.hands.iter().map(|h| h.player.clone()).collect();
+        if self.reversed {
+            self.hands.rotate_left(1);
+            self.uno_called.rotate_left(1);
+        } else {
+            self.hands.rotate_right(1);
+            self.uno_called.rotate_right(1);
+        }
+        for (hand, name) in self.hands.iter_mut().zip(names) {
+            hand.player = name;
+        }
+        self.exposed = None;
+    }
+
+    /// The opportunity to catch a missed "Uno!" ends once someone else acts.
+    fn close_uno_window(&mut self, actor: usize) {
+        if self.exposed.is_some_and(|seat| seat != actor) {
+            self.exposed = None;
+        }
+    }
+
+    fn seat_after(&self, seat: usize, steps: usize) -> usize {
+        table::seat_after(self.hands.len(), seat, steps, self.reversed)
+    }
+
+    fn advance(&mut self, steps: usize) {
+        self.current = self.seat_after(self.current, steps);
+    }
+
+    /// Draw up to `count` cards into the hand at `seat`; see [`table::draw_into`].
+    fn draw_cards(&mut self, seat: usize, count: usize) -> Option<uuid::Uuid> {
+        let last = table::draw_into(
+            &mut self.hands[seat],
+            &mut self.draw_pile,
+            &mut self.discard,
+            count,
+        );
+        if self.hands[seat].size() > 1 {
+            self.uno_called[seat] = false;
+        }
+        last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::seat_names;
+
+    fn face(color: UnoColor, kind: UnoCardKind) -> UnoCard {
+        UnoCard { color, kind }
+    }
+
+    fn num(color: UnoColor, n: u8) -> UnoCard {
+        face(color, UnoCardKind::Number(n))
+    }
+
+    fn action(color: UnoColor, action: UnoAction) -> UnoCard {
+        face(color, UnoCardKind::Action(action))
+    }
+
+    /// Build a game with fixed hands, a fixed top card, and a draw pile of red 5s.
+    fn rigged(hands: Vec<Vec<UnoCard>>, top: UnoCard, rules: UnoRules) -> UnoGame {
+        let names = seat_names(hands.len());
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut game = UnoGame::new(&names, rules).unwrap();
+        let table = Table::rigged(hands, top, &num(UnoColor::Red, 5), 20, "draw");
+        game.hands = table.hands;
+        game.draw_pile = table.draw_pile;
+        game.discard = table.discard;
+        game.current = 0;
+        game.reversed = false;
+        game.pending_draw = 0;
+        game.declared_color = None;
+        game
+    }
+
+    #[test]
+    fn new_game_deals_hands_and_starts_discard() {
+        let game = UnoGame::new(&["a", "b", "c"], UnoRules::default()).unwrap();
+        assert_eq!(game.player_count(), 3);
+        assert!(!game.top_card().faces.kind.is_wild());
+        let dealt: usize = game.hands().iter().map(CardCollection::size).sum();
+        assert_eq!(dealt + game.draw_pile_size() + game.discard.size(), 108);
+        assert!(UnoGame::new(&["solo"], UnoRules::default()).is_err());
+    }
+
+    #[test]
+    fn undersized_decks_are_rejected() {
+        let rules = UnoRules {
+            deck: UnoDeckRecipe {
+                colors: vec![UnoColor::Red],
+                number_counts: vec![1; 10],
+                actions: vec![],
+                wilds: vec![],
+            },
+            hand_size: 5,
+            ..UnoRules::default()
+        };
+        assert_eq!(
+            UnoGame::new(&["a", "b"], rules.clone()).unwrap_err(),
+            UnoError::DeckTooSmall {
+                needed: 11,
+                available: 10
+            }
+            .into()
+        );
+        let rules = UnoRules {
+            hand_size: 4,
+            ..rules
+        };
+        let game = UnoGame::new(&["a", "b"], rules).unwrap();
+        assert_eq!(game.draw_pile_size(), 1);
+        assert_eq!(game.discard.size(), 1);
+    }
+
+    #[test]
+    fn flip_recipes_are_rejected() {
+        for deck in [UnoDeckRecipe::flip_light(), UnoDeckRecipe::flip_dark()] {
+            let rules = UnoRules {
+                deck,
+                ..UnoRules::default()
+            };
+            assert_eq!(
+                UnoGame::new(&["a", "b"], rules).unwrap_err(),
+                UnoError::FlipUnsupported.into()
+            );
+        }
+    }
+
+    #[test]
+    fn plays_remove_the_exact_card() {
+        let r = UnoColor::Red;
+        let mut game = rigged(
+            vec![vec![num(r, 1), num(r, 1), num(r, 2)], vec![num(r, 3)]],
+            num(r, 9),
+            UnoRules::default(),
+        );
+        let kept = game.hand(0).unwrap().cards()[0].uuid;
+        let played = game.hand(0).unwrap().cards()[1].uuid;
+        game.play(1, None).unwrap();
+        assert_eq!(game.top_card().uuid, played);
+        assert_eq!(game.hand(0).unwrap().cards()[0].uuid, kept);
+    }
+
+    #[test]
+    fn skip_and_reverse_change_turn_order() {
+        let b = UnoColor::Blue;
+        let mut game = rigged(
+            vec![
+                vec![action(b, UnoAction::Skip), num(b, 1)],
+                vec![num(b, 2), num(b, 3)],
+                vec![action(b, UnoAction::Reverse), num(b, 4)],
+            ],
+            num(b, 9),
+            UnoRules::default(),
+        );
+        assert_eq!(game.play(0, None).unwrap(), UnoOutcome::Continue);
+        assert_eq!(game.current_player(), 2);
+        game.play(0, None).unwrap();
+        assert!(game.is_reversed());
+        assert_eq!(game.current_player(), 1);
+    }
+
+    #[test]
+    fn draw_two_penalizes_and_skips_next_player() {
+        let g = UnoColor::Green;
+        let mut game = rigged(
+            vec![
+                vec![action(g, UnoAction::DrawTwo), num(g, 1)],
+                vec![num(g, 2)],
+                vec![num(g, 3)],
+            ],
+            num(g, 7),
+            UnoRules::default(),
+        );
+        game.play(0, None).unwrap();
+        assert_eq!(game.hand(1).unwrap().size(), 3);
+        assert_eq!(game.current_player(), 2);
+    }
+
+    #[test]
+    fn wild_requires_and_applies_declared_color() {
+        let mut game = rigged(
+            vec![
+                vec![
+                    face(UnoColor::Black, UnoCardKind::Wild),
+                    num(UnoColor::Red, 1),
+                ],
+                vec![num(UnoColor::Yellow, 2), num(UnoColor::Blue, 8)],
+            ],
+            num(UnoColor::Red, 4),
+            UnoRules::default(),
+        );
+        assert_eq!(game.play(0, None), Err(UnoError::ColorNotDeclared.into()));
+        game.play(0, Some(UnoColor::Yellow)).unwrap();
+        assert_eq!(game.declared_color(), Some(UnoColor::Yellow));
+        assert_eq!(game.legal_plays(), vec![0]);
+        assert_eq!(game.play(1, None), Err(UnoError::IllegalPlay.into()));
+    }
+
+    #[test]
+    fn missed_uno_call_can_be_challenged_until_next_turn() {
+        let r = UnoColor::Red;
+        let mut game = rigged(
+            vec![vec![num(r, 1), num(r, 2)], vec![num(r, 3), num(r, 4)]],
+            num(r, 9),
+            UnoRules::default(),
+        );
+        game.play(0, None).unwrap();
+        assert!(game.challenge_uno(0).unwrap());
+        assert_eq!(game.hand(0).unwrap().size(), 3);
+        assert!(!game.challenge_uno(0).unwrap(), "only one penalty per miss");
+
+        // a called uno is safe
+        let mut game = rigged(
+            vec![vec![num(r, 1), num(r, 2)], vec![num(r, 3), num(r, 4)]],
+            num(r, 9),
+            UnoRules::default(),
+        );
+        assert!(game.call_uno(0).unwrap());
+        game.play(0, None).unwrap();
+        assert!(!game.challenge_uno(0).unwrap());
+
+        // once the next player acts the window closes
+        let mut game = rigged(
+            vec![vec![num(r, 1), num(r, 2)], vec![num(r, 3), num(r, 4)]],
+            num(r, 9),
+            UnoRules::default(),
+        );
+        game.play(0, None).unwrap();
+        game.play(0, None).unwrap();
+        assert!(!game.challenge_uno(0).unwrap());
+    }
+
+    #[test]
+    fn drawing_offers_playable_card_or_passes_turn() {
+        let b = UnoColor::Blue;
+        let mut game = rigged(
+            vec![vec![num(b, 1)], vec![num(b, 2)]],
+            num(UnoColor::Red, 9),
+            UnoRules::default(),
+        );
+        // draw pile is all red 5s, which play on a red 9
+        assert_eq!(
+            game.draw().unwrap(),
+            DrawOutcome::Drew {
+                count: 1,
+                playable: true
+            }
+        );
+        assert_eq!(
+            game.play(0, None),
+            Err(UnoError::OnlyDrawnCardPlayable.into())
+        );
+        assert_eq!(game.draw(), Err(UnoError::AlreadyDrew.into()));
+        game.pass().unwrap();
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.pass(), Err(UnoError::MustDrawFirst.into()));
+    }
+
+    #[test]
+    fn empty_draw_pile_is_refilled_from_discard() {
+        let y = UnoColor::Yellow;
+        let mut game = rigged(
+            vec![vec![num(y, 1), num(y, 2)], vec![num(y, 3)]],
+            num(y, 9),
+            UnoRules::default(),
+        );
+        game.draw_pile = Pile::new_pile("draw");
+        game.discard.add_card(Card::new_card(num(y, 4)));
+        game.discard.add_card(Card::new_card(num(y, 6)));
+        game.current = 1;
+        game.pending_draw = 0;
+        game.draw_cards(1, 2);
+        assert_eq!(game.hand(1).unwrap().size(), 3);
+        assert_eq!(game.top_card().faces, num(y, 6));
+        assert_eq!(game.discard.size(), 1);
+    }
+
+    #[test]
+    fn round_win_scores_opponent_hands() {
+        let r = UnoColor::Red;
+        let mut game = rigged(
+            vec![
+                vec![num(r, 1)],
+                vec![num(r, 8), action(r, UnoAction::Skip)],
+                vec![face(UnoColor::Black, UnoCardKind::Wild)],
+            ],
+            num(r, 9),
+            UnoRules {
+                target_score: 70,
+                ..UnoRules::default()
+            },
+        );
+        let outcome = game.play(0, None).unwrap();
+        assert_eq!(
+            outcome,
+            UnoOutcome::RoundWon {
+                player: 0,
+                points: 78
+            }
+        );
+        assert_eq!(game.scores()[0], 78);
+        assert_eq!(game.game_winner(), Some(0));
+        assert_eq!(game.play(0, None), Err(UnoError::RoundOver.into()));
+        assert_eq!(game.challenge_uno(1), Err(UnoError::RoundOver.into()));
+        assert_eq!(game.next_round(), Err(UnoError::GameOver.into()));
+    }
+
+    #[test]
+    fn stacking_passes_penalty_along() {
+        let g = UnoColor::Green;
+        let mut game = rigged(
+            vec![
+                vec![action(g, UnoAction::DrawTwo), num(g, 1)],
+                vec![action(UnoColor::Red, UnoAction::DrawTwo), num(g, 2)],
+                vec![num(g, 3), num(g, 4)],
+            ],
+            num(g, 7),
+            UnoRules {
+                stacking: true,
+                ..UnoRules::default()
+            },
+        );
+        game.play(0, None).unwrap();
+        assert_eq!(game.pending_draw(), 2);
+        assert_eq!(game.legal_plays(), vec![0]);
+        game.play(0, None).unwrap();
+        assert_eq!(game.current_player(), 2);
+        assert_eq!(game.play(0, None), Err(UnoError::PenaltyPending(4).into()));
+        assert_eq!(game.draw().unwrap(), DrawOutcome::Penalty(4));
+        assert_eq!(game.hand(2).unwrap().size(), 6);
+        assert_eq!(game.current_player(), 0);
+    }
+
+    #[test]
+    fn seven_swaps_and_zero_rotates_hands() {
+        let b = UnoColor::Blue;
+        let rules = UnoRules {
+            seven_zero: true,
+            ..UnoRules::default()
+        };
+        let mut game = rigged(
+            vec![
+                vec![num(b, 7), num(b, 0), num(b, 1)],
+                vec![num(b, 2)],
+                vec![num(b, 3), num(b, 4), num(b, 5), num(b, 6)],
+            ],
+            num(b, 9),
+            rules,
+        );
+        assert_eq!(game.play(0, None), Err(UnoError::SwapTargetRequired.into()));
+        game.play_seven(0, 2).unwrap();
+        assert_eq!(game.hand(0).unwrap().size(), 4);
+        assert_eq!(game.hand(2).unwrap().size(), 2);
+        assert_eq!(game.hand(2).unwrap().player, "p2");
+
+        game.current = 2;
+        game.play(0, None).unwrap(); // the 0 moved to seat 2 with the swap
+        let sizes: Vec<usize> = game.hands().iter().map(CardCollection::size).collect();
+        assert_eq!(sizes, vec![1, 4, 1]);
+    }
+
+    #[test]
+    fn going_out_on_seven_or_zero_wins_before_hands_move() {
+        let b = UnoColor::Blue;
+        let rules = UnoRules {
+            seven_zero: true,
+            ..UnoRules::default()
+        };
+        let hands = vec![vec![num(b, 7)], vec![num(b, 0)], vec![num(b, 3), num(b, 4)]];
+        let mut game = rigged(hands.clone(), num(b, 9), rules.clone());
+        assert_eq!(
+            game.play_seven(0, 2).unwrap(),
+            UnoOutcome::RoundWon {
+                player: 0,
+                points: 7
+            }
+        );
+        assert_eq!(game.round_winner(), Some(0));
+        assert_eq!(game.hand(0).unwrap().size(), 0);
+        assert_eq!(game.hand(2).unwrap().size(), 2);
+
+        let mut game = rigged(hands, num(b, 9), rules);
+        game.current = 1;
+        assert_eq!(
+            game.play(0, None).unwrap(),
+            UnoOutcome::RoundWon {
+                player: 1,
+                points: 14
+            }
+        );
+        assert_eq!(game.round_winner(), Some(1));
+        assert_eq!(game.hand(1).unwrap().size(), 0);
+        assert_eq!(game.hand(0).unwrap().size(), 1);
+    }
+
+    #[test]
+    fn jump_in_requires_identical_card_and_takes_the_turn() {
+        let r = UnoColor::Red;
+        let mut game = rigged(
+            vec![vec![num(r, 1)], vec![num(r, 2)], vec![num(r, 9), num(r, 3)]],
+            num(r, 9),
+            UnoRules {
+                jump_in: true,
+                ..UnoRules::default()
+            },
+        );
+        assert_eq!(game.jump_in(2, 1), Err(UnoError::IllegalPlay.into()));
+        game.jump_in(2, 0).unwrap();
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.hand(2).unwrap().size(), 1);
+    }
+
+    #[test]
+    fn variant_actions_take_effect() {
+        let p = UnoColor::Pink;
+        let mut game = rigged(
+            vec![
+                vec![
+                    action(p, UnoAction::DrawFive),
+                    action(p, UnoAction::SkipEveryone),
+                    num(p, 1),
+                ],
+                vec![num(p, 2)],
+                vec![num(p, 3)],
+            ],
+            num(p, 9),
+            UnoRules::default(),
+        );
+        game.play(1, None).unwrap();
+        assert_eq!(game.current_player(), 0, "skip everyone keeps the turn");
+        game.play(0, None).unwrap();
+        assert_eq!(game.hand(1).unwrap().size(), 6);
+        assert_eq!(game.current_player(), 2);
+    }
+
+    #[test]
+    fn wild_shuffle_hands_redeals_every_card() {
+        let r = UnoColor::Red;
+        let mut game = rigged(
+            vec![
+                vec![
+                    face(UnoColor::Black, UnoCardKind::WildShuffleHands),
+                    num(r, 1),
+                ],
+                vec![num(r, 2), num(r, 3), num(r, 4)],
+                vec![num(r, 5), num(r, 6)],
+            ],
+            num(r, 9),
+            UnoRules::default(),
+        );
+        game.play(0, Some(UnoColor::Green)).unwrap();
+        let sizes: Vec<usize> = game.hands().iter().map(CardCollection::size).collect();
+        assert_eq!(sizes, vec![2, 2, 2]);
+        assert_eq!(game.declared_color(), Some(UnoColor::Green));
+    }
+
+    #[test]
+    fn wild_draw_color_draws_until_declared_color() {
+        let mut game = rigged(
+            vec![
+                vec![
+                    face(UnoColor::Black, UnoCardKind::WildDrawColor),
+                    num(UnoColor::Teal, 1),
+                ],
+                vec![num(UnoColor::Teal, 2)],
+            ],
+            num(UnoColor::Teal, 9),
+            UnoRules::default(),
+        );
+        game.draw_pile = Pile::new_pile("draw");
+        for color in [UnoColor::Orange, UnoColor::Purple, UnoColor::Pink] {
+            game.draw_pile.add_card(Card::new_card(num(color, 4)));
+        }
+        game.play(0, Some(UnoColor::Purple)).unwrap();
+        assert_eq!(game.hand(1).unwrap().size(), 3);
+        assert_eq!(game.current_player(), 0);
+    }
+
+    #[test]
+    fn custom_recipe_and_play_rule_drive_the_game() {
+        use crate::cards::uno_cards::DosRule;
+
+        let rules = UnoRules {
+            deck: UnoDeckRecipe {
+                colors: vec![UnoColor::Red, UnoColor::Blue],
+                number_counts: vec![2; 10],
+                actions: vec![],
+                wilds: vec![],
+            },
+            hand_size: 3,
+            ..UnoRules::default()
+        };
+        let mut game = UnoGame::new(&["a", "b"], rules).unwrap();
+        let dealt: usize = game.hands().iter().map(CardCollection::size).sum();
+        assert_eq!(dealt + game.draw_pile_size() + 1, 40);
+
+        game.set_play_rule(DosRule);
+        game.hands[0] = Hand::new("a");
+        game.hands[0].add_card(Card::new_card(num(UnoColor::Blue, 3)));
+        game.hands[0].add_card(Card::new_card(num(UnoColor::Red, 4)));
+        game.discard.add_card(Card::new_card(num(UnoColor::Red, 3)));
+        game.current = 0;
+        assert_eq!(game.legal_plays(), vec![0]);
+    }
+
+    #[test]
+    fn dos_rule_lets_the_declared_color_follow_a_wild() {
+        use crate::cards::uno_cards::DosRule;
+
+        let b = UnoColor::Blue;
+        let mut game = rigged(
+            vec![
+                vec![face(UnoColor::Black, UnoCardKind::Wild), num(b, 1)],
+                vec![num(b, 7), num(UnoColor::Red, 8), num(b, 2)],
+            ],
+            num(UnoColor::Red, 9),
+            UnoRules::default(),
+        );
+        game.set_play_rule(DosRule);
+        game.play(0, Some(b)).unwrap();
+        assert_eq!(game.legal_plays(), vec![0, 2]);
+        assert_eq!(game.play(0, None).unwrap(), UnoOutcome::Continue);
+        assert_eq!(game.top_card().faces, num(b, 7));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn play_rule_is_not_saved_and_reloads_as_classic() {
+        use crate::cards::uno_cards::DosRule;
+
+        let mut game = rigged(
+            vec![vec![num(UnoColor::Blue, 3), num(UnoColor::Red, 4)], vec![]],
+            num(UnoColor::Red, 3),
+            UnoRules::default(),
+        );
+        game.set_play_rule(DosRule);
+        assert_eq!(game.legal_plays(), vec![0]);
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut loaded: UnoGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.legal_plays(), vec![0, 1]);
+        loaded.set_play_rule(DosRule);
+        assert_eq!(loaded.legal_plays(), vec![0]);
+    }
+
+    #[test]
+    fn draw_until_playable_keeps_drawing() {
+        let b = UnoColor::Blue;
+        let mut game = rigged(
+            vec![vec![num(b, 1)], vec![num(b, 2)]],
+            num(UnoColor::Red, 9),
+            UnoRules {
+                draw_until_playable: true,
+                ..UnoRules::default()
+            },
+        );
+        game.draw_pile = Pile::new_pile("draw");
+        game.draw_pile
+            .add_card(Card::new_card(num(UnoColor::Red, 3)));
+        game.draw_pile.add_card(Card::new_card(num(b, 4)));
+        game.draw_pile.add_card(Card::new_card(num(b, 6)));
+        assert_eq!(
+            game.draw().unwrap(),
+            DrawOutcome::Drew {
+                count: 3,
+                playable: true
+            }
+        );
+    }
+}
@@ -33,11 +33,19 @@
 use rand::prelude::SliceRandom;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 
 use crate::{DominoError, GameResult};
 
+pub mod chickenfoot;
+pub mod inference;
+pub mod layout;
+pub mod line_planner;
+pub mod longest_line;
+pub mod mexican_train;
+pub mod turn_planner;
+
 /// The maximum number of pips allowed on each side of a domino.
 pub const MAX_PIPS: u8 = 18;
 
@@ -128,6 +136,17 @@ impl Domino {
     pub fn points(&self) -> u8 {
         self.left + self.right
     }
+
+    /// Returns whether both ends of the tile show the same number.
+    /// ```
+    /// # use gametools::Domino;
+    /// assert!(Domino::new(6, 6, 0).is_double());
+    /// assert!(!Domino::new(6, 5, 1).is_double());
+    /// ```
+    #[must_use]
+    pub fn is_double(&self) -> bool {
+        self.left == self.right
+    }
 }
 impl fmt::Display for Domino {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -135,6 +154,28 @@ impl fmt::Display for Domino {
     }
 }
 
+/// The direction an arm of a [`layout`] grows in, seen from the set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    East,
+    West,
+    North,
+    South,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Direction::East => "east",
+            Direction::West => "west",
+            Direction::North => "north",
+            Direction::South => "south",
+        };
+        write!(f, "{name}")
+    }
+}
+
 /// Pile of all of the dominos used for a game.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -148,6 +189,15 @@ impl BonePile {
     /// This is capped at `MAX_PIPS` = 18 per side, the highest typically found in any domino set.
     #[must_use]
     pub fn new(most_pips: u8) -> Self {
+        let mut tiles = Self::full_set(most_pips);
+        let mut rng = rand::rng();
+        tiles.shuffle(&mut rng);
+        Self { tiles }
+    }
+
+    /// Every tile of a double-`most_pips` set (capped at `MAX_PIPS`) in order, with the ids
+    /// [`BonePile::new`] gives them.
+    pub(crate) fn full_set(most_pips: u8) -> Vec<Domino> {
         let mut tiles = Vec::<Domino>::new();
         let max = std::cmp::min(most_pips, MAX_PIPS);
         let mut did = 0;
@@ -157,12 +207,119 @@ impl BonePile {
                 did += 1;
             }
         }
-        let mut rng = rand::rng();
-        tiles.shuffle(&mut rng);
+        tiles
+    }
+
+    /// Create a shuffled pile of `sets` complete double-`most_pips` sets combined, for
+    /// games with many players. Every tile gets a unique id.
+    #[must_use]
+    pub fn new_combined(most_pips: u8, sets: usize) -> Self {
+        let mut tiles = Vec::new();
+        for _ in 0..sets {
+            let offset = tiles.len();
+            tiles.extend(
+                Self::new(most_pips)
+                    .tiles
+                    .into_iter()
+                    .map(|t| Domino::new(t.left, t.right, t.id + offset)),
+            );
+        }
+        tiles.shuffle(&mut rand::rng());
         Self { tiles }
     }
+
+    /// Create a pile holding exactly the given tiles, numbered by position, for custom sets
+    /// and puzzles. The pile is not shuffled: the last tile listed is drawn first.
+    #[must_use]
+    pub fn from_tiles(tiles: &[(u8, u8)]) -> Self {
+        Self {
+            tiles: tiles
+                .iter()
+                .enumerate()
+                .map(|(id, &(left, right))| Domino::new(left, right, id))
+                .collect(),
+        }
+    }
+}
+
+/// Summary statistics for the tiles in a [`BonePile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BonePileStats {
+    pub tiles: usize,
+    pub total_pips: u32,
+    /// Number of tiles showing each number on either end (a double counts once).
+    pub per_suit: BTreeMap<u8, usize>,
 }
+
 impl BonePile {
+    /// Number of tiles left in the pile.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Whether the pile has been drawn out.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The tile the next draw would return, without drawing it.
+    #[must_use]
+    pub fn peek(&self) -> Option<&Domino> {
+        self.tiles.last()
+    }
+
+    /// Put a tile back on the bottom of the pile, so it is drawn last until the pile is
+    /// reshuffled.
+    ///
+    /// # Errors
+    /// - if a tile with the same id is already in the pile
+    pub fn return_tile(&mut self, tile: Domino) -> GameResult<()> {
+        if self.tiles.iter().any(|t| t.id == tile.id) {
+            return Err(DominoError::DuplicateTile(tile.id).into());
+        }
+        self.tiles.insert(0, tile);
+        Ok(())
+    }
+
+    /// Put several tiles back on the bottom of the pile. Either all are returned or,
+    /// if any id is already present (or repeated), none are.
+    ///
+    /// # Errors
+    /// - if any tile's id is already in the pile or appears twice in `tiles`
+    pub fn return_tiles(&mut self, tiles: Vec<Domino>) -> GameResult<()> {
+        let mut ids: HashSet<usize> = self.tiles.iter().map(|t| t.id).collect();
+        if let Some(dup) = tiles.iter().find(|t| !ids.insert(t.id)) {
+            return Err(DominoError::DuplicateTile(dup.id).into());
+        }
+        self.tiles.splice(0..0, tiles);
+        Ok(())
+    }
+
+    /// Shuffle the tiles left in the pile, e.g. after returning some.
+    pub fn shuffle(&mut self) {
+        self.tiles.shuffle(&mut rand::rng());
+    }
+
+    /// Tile count, pip total and per-number counts for the tiles left in the pile.
+    #[must_use]
+    pub fn stats(&self) -> BonePileStats {
+        let mut per_suit = BTreeMap::new();
+        for tile in &self.tiles {
+            *per_suit.entry(tile.left).or_insert(0) += 1;
+            if !tile.is_double() {
+                *per_suit.entry(tile.right).or_insert(0) += 1;
+            }
+        }
+        BonePileStats {
+            tiles: self.tiles.len(),
+            total_pips: self.tiles.iter().map(|t| u32::from(t.points())).sum(),
+            per_suit,
+        }
+    }
+
     /// Draw a single tile from the pile.
     ///
     /// Returns Some(Domino), or None if there are none left to draw.
@@ -181,6 +338,17 @@ impl BonePile {
     }
 }
 
+/// Who played a tile on a [`Train`], and on which turn if the game numbers them.
+///
+/// Trains rebuilt with [`Train::from_tiles`] don't know who played their tiles, so both
+/// fields are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayRecord {
+    pub player: Option<String>,
+    pub turn: Option<usize>,
+}
+
 /// A train of dominos that have been played.
 ///
 /// Player should be an empty string or other chosen token to indicate a public train,
@@ -189,13 +357,51 @@ impl BonePile {
 /// round -- the initial value upon which the train must be built.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(rename_all = "camelCase", from = "SavedTrain")
+)]
 pub struct Train {
     player: String,
     open: bool,
     head: u8,
     tail: u8,
     tiles: Vec<Domino>,
+    /// One record per tile, in play order.
+    history: Vec<PlayRecord>,
+}
+
+/// A [`Train`] as stored, which may predate play records.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedTrain {
+    player: String,
+    open: bool,
+    head: u8,
+    tail: u8,
+    tiles: Vec<Domino>,
+    #[serde(default)]
+    history: Vec<PlayRecord>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SavedTrain> for Train {
+    /// Trains saved without play records get an unknown record for every tile.
+    fn from(saved: SavedTrain) -> Self {
+        let mut history = saved.history;
+        if history.is_empty() {
+            history = vec![PlayRecord::default(); saved.tiles.len()];
+        }
+        Self {
+            player: saved.player,
+            open: saved.open,
+            head: saved.head,
+            tail: saved.tail,
+            tiles: saved.tiles,
+            history,
+        }
+    }
 }
 impl fmt::Display for Train {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -230,20 +436,140 @@ impl Train {
             head: start,
             tail: start,
             tiles: Vec::<Domino>::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Rebuild a train from its tiles in play order, e.g. after loading a saved game or
+    /// receiving one over the network. Tiles may be given in either orientation.
+    ///
+    /// # Errors
+    /// - if a tile doesn't connect to the one before it (or the first to `head`)
+    /// - if the same tile id appears twice
+    pub fn from_tiles(player: &str, open: bool, head: u8, tiles: &[Domino]) -> GameResult<Self> {
+        Self::from_history(
+            player,
+            open,
+            head,
+            tiles.iter().map(|&t| (t, PlayRecord::default())),
+        )
+    }
+
+    /// Rebuild a train from its tiles and who played them, in play order.
+    ///
+    /// # Errors
+    /// - if a tile doesn't connect to the one before it (or the first to `head`)
+    /// - if the same tile id appears twice
+    pub fn from_history(
+        player: &str,
+        open: bool,
+        head: u8,
+        plays: impl IntoIterator<Item = (Domino, PlayRecord)>,
+    ) -> GameResult<Self> {
+        let mut train = Self::new(player, open, head);
+        for (position, (tile, record)) in plays.into_iter().enumerate() {
+            if train.tiles.iter().any(|t| t.id == tile.id) {
+                return Err(DominoError::DuplicateTile(tile.id).into());
+            }
+            let tile = Self::oriented_for_tail(tile, train.tail)
+                .map_err(|_| DominoError::TrainBroken(position))?;
+            train.push(tile, record);
         }
+        Ok(train)
     }
+
+    /// Check that the tiles connect from the head to the tail, no tile appears twice,
+    /// and there is a play record for every tile. Useful after deserializing a train
+    /// from an untrusted source.
+    ///
+    /// # Errors
+    /// - [`DominoError::TrainBroken`] at the first tile that doesn't connect, or at the
+    ///   end if the last tile doesn't match the tail
+    /// - [`DominoError::DuplicateTile`] if a tile id repeats
+    /// - [`DominoError::HistoryMismatch`] if the play records don't cover the tiles
+    pub fn validate(&self) -> GameResult<()> {
+        let mut end = self.head;
+        let mut seen = HashSet::new();
+        for (position, tile) in self.tiles.iter().enumerate() {
+            if !seen.insert(tile.id) {
+                return Err(DominoError::DuplicateTile(tile.id).into());
+            }
+            if tile.left != end {
+                return Err(DominoError::TrainBroken(position).into());
+            }
+            end = tile.right;
+        }
+        if end != self.tail {
+            return Err(DominoError::TrainBroken(self.tiles.len()).into());
+        }
+        if self.history.len() != self.tiles.len() {
+            return Err(DominoError::HistoryMismatch {
+                tiles: self.tiles.len(),
+                records: self.history.len(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
     /// Attempt to play a tile on the train.
     ///
     /// # Errors
     /// - if it isn't a valid play or if the train is closed and doesn't belong to the calling player.
     pub fn play(&mut self, tile: Domino, player: &str) -> GameResult<()> {
+        self.play_recorded(tile, player, None)
+    }
+
+    /// Like [`play`](Self::play), also recording the turn number the tile was played on.
+    ///
+    /// # Errors
+    /// - if it isn't a valid play or if the train is closed and doesn't belong to the calling player.
+    pub fn play_on_turn(&mut self, tile: Domino, player: &str, turn: usize) -> GameResult<()> {
+        self.play_recorded(tile, player, Some(turn))
+    }
+
+    /// Take back the last tile played and its play record, returning the tile (oriented
+    /// as it was on the train).
+    pub fn undo(&mut self) -> Option<Domino> {
+        let tile = self.tiles.pop()?;
+        self.history.truncate(self.tiles.len());
+        self.tail = tile.left;
+        Some(tile)
+    }
+
+    /// The number the train started from.
+    #[must_use]
+    pub fn head(&self) -> u8 {
+        self.head
+    }
+
+    /// Who played each tile, in play order alongside the tile.
+    ///
+    /// Every tile has a record unless the train was loaded from a save whose records
+    /// don't match its tiles; [`validate`](Self::validate) reports that case.
+    pub fn history(&self) -> impl Iterator<Item = (&Domino, &PlayRecord)> {
+        self.tiles.iter().zip(&self.history)
+    }
+
+    fn play_recorded(&mut self, tile: Domino, player: &str, turn: Option<usize>) -> GameResult<()> {
         self.ensure_player_can_play(player)?;
         let new_tile = Self::oriented_for_tail(tile, self.tail)?;
-        self.tail = new_tile.right;
-        self.tiles.push(new_tile);
+        self.push(
+            new_tile,
+            PlayRecord {
+                player: Some(player.to_owned()),
+                turn,
+            },
+        );
         Ok(())
     }
 
+    fn push(&mut self, tile: Domino, record: PlayRecord) {
+        self.tail = tile.right;
+        self.tiles.push(tile);
+        self.history.push(record);
+    }
+
     /// Opens the train, allowing it to be played on.
     pub fn open(&mut self) {
         self.open = true;
@@ -259,6 +585,25 @@ impl Train {
         self.open
     }
 
+    /// The player who owns the train (or the token used for a public train).
+    #[must_use]
+    pub fn player(&self) -> &str {
+        &self.player
+    }
+
+    /// The number the next tile must match.
+    #[must_use]
+    pub fn tail(&self) -> u8 {
+        self.tail
+    }
+
+    /// The tiles played on the train so far, oriented so each one's left matches the
+    /// previous tile's right.
+    #[must_use]
+    pub fn tiles(&self) -> &[Domino] {
+        &self.tiles
+    }
+
     fn ensure_player_can_play(&self, player: &str) -> GameResult<()> {
         if !self.open && self.player != player {
             return Err(DominoError::TrainClosed.into());
@@ -316,63 +661,28 @@ impl DominoHand {
         }
     }
 
+    /// The player holding the hand.
+    #[must_use]
+    pub fn player(&self) -> &str {
+        &self.player
+    }
+
+    /// The tiles currently in the hand.
+    #[must_use]
+    pub fn tiles(&self) -> &[Domino] {
+        &self.tiles
+    }
+
     /// Build the longest possible sequence of dominos from this hand, starting with the
     /// specified number.
     ///
-    /// This is considered an NP-hard problem. The function uses a graph-based, depth first
-    /// search with pruning and backtracking to find the optimal solution. For a typical starting
-    /// hand of 15 tiles, execution takes around 200-300 ms on a modern processor (unoptimized + debug)...
-    /// but it increases exponentially. A few runs of 25 tiles took anywhere from 11 sec to 3 min,
-    /// and I didn't wait long enough for 30 tiles to finish.
+    /// Finding the longest line is NP-hard in general. The solver in [`longest_line`]
+    /// prunes and memoizes aggressively, so typical hands of 30+ tiles still finish in
+    /// well under a second. Use
+    /// [`find_longest_within`](Self::find_longest_within) when a hard time limit matters.
     #[must_use]
     pub fn find_longest_from(&self, head: u8) -> Vec<usize> {
-        // * build a graph - #pips are nodes, and dominos that connect them are edges
-        // * modeled with a HashMap (key = #pips, val = list of domino ids that can connect to it)
-        let mut graph = HashMap::<u8, Vec<(u8, usize)>>::new();
-        for tile in &self.tiles {
-            // each tile added twice since it can be used with left and right flipped at will
-            graph
-                .entry(tile.left)
-                .or_default()
-                .push((tile.right, tile.id));
-            graph
-                .entry(tile.right)
-                .or_default()
-                .push((tile.left, tile.id));
-        }
-
-        // initialize and start depth-first search
-        let mut best_line = Vec::<usize>::new();
-        let mut used = HashSet::<usize>::new(); // keeps track of tiles/edges already used
-        let mut working_line = Vec::<usize>::new();
-        Self::depth_first_search(&graph, head, &mut best_line, &mut used, &mut working_line);
-        best_line
-    }
-    fn depth_first_search(
-        graph: &HashMap<u8, Vec<(u8, usize)>>,
-        head: u8,
-        best: &mut Vec<usize>,
-        used: &mut HashSet<usize>,
-        working: &mut Vec<usize>,
-    ) {
-        let mut extended = false;
-
-        for &(pips, domino_id) in graph.get(&head).unwrap_or(&vec![]) {
-            if !used.contains(&domino_id) {
-                used.insert(domino_id);
-                working.push(domino_id);
-
-                Self::depth_first_search(graph, pips, best, used, working);
-
-                working.pop();
-                used.remove(&domino_id);
-                extended = true;
-            }
-        }
-
-        if !extended && working.len() > best.len() {
-            *best = working.clone();
-        }
+        longest_line::longest_line(&self.tiles, head)
     }
 
     /// Takes a sequence of domino ids and attempts to play them on a train.
@@ -421,8 +731,14 @@ impl DominoHand {
         }
 
         // then extend the train with the planned tiles
-        train.tail = tail;
-        train.tiles.extend(planned_tiles);
+        for tile in planned_tiles {
+            let record = PlayRecord {
+                player: Some(self.player.clone()),
+                turn: None,
+            };
+            train.push(tile, record);
+        }
+        debug_assert_eq!(train.tail, tail);
         Ok(())
     }
 }
@@ -430,6 +746,7 @@ impl DominoHand {
 #[cfg(test)]
 mod domino_tests {
     use crate::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_find_longest_from_returns_expected_ids() {
@@ -564,6 +881,47 @@ mod domino_tests {
         assert_eq!(over_max.tiles.len(), 190); // number of tiles in a double-18 (MAX_PIPS) set
     }
 
+    #[test]
+    fn bonepile_variants_have_unique_ids() {
+        let doubled = BonePile::new_combined(6, 2);
+        assert_eq!(doubled.remaining(), 56);
+        let ids: HashSet<usize> = doubled.tiles.iter().map(|t| t.id).collect();
+        assert_eq!(ids.len(), 56);
+
+        let mut custom = BonePile::from_tiles(&[(1, 2), (3, 3), (0, 5)]);
+        assert_eq!(custom.peek().map(Domino::as_tuple), Some((0, 5, 2)));
+        assert_eq!(custom.draw_tile().map(|t| t.id), Some(2));
+        assert_eq!(custom.remaining(), 2);
+        assert!(BonePile::from_tiles(&[]).is_empty());
+    }
+
+    #[test]
+    fn bonepile_returns_and_stats() {
+        let mut pile = BonePile::from_tiles(&[(1, 2), (3, 3), (0, 5)]);
+        let stats = pile.stats();
+        assert_eq!((stats.tiles, stats.total_pips), (3, 14));
+        assert_eq!(stats.per_suit[&3], 1);
+        assert_eq!(stats.per_suit.values().sum::<usize>(), 5);
+        assert_eq!(BonePile::new(6).stats().per_suit[&6], 7);
+
+        let drawn = pile.draw_tiles(2).unwrap();
+        assert!(
+            pile.return_tile(Domino::new(9, 9, 0))
+                .is_err_and(|e| e == DominoError::DuplicateTile(0).into())
+        );
+        let twice = vec![drawn[0], drawn[0]];
+        assert!(pile.return_tiles(twice).is_err());
+        assert_eq!(pile.remaining(), 1);
+        pile.return_tiles(drawn).unwrap();
+        assert_eq!(pile.remaining(), 3);
+        assert_eq!(pile.peek().map(Domino::id), Some(0)); // returned tiles go underneath
+        pile.shuffle();
+        assert_eq!(
+            pile.stats(),
+            BonePile::from_tiles(&[(1, 2), (3, 3), (0, 5)]).stats()
+        );
+    }
+
     #[test]
     fn train_display_is_correct() {
         let private = Train::new("moon", false, 12);
@@ -676,4 +1034,90 @@ mod domino_tests {
         assert_eq!(train.tiles.len(), 3);
         assert_eq!(train.tail, 4); // last tile should be [3:4]
     }
+
+    #[test]
+    fn train_history_and_undo() {
+        let mut train = Train::new("bonzo", true, 12);
+        train.play(Domino::new(12, 3, 0), "bonzo").unwrap();
+        train
+            .play_on_turn(Domino::new(5, 3, 1), "percy", 4)
+            .unwrap();
+        let who: Vec<_> = train
+            .history()
+            .map(|(tile, rec)| (tile.id, rec.player.as_deref(), rec.turn))
+            .collect();
+        assert_eq!(who, [(0, Some("bonzo"), None), (1, Some("percy"), Some(4))]);
+
+        let undone = train.undo().unwrap();
+        assert_eq!(undone.as_tuple(), (3, 5, 1)); // returned as it lay on the train
+        assert_eq!((train.tail(), train.history().count()), (3, 1));
+        assert_eq!(train.undo().map(|t| t.id), Some(0));
+        assert_eq!(train.undo(), None);
+        assert_eq!(train.tail(), train.head());
+        train.validate().unwrap();
+    }
+
+    #[test]
+    fn train_rebuild_and_validate() {
+        let tiles = [Domino::new(6, 2, 0), Domino::new(4, 2, 1)];
+        let train = Train::from_tiles("moon", false, 6, &tiles).unwrap();
+        assert_eq!(train.to_string(), "[X]-moon-(6)[6:2][2:4]");
+        assert!(
+            train
+                .history()
+                .all(|(_, rec)| *rec == PlayRecord::default())
+        );
+        train.validate().unwrap();
+
+        let broken = [tiles[0], Domino::new(5, 5, 2)];
+        assert_eq!(
+            Train::from_tiles("moon", false, 6, &broken).unwrap_err(),
+            DominoError::TrainBroken(1).into()
+        );
+        let repeated = [tiles[0], Domino::new(2, 6, 0)];
+        assert_eq!(
+            Train::from_tiles("moon", false, 6, &repeated).unwrap_err(),
+            DominoError::DuplicateTile(0).into()
+        );
+
+        // corrupt trains, as might arrive from a bad save file
+        let mut tampered = train.clone();
+        tampered.tiles[1] = Domino::new(3, 4, 1);
+        assert_eq!(tampered.validate(), Err(DominoError::TrainBroken(1).into()));
+        let mut tampered = train.clone();
+        tampered.tail = 9;
+        assert_eq!(tampered.validate(), Err(DominoError::TrainBroken(2).into()));
+        let mut tampered = train;
+        tampered.history.truncate(1);
+        assert_eq!(
+            tampered.validate(),
+            Err(DominoError::HistoryMismatch {
+                tiles: 2,
+                records: 1
+            }
+            .into())
+        );
+        // undo only drops the record belonging to the tile taken back
+        tampered.undo();
+        assert_eq!(tampered.history.len(), 1);
+        tampered.validate().unwrap();
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn trains_saved_without_history_load_with_unknown_records() {
+        let json = r#"{"player": "moon", "open": false, "head": 6, "tail": 4,
+            "tiles": [{"left": 6, "right": 2, "id": 0}, {"left": 2, "right": 4, "id": 1}]}"#;
+        let mut train: Train = serde_json::from_str(json).unwrap();
+        train.validate().unwrap();
+        assert_eq!(train.history().count(), 2);
+        assert!(
+            train
+                .history()
+                .all(|(_, rec)| *rec == PlayRecord::default())
+        );
+        assert_eq!(train.undo().map(|t| t.id), Some(1));
+        assert_eq!(train.history().count(), 1);
+        train.validate().unwrap();
+    }
 }
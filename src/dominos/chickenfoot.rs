@@ -0,0 +1,374 @@
+//! # Chickenfoot and Branching Trains
+//!
+//! In Chickenfoot play starts from a center double, and every double must be covered
+//! several ways (usually three, forming the "chicken foot") before anything else may be
+//! played. The result is a tree of tiles rather than a line. [`BranchingTrain`] holds
+//! that tree, tracks the open end of every branch, and refuses other plays while a double
+//! is still waiting for covers. Hands left at the end of a round are scored with the 0-0
+//! tile worth [`ChickenfootRules::double_zero_worth`].
+//!
+//! ```
+//! use gametools::{Domino, DominoHand, GameResult};
+//! use gametools::dominos::chickenfoot::{BranchingTrain, ChickenfootRules};
+//! # fn main() -> GameResult<()> {
+//!
+//! // a small table: the center double takes 2 covers, later doubles 3
+//! let rules = ChickenfootRules { center_ways: 2, ..ChickenfootRules::default() };
+//! let mut train = BranchingTrain::new(Domino::new(6, 6, 0), rules)?;
+//! assert_eq!(train.awaiting_cover(), Some((0, 2)));
+//!
+//! train.play(Domino::new(6, 3, 1), 0)?;
+//! train.play(Domino::new(2, 6, 2), 0)?;
+//! assert_eq!(train.awaiting_cover(), None);
+//!
+//! // a double on a branch must now be covered 3 ways before any other play
+//! train.play(Domino::new(3, 3, 3), 1)?;
+//! assert!(train.play(Domino::new(2, 5, 4), 2).is_err());
+//! assert_eq!(train.open_ends(), [(3, 3)]);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::VecDeque;
+
+use crate::dominos::{Domino, DominoHand};
+use crate::{ChickenfootError, DominoError, GameResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How many ways doubles must be covered, and how the 0-0 tile scores.
+///
+/// The `Default` implementation gives the common Chickenfoot rules: six tiles around the
+/// center double, three on every later double, and the 0-0 tile worth 50 points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChickenfootRules {
+    /// Tiles that must be played against the center double.
+    pub center_ways: usize,
+    /// Tiles that must be played against every other double.
+    pub cover_ways: usize,
+    /// Points charged for the 0-0 tile when it is left in a hand.
+    pub double_zero_worth: u8,
+}
+
+impl Default for ChickenfootRules {
+    fn default() -> Self {
+        Self {
+            center_ways: 6,
+            cover_ways: 3,
+            double_zero_worth: 50,
+        }
+    }
+}
+
+/// A tile in a [`BranchingTrain`], oriented so its `left` touches its parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BranchTile {
+    pub tile: Domino,
+    /// Position of the tile this one was played against, or `None` for the center double.
+    pub parent: Option<usize>,
+}
+
+/// A tree of dominos grown from a center double.
+///
+/// Tiles are addressed by position in play order; the center double is position 0.
+/// A regular tile has one open end until something is played on it. A double has
+/// `cover_ways` (or `center_ways`) open ends and, until all are filled, no other tile
+/// may be played anywhere.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BranchingTrain {
+    rules: ChickenfootRules,
+    tiles: Vec<BranchTile>,
+    /// Tiles played against each position.
+    children: Vec<usize>,
+    /// Doubles still waiting for covers, oldest first.
+    uncovered: VecDeque<usize>,
+}
+
+impl BranchingTrain {
+    /// Start a train from the center double.
+    ///
+    /// # Errors
+    /// - if `center` is not a double
+    /// - if either cover count in `rules` is zero
+    pub fn new(center: Domino, rules: ChickenfootRules) -> GameResult<Self> {
+        if !center.is_double() {
+            return Err(ChickenfootError::RootNotDouble.into());
+        }
+        if rules.center_ways == 0 || rules.cover_ways == 0 {
+            return Err(ChickenfootError::NoCoverWays.into());
+        }
+        Ok(Self {
+            rules,
+            tiles: vec![BranchTile {
+                tile: center,
+                parent: None,
+            }],
+            children: vec![0],
+            uncovered: VecDeque::from([0]),
+        })
+    }
+
+    /// The rules this train enforces.
+    #[must_use]
+    pub fn rules(&self) -> &ChickenfootRules {
+        &self.rules
+    }
+
+    /// Every tile played, in play order, starting with the center double.
+    #[must_use]
+    pub fn tiles(&self) -> &[BranchTile] {
+        &self.tiles
+    }
+
+    /// The tiles from the center double out to the tile at `position`.
+    ///
+    /// # Errors
+    /// - if there is no tile at `position`
+    pub fn branch(&self, position: usize) -> GameResult<Vec<Domino>> {
+        let mut at = Some(self.node(position)?);
+        let mut path = Vec::new();
+        while let Some(pos) = at {
+            path.push(self.tiles[pos].tile);
+            at = self.tiles[pos].parent;
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    /// The double that must be covered next and how many covers it still needs.
+    #[must_use]
+    pub fn awaiting_cover(&self) -> Option<(usize, usize)> {
+        self.uncovered
+            .front()
+            .map(|&pos| (pos, self.capacity(pos) - self.children[pos]))
+    }
+
+    /// The ends a tile may be played on now, as (position, number to match). While a
+    /// double awaits covers it is the only end listed.
+    #[must_use]
+    pub fn open_ends(&self) -> Vec<(usize, u8)> {
+        if let Some((pos, _)) = self.awaiting_cover() {
+            return vec![(pos, self.tiles[pos].tile.right)];
+        }
+        (0..self.tiles.len())
+            .filter(|&pos| self.children[pos] < self.capacity(pos))
+            .map(|pos| (pos, self.tiles[pos].tile.right))
+            .collect()
+    }
+
+    /// Play `tile` against the tile at `position`, flipping it if needed.
+    ///
+    /// # Errors
+    /// - if there is no tile at `position`, or it has no open end left
+    /// - if another double must be covered first
+    /// - if the tile doesn't match
+    pub fn play(&mut self, tile: Domino, position: usize) -> GameResult<()> {
+        self.check_end(position)?;
+        let number = self.tiles[position].tile.right;
+        let tile = match tile {
+            _ if tile.left == number => tile,
+            _ if tile.right == number => tile.flipped(),
+            _ => return Err(DominoError::TileUnconnected.into()),
+        };
+        self.tiles.push(BranchTile {
+            tile,
+            parent: Some(position),
+        });
+        self.children.push(0);
+        self.children[position] += 1;
+        if self.uncovered.front() == Some(&position)
+            && self.children[position] == self.capacity(position)
+        {
+            self.uncovered.pop_front();
+        }
+        if tile.is_double() {
+            self.uncovered.push_back(self.tiles.len() - 1);
+        }
+        Ok(())
+    }
+
+    /// Play the tile with id `tile_id` from `hand` against the tile at `position`. The
+    /// hand is only changed if the play succeeds.
+    ///
+    /// # Errors
+    /// - if the tile is not in the hand
+    /// - for the reasons [`play`](Self::play) fails
+    pub fn play_from_hand(
+        &mut self,
+        hand: &mut DominoHand,
+        tile_id: usize,
+        position: usize,
+    ) -> GameResult<()> {
+        let idx = hand
+            .tiles
+            .iter()
+            .position(|t| t.id == tile_id)
+            .ok_or(DominoError::TileNotFound(tile_id))?;
+        self.play(hand.tiles[idx], position)?;
+        hand.tiles.remove(idx);
+        Ok(())
+    }
+
+    /// Every (tile id, position) play open to `hand` right now.
+    #[must_use]
+    pub fn legal_moves(&self, hand: &DominoHand) -> Vec<(usize, usize)> {
+        let ends = self.open_ends();
+        hand.tiles
+            .iter()
+            .flat_map(|t| {
+                ends.iter()
+                    .filter(|(_, n)| t.left == *n || t.right == *n)
+                    .map(|&(pos, _)| (t.id, pos))
+            })
+            .collect()
+    }
+
+    /// Points left in `hand`, with the 0-0 tile worth `double_zero_worth`.
+    #[must_use]
+    pub fn hand_points(&self, hand: &DominoHand) -> u32 {
+        hand.tiles
+            .iter()
+            .map(|t| u32::from(t.points_with_zero_worth(self.rules.double_zero_worth)))
+            .sum()
+    }
+
+    fn node(&self, position: usize) -> GameResult<usize> {
+        if position < self.tiles.len() {
+            Ok(position)
+        } else {
+            Err(ChickenfootError::NoSuchTile(position).into())
+        }
+    }
+
+    fn check_end(&self, position: usize) -> GameResult<()> {
+        self.node(position)?;
+        if let Some((pending, remaining)) = self.awaiting_cover()
+            && pending != position
+        {
+            return Err(ChickenfootError::MustCoverDouble {
+                number: self.tiles[pending].tile.right,
+                remaining,
+            }
+            .into());
+        }
+        if self.children[position] >= self.capacity(position) {
+            return Err(ChickenfootError::EndClosed(position).into());
+        }
+        Ok(())
+    }
+
+    fn capacity(&self, position: usize) -> usize {
+        match position {
+            0 => self.rules.center_ways,
+            _ if self.tiles[position].tile.is_double() => self.rules.cover_ways,
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(center_ways: usize) -> ChickenfootRules {
+        ChickenfootRules {
+            center_ways,
+            ..ChickenfootRules::default()
+        }
+    }
+
+    #[test]
+    fn center_double_must_be_covered_first() {
+        let mut train = BranchingTrain::new(Domino::new(9, 9, 0), rules(3)).unwrap();
+        train.play(Domino::new(9, 1, 1), 0).unwrap();
+        assert_eq!(
+            train.play(Domino::new(1, 4, 2), 1).unwrap_err(),
+            ChickenfootError::MustCoverDouble {
+                number: 9,
+                remaining: 2
+            }
+            .into()
+        );
+        train.play(Domino::new(2, 9, 2), 0).unwrap();
+        train.play(Domino::new(9, 3, 3), 0).unwrap();
+        assert_eq!(train.open_ends(), [(1, 1), (2, 2), (3, 3)]);
+        assert_eq!(
+            train.play(Domino::new(9, 5, 4), 0).unwrap_err(),
+            ChickenfootError::EndClosed(0).into()
+        );
+    }
+
+    #[test]
+    fn doubles_on_branches_form_a_foot() {
+        let mut train = BranchingTrain::new(Domino::new(9, 9, 0), rules(1)).unwrap();
+        train.play(Domino::new(9, 4, 1), 0).unwrap();
+        train.play(Domino::new(4, 4, 2), 1).unwrap();
+        assert_eq!(train.awaiting_cover(), Some((2, 3)));
+        assert_eq!(train.open_ends(), [(2, 4)]);
+        for (id, toe) in [(3, 6), (4, 7), (5, 8)] {
+            train.play(Domino::new(4, toe, id), 2).unwrap();
+        }
+        assert_eq!(train.awaiting_cover(), None);
+        assert_eq!(train.open_ends(), [(3, 6), (4, 7), (5, 8)]);
+        let path: Vec<_> = train.branch(5).unwrap().iter().map(Domino::id).collect();
+        assert_eq!(path, [0, 1, 2, 5]);
+        assert_eq!(
+            train.branch(9).unwrap_err(),
+            ChickenfootError::NoSuchTile(9).into()
+        );
+    }
+
+    #[test]
+    fn configurable_cover_count() {
+        let rules = ChickenfootRules {
+            center_ways: 1,
+            cover_ways: 2,
+            double_zero_worth: 0,
+        };
+        let mut train = BranchingTrain::new(Domino::new(5, 5, 0), rules).unwrap();
+        train.play(Domino::new(5, 5, 1), 0).unwrap();
+        train.play(Domino::new(5, 2, 2), 1).unwrap();
+        assert_eq!(train.awaiting_cover(), Some((1, 1)));
+        train.play(Domino::new(5, 3, 3), 1).unwrap();
+        assert_eq!(train.open_ends(), [(2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn hands_play_and_score() {
+        let mut train = BranchingTrain::new(Domino::new(6, 6, 0), rules(2)).unwrap();
+        let mut hand = DominoHand::new("ann");
+        hand.tiles = vec![
+            Domino::new(0, 0, 1),
+            Domino::new(6, 2, 2),
+            Domino::new(1, 6, 3),
+        ];
+        assert_eq!(train.legal_moves(&hand), [(2, 0), (3, 0)]);
+        assert_eq!(train.hand_points(&hand), 50 + 8 + 7);
+        assert!(train.play_from_hand(&mut hand, 1, 0).is_err());
+        assert_eq!(hand.tiles.len(), 3);
+        train.play_from_hand(&mut hand, 2, 0).unwrap();
+        train.play_from_hand(&mut hand, 3, 0).unwrap();
+        assert_eq!(train.hand_points(&hand), 50);
+        assert_eq!(train.tiles()[2].tile.as_tuple(), (6, 1, 3));
+    }
+
+    #[test]
+    fn invalid_setups_are_rejected() {
+        assert_eq!(
+            BranchingTrain::new(Domino::new(6, 5, 0), ChickenfootRules::default()).unwrap_err(),
+            ChickenfootError::RootNotDouble.into()
+        );
+        assert_eq!(
+            BranchingTrain::new(Domino::new(6, 6, 0), rules(0)).unwrap_err(),
+            ChickenfootError::NoCoverWays.into()
+        );
+        let mut train = BranchingTrain::new(Domino::new(6, 6, 0), rules(1)).unwrap();
+        assert_eq!(
+            train.play(Domino::new(1, 2, 1), 0).unwrap_err(),
+            DominoError::TileUnconnected.into()
+        );
+    }
+}
@@ -0,0 +1,519 @@
+//! # Opponent Tile Inference
+//!
+//! A domino player never sees the other hands, but the public record narrows them down:
+//! a player who passes on a number holds no tile showing it, at least until they draw
+//! again. [`TileInference`] follows the public record (tiles played, passes, draws and
+//! the boneyard size) from one player's seat and keeps, for each opponent, the set of
+//! tiles they could still hold. [`TileInference::sample_world`] deals the unseen tiles
+//! into hands and a boneyard consistent with everything observed, for determinized
+//! Monte Carlo players that simulate many such worlds and pick the move that does best.
+//!
+//! ```
+//! use gametools::{BonePile, Domino, DominoHand, GameResult};
+//! use gametools::dominos::inference::TileInference;
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut pile = BonePile::new(6);
+//! let me = DominoHand::new_with_draw("ann", 7, &mut pile)?;
+//! let bobs_hand = DominoHand::new_with_draw("bob", 7, &mut pile)?;
+//! let mut tracker = TileInference::for_set(6, &me, &[("bob", 7)], pile.remaining());
+//!
+//! // bob can't play on a 6 or a 0
+//! tracker.passed("bob", &[6, 0])?;
+//! let shows = |n: u8| move |t: &Domino| [t.left(), t.right()].contains(&n);
+//! assert!(!tracker.possible_tiles("bob")?.iter().any(shows(6)));
+//!
+//! let world = tracker.sample_world(&mut rand::rng())?;
+//! assert_eq!(world.hands[0].tiles().len(), bobs_hand.tiles().len());
+//! assert!(!world.hands[0].tiles().iter().any(shows(0)));
+//! assert_eq!(world.boneyard.remaining(), 14);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::{BTreeSet, HashSet};
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::dominos::{BonePile, Domino, DominoHand};
+use crate::{GameResult, InferenceError};
+
+/// How many randomized deals [`TileInference::sample_world`] tries before giving up.
+pub const SAMPLE_ATTEMPTS: usize = 200;
+
+/// Tiles an opponent took into hand together, and the numbers none of them can show.
+///
+/// A pass rules a number out for everything currently held; tiles drawn afterwards start
+/// a new group with nothing ruled out. Groups are kept in the order they were drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Group {
+    count: usize,
+    excluded: BTreeSet<u8>,
+}
+
+impl Group {
+    fn allows(&self, tile: &Domino) -> bool {
+        !self.excluded.contains(&tile.left) && !self.excluded.contains(&tile.right)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Opponent {
+    name: String,
+    groups: Vec<Group>,
+}
+
+/// One consistent guess at the hidden state of the game.
+#[derive(Debug, Clone)]
+pub struct SampledWorld {
+    /// Opponent hands, in the order the opponents were given to the tracker.
+    pub hands: Vec<DominoHand>,
+    pub boneyard: BonePile,
+}
+
+/// Tracks what each opponent could be holding, from one player's point of view.
+#[derive(Debug, Clone)]
+pub struct TileInference {
+    universe: Vec<Domino>,
+    player: String,
+    mine: HashSet<usize>,
+    played: HashSet<usize>,
+    opponents: Vec<Opponent>,
+    boneyard_size: usize,
+}
+
+impl TileInference {
+    /// Start tracking a game played with the tiles in `universe`, seen from `own_hand`'s
+    /// seat. `opponents` lists each opponent's name and starting hand size.
+    #[must_use]
+    pub fn new(
+        universe: Vec<Domino>,
+        own_hand: &DominoHand,
+        opponents: &[(&str, usize)],
+        boneyard_size: usize,
+    ) -> Self {
+        Self {
+            universe,
+            player: own_hand.player.clone(),
+            mine: own_hand.tiles.iter().map(|t| t.id).collect(),
+            played: HashSet::new(),
+            opponents: opponents
+                .iter()
+                .map(|&(name, count)| Opponent {
+                    name: name.to_string(),
+                    groups: vec![Group {
+                        count,
+                        excluded: BTreeSet::new(),
+                    }],
+                })
+                .collect(),
+            boneyard_size,
+        }
+    }
+
+    /// Start tracking a game played with a double-`most_pips` set from [`BonePile::new`].
+    #[must_use]
+    pub fn for_set(
+        most_pips: u8,
+        own_hand: &DominoHand,
+        opponents: &[(&str, usize)],
+        boneyard_size: usize,
+    ) -> Self {
+        Self::new(
+            BonePile::full_set(most_pips),
+            own_hand,
+            opponents,
+            boneyard_size,
+        )
+    }
+
+    /// Record `player` playing `tile`, whether an opponent or the tracking player.
+    ///
+    /// # Errors
+    /// - if the tile is not in the set or has already been played
+    /// - if the tile isn't in the tracking player's hand (for their own plays)
+    /// - if the player is unknown, or their earlier passes rule the tile out
+    pub fn played(&mut self, player: &str, tile: &Domino) -> GameResult<()> {
+        let tile = self.tile(tile.id)?;
+        if self.played.contains(&tile.id) {
+            return Err(InferenceError::TileAlreadySeen(tile.id).into());
+        }
+        if player == self.player {
+            if !self.mine.remove(&tile.id) {
+                return Err(InferenceError::UnknownTile(tile.id).into());
+            }
+        } else {
+            if self.mine.contains(&tile.id) {
+                return Err(InferenceError::TileAlreadySeen(tile.id).into());
+            }
+            let opponent = self.opponent_mut(player)?;
+            // a pass rules numbers out for every group held, so older groups exclude at
+            // least what newer ones do; taking the tile from the oldest group that allows
+            // it leaves the loosest hand, which covers every other choice
+            let Some(group) = opponent
+                .groups
+                .iter_mut()
+                .find(|g| g.count > 0 && g.allows(&tile))
+            else {
+                return Err(InferenceError::ImpossiblePlay {
+                    player: player.to_string(),
+                    tile: tile.id,
+                }
+                .into());
+            };
+            group.count -= 1;
+        }
+        self.played.insert(tile.id);
+        Ok(())
+    }
+
+    /// Record an opponent passing (or drawing instead of playing) when the open ends
+    /// showed `numbers`: they hold no tile showing any of them.
+    ///
+    /// # Errors
+    /// - if the player is unknown
+    pub fn passed(&mut self, player: &str, numbers: &[u8]) -> GameResult<()> {
+        let opponent = self.opponent_mut(player)?;
+        for group in &mut opponent.groups {
+            group.excluded.extend(numbers);
+        }
+        Ok(())
+    }
+
+    /// Record `player` drawing `count` tiles from the boneyard. The tracking player should
+    /// also call [`observe_own_hand`](Self::observe_own_hand) so the drawn tiles are known.
+    ///
+    /// # Errors
+    /// - if the player is unknown
+    pub fn drew(&mut self, player: &str, count: usize) -> GameResult<()> {
+        if player != self.player {
+            self.opponent_mut(player)?.groups.push(Group {
+                count,
+                excluded: BTreeSet::new(),
+            });
+        }
+        self.boneyard_size = self.boneyard_size.saturating_sub(count);
+        Ok(())
+    }
+
+    /// Update the tiles known to be in the tracking player's own hand.
+    ///
+    /// # Errors
+    /// - if the hand holds a tile outside the set or one already played
+    pub fn observe_own_hand(&mut self, hand: &DominoHand) -> GameResult<()> {
+        for tile in &hand.tiles {
+            self.tile(tile.id)?;
+            if self.played.contains(&tile.id) {
+                return Err(InferenceError::TileAlreadySeen(tile.id).into());
+            }
+        }
+        self.mine = hand.tiles.iter().map(|t| t.id).collect();
+        Ok(())
+    }
+
+    /// Set the number of tiles left in the boneyard, as publicly visible.
+    pub fn set_boneyard_size(&mut self, size: usize) {
+        self.boneyard_size = size;
+    }
+
+    /// The boneyard size the tracker currently believes.
+    #[must_use]
+    pub fn boneyard_size(&self) -> usize {
+        self.boneyard_size
+    }
+
+    /// Tiles neither played nor in the tracking player's hand.
+    #[must_use]
+    pub fn hidden_tiles(&self) -> Vec<Domino> {
+        self.universe
+            .iter()
+            .filter(|t| !self.played.contains(&t.id) && !self.mine.contains(&t.id))
+            .copied()
+            .collect()
+    }
+
+    /// How many tiles `player` holds.
+    ///
+    /// # Errors
+    /// - if the player is unknown
+    pub fn hand_size(&self, player: &str) -> GameResult<usize> {
+        Ok(self.opponent(player)?.groups.iter().map(|g| g.count).sum())
+    }
+
+    /// Numbers `player` is known to hold no tile of.
+    ///
+    /// # Errors
+    /// - if the player is unknown
+    pub fn voids(&self, player: &str) -> GameResult<BTreeSet<u8>> {
+        Ok(self
+            .opponent(player)?
+            .groups
+            .iter()
+            .filter(|g| g.count > 0)
+            .map(|g| g.excluded.clone())
+            .reduce(|a, b| a.intersection(&b).copied().collect())
+            .unwrap_or_default())
+    }
+
+    /// Hidden tiles `player` could be holding.
+    ///
+    /// # Errors
+    /// - if the player is unknown
+    pub fn possible_tiles(&self, player: &str) -> GameResult<Vec<Domino>> {
+        let groups = &self.opponent(player)?.groups;
+        Ok(self
+            .hidden_tiles()
+            .into_iter()
+            .filter(|t| groups.iter().any(|g| g.count > 0 && g.allows(t)))
+            .collect())
+    }
+
+    /// Deal the hidden tiles into opponent hands and a boneyard consistent with every
+    /// pass, play and draw recorded so far.
+    ///
+    /// Tiles are dealt at random, most constrained group first, retrying up to
+    /// [`SAMPLE_ATTEMPTS`] times if a deal gets stuck.
+    ///
+    /// Every world returned is consistent, but consistent worlds are not equally likely to
+    /// be drawn: a tightly constrained group picks first, so the tiles it allows turn up
+    /// in its hand more often than uniform sampling would give. Treat averages over
+    /// sampled worlds as estimates, not exact odds.
+    ///
+    /// # Errors
+    /// - if the hidden tile count doesn't match the hand sizes plus the boneyard
+    /// - if no consistent deal was found
+    pub fn sample_world<R: Rng + ?Sized>(&self, rng: &mut R) -> GameResult<SampledWorld> {
+        let mut hidden = self.hidden_tiles();
+        let accounted = self.boneyard_size
+            + self
+                .opponents
+                .iter()
+                .flat_map(|o| &o.groups)
+                .map(|g| g.count)
+                .sum::<usize>();
+        if hidden.len() != accounted {
+            return Err(InferenceError::CountMismatch {
+                hidden: hidden.len(),
+                accounted,
+            }
+            .into());
+        }
+
+        let mut groups: Vec<(usize, &Group)> = self
+            .opponents
+            .iter()
+            .enumerate()
+            .flat_map(|(seat, o)| o.groups.iter().map(move |g| (seat, g)))
+            .filter(|(_, g)| g.count > 0)
+            .collect();
+        groups.sort_by_key(|(_, g)| hidden.iter().filter(|t| g.allows(t)).count());
+
+        'attempt: for _ in 0..SAMPLE_ATTEMPTS {
+            hidden.shuffle(rng);
+            let mut dealt = vec![false; hidden.len()];
+            let mut hands: Vec<DominoHand> = self
+                .opponents
+                .iter()
+                .map(|o| DominoHand::new(&o.name))
+                .collect();
+            for &(seat, group) in &groups {
+                let picks: Vec<usize> = (0..hidden.len())
+                    .filter(|&i| !dealt[i] && group.allows(&hidden[i]))
+                    .take(group.count)
+                    .collect();
+                if picks.len() < group.count {
+                    continue 'attempt;
+                }
+                for i in picks {
+                    dealt[i] = true;
+                    hands[seat].tiles.push(hidden[i]);
+                }
+            }
+            let boneyard = hidden
+                .iter()
+                .zip(&dealt)
+                .filter(|(_, dealt)| !**dealt)
+                .map(|(tile, _)| *tile)
+                .collect();
+            return Ok(SampledWorld {
+                hands,
+                boneyard: BonePile { tiles: boneyard },
+            });
+        }
+        Err(InferenceError::NoConsistentWorld.into())
+    }
+
+    fn tile(&self, id: usize) -> GameResult<Domino> {
+        self.universe
+            .iter()
+            .find(|t| t.id == id)
+            .copied()
+            .ok_or_else(|| InferenceError::UnknownTile(id).into())
+    }
+
+    fn opponent(&self, player: &str) -> GameResult<&Opponent> {
+        self.opponents
+            .iter()
+            .find(|o| o.name == player)
+            .ok_or_else(|| InferenceError::UnknownPlayer(player.to_string()).into())
+    }
+
+    fn opponent_mut(&mut self, player: &str) -> GameResult<&mut Opponent> {
+        self.opponents
+            .iter_mut()
+            .find(|o| o.name == player)
+            .ok_or_else(|| InferenceError::UnknownPlayer(player.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn shows(tile: &Domino, n: u8) -> bool {
+        tile.left == n || tile.right == n
+    }
+
+    /// Double-3 set (10 tiles); ann holds 0:0 and 0:1, bob and cy hold 3 each, 2 in the boneyard.
+    fn tracker() -> TileInference {
+        let mut ann = DominoHand::new("ann");
+        ann.tiles = BonePile::full_set(3)[..2].to_vec();
+        TileInference::for_set(3, &ann, &[("bob", 3), ("cy", 3)], 2)
+    }
+
+    #[test]
+    fn passes_rule_out_numbers_until_a_draw() {
+        let mut t = tracker();
+        assert_eq!(t.possible_tiles("bob").unwrap().len(), 8);
+        t.passed("bob", &[3]).unwrap();
+        assert!(
+            t.possible_tiles("bob")
+                .unwrap()
+                .iter()
+                .all(|d| !shows(d, 3))
+        );
+        assert_eq!(t.voids("bob").unwrap(), BTreeSet::from([3]));
+        assert!(t.voids("cy").unwrap().is_empty());
+
+        t.drew("bob", 1).unwrap();
+        assert_eq!(t.hand_size("bob").unwrap(), 4);
+        assert_eq!(t.boneyard_size(), 1);
+        // the new tile could be anything, but bob's older three still lack a 3
+        assert!(t.voids("bob").unwrap().is_empty());
+        assert_eq!(t.possible_tiles("bob").unwrap().len(), 8);
+    }
+
+    #[test]
+    fn plays_come_from_the_most_constrained_group() {
+        let mut t = tracker();
+        t.passed("bob", &[3]).unwrap();
+        t.drew("bob", 1).unwrap();
+        // 2:3 can only be the tile drawn after the pass
+        let two_three = Domino::new(2, 3, 8);
+        t.played("bob", &two_three).unwrap();
+        assert_eq!(t.hand_size("bob").unwrap(), 3);
+        assert_eq!(t.voids("bob").unwrap(), BTreeSet::from([3]));
+        t.passed("bob", &[2]).unwrap();
+        assert_eq!(
+            t.played("bob", &Domino::new(3, 3, 9)),
+            Err(InferenceError::ImpossiblePlay {
+                player: "bob".into(),
+                tile: 9
+            }
+            .into())
+        );
+        assert_eq!(
+            t.played("cy", &two_three),
+            Err(InferenceError::TileAlreadySeen(8).into())
+        );
+    }
+
+    #[test]
+    fn plays_keep_earlier_passes() {
+        let mut t = tracker();
+        t.passed("bob", &[3]).unwrap();
+        t.drew("bob", 2).unwrap();
+        // 1:1 fits either group, but two of bob's tiles still predate the pass
+        t.played("bob", &Domino::new(1, 1, 4)).unwrap();
+        assert_eq!(t.hand_size("bob").unwrap(), 4);
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..100 {
+            let world = t.sample_world(&mut rng).unwrap();
+            let threes = world.hands[0]
+                .tiles()
+                .iter()
+                .filter(|d| shows(d, 3))
+                .count();
+            assert!(threes <= 2);
+        }
+    }
+
+    #[test]
+    fn own_plays_and_hand_updates() {
+        let mut t = tracker();
+        assert_eq!(t.hidden_tiles().len(), 8);
+        t.played("ann", &Domino::new(0, 1, 1)).unwrap();
+        assert_eq!(t.hidden_tiles().len(), 8);
+        assert!(t.played("ann", &Domino::new(1, 1, 4)).is_err());
+        assert_eq!(
+            t.played("dee", &Domino::new(1, 1, 4)),
+            Err(InferenceError::UnknownPlayer("dee".into()).into())
+        );
+
+        t.drew("ann", 1).unwrap();
+        let mut ann = DominoHand::new("ann");
+        ann.tiles = vec![Domino::new(0, 0, 0), Domino::new(1, 1, 4)];
+        t.observe_own_hand(&ann).unwrap();
+        assert_eq!(t.hidden_tiles().len(), 7);
+        assert_eq!(t.boneyard_size(), 1);
+    }
+
+    #[test]
+    fn sampled_worlds_respect_the_record() {
+        let mut t = tracker();
+        t.passed("bob", &[3]).unwrap();
+        t.passed("cy", &[0]).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let world = t.sample_world(&mut rng).unwrap();
+            assert_eq!(world.hands[0].player(), "bob");
+            assert!(world.hands[0].tiles().iter().all(|d| !shows(d, 3)));
+            assert!(world.hands[1].tiles().iter().all(|d| !shows(d, 0)));
+            assert_eq!(world.hands[1].tiles().len(), 3);
+            assert_eq!(world.boneyard.remaining(), 2);
+            let mut ids: Vec<usize> = world
+                .hands
+                .iter()
+                .flat_map(|h| h.tiles())
+                .chain(&world.boneyard.tiles)
+                .map(|d| d.id)
+                .collect();
+            ids.sort_unstable();
+            assert_eq!(ids, [2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+    }
+
+    #[test]
+    fn impossible_worlds_are_reported() {
+        let mut t = tracker();
+        t.set_boneyard_size(3);
+        assert_eq!(
+            t.sample_world(&mut rand::rng()).unwrap_err(),
+            InferenceError::CountMismatch {
+                hidden: 8,
+                accounted: 9
+            }
+            .into()
+        );
+        t.set_boneyard_size(2);
+        // every hidden tile shows a 1, 2 or 3
+        t.passed("bob", &[1, 2, 3]).unwrap();
+        t.passed("cy", &[1, 2, 3]).unwrap();
+        assert_eq!(
+            t.sample_world(&mut rand::rng()).unwrap_err(),
+            InferenceError::NoConsistentWorld.into()
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! # Two-Ended Layouts
+//!
+//! A [`Train`](crate::Train) only grows from its tail. The classic line games (Block,
+//! Draw, All Fives / Muggins) instead grow a [`Layout`] from both ends of the first tile
+//! played, the *set*. With spinners enabled, the first double played becomes the
+//! *spinner*: once both of its sides have been played on, it also opens north and south,
+//! giving up to four open ends.
+//!
+//! Each line of tiles leading away from the set is an *arm*, identified by the
+//! [`Direction`] it grows in. Tiles on an arm are stored oriented so their `left` faces
+//! the centre of the layout.
+//!
+//! The layout offers legal-move enumeration for a [`DominoHand`], the open-end pip sum
+//! used for All Fives scoring, and blocked-game detection with pip-count resolution.
+//!
+//! ```
+//! use gametools::{Domino, GameResult};
+//! use gametools::dominos::layout::{Direction, Layout};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut layout = Layout::with_spinner(Domino::new(5, 5, 0));
+//! assert_eq!(layout.end_sum(), 10); // a lone double counts both halves
+//!
+//! layout.play(Domino::new(5, 2, 1), Direction::East)?;
+//! layout.play(Domino::new(0, 5, 2), Direction::West)?;
+//! assert_eq!(layout.end_sum(), 2); // the covered spinner no longer counts
+//!
+//! // both sides of the spinner are covered, so north and south are open too
+//! layout.play(Domino::new(5, 3, 3), Direction::North)?;
+//! assert_eq!(layout.end_sum(), 5);
+//! assert_eq!(layout.fives_score(), 5);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub use crate::dominos::Direction;
+use crate::dominos::{Domino, DominoHand};
+use crate::{DominoError, GameResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A tile on the table, in the order it was played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Placement {
+    /// The tile, oriented so `left` faces the centre of the layout.
+    pub tile: Domino,
+    /// The arm the tile was added to, or `None` for the set.
+    pub direction: Option<Direction>,
+    /// Doubles are laid across the line of play.
+    pub crosswise: bool,
+}
+
+/// A legal play of a tile from a hand onto one open end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutMove {
+    pub tile_id: usize,
+    pub end: Direction,
+}
+
+/// How a blocked game is resolved: the player with the fewest pips wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockedResult {
+    /// Index of the winning hand, or `None` if the lowest pip count is tied.
+    pub winner: Option<usize>,
+    /// Pips left in each hand, in the order the hands were supplied.
+    pub pip_counts: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Arm {
+    base: u8,
+    base_is_double: bool,
+    /// Arms growing from the set count their base as an open end before any tile is
+    /// played on them; the arms of a spinner do not.
+    from_set: bool,
+    tiles: Vec<Domino>,
+}
+
+impl Arm {
+    fn tip(&self) -> u8 {
+        self.tiles.last().map_or(self.base, |t| t.right)
+    }
+
+    fn tip_is_double(&self) -> bool {
+        self.tiles
+            .last()
+            .map_or(self.base_is_double, Domino::is_double)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SpinnerSpot {
+    /// Arm holding the spinner, or `None` when the set is the spinner.
+    arm: Option<Direction>,
+    /// Position of the spinner within that arm.
+    index: usize,
+}
+
+/// A line of dominos that can be extended at either end, and at a spinner.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Layout {
+    set: Domino,
+    arms: BTreeMap<Direction, Arm>,
+    placements: Vec<Placement>,
+    spinners: bool,
+    spinner: Option<SpinnerSpot>,
+}
+
+impl Layout {
+    /// Start a layout without spinners (Block and Draw games) from the first tile played.
+    #[must_use]
+    pub fn new(set: Domino) -> Self {
+        Self::build(set, false)
+    }
+
+    /// Start a layout where the first double played becomes a spinner (All Fives, Muggins).
+    #[must_use]
+    pub fn with_spinner(set: Domino) -> Self {
+        Self::build(set, true)
+    }
+
+    fn build(set: Domino, spinners: bool) -> Self {
+        let side = |base| Arm {
+            base,
+            base_is_double: set.is_double(),
+            from_set: true,
+            tiles: Vec::new(),
+        };
+        let mut layout = Self {
+            set,
+            arms: BTreeMap::from([
+                (Direction::East, side(set.right)),
+                (Direction::West, side(set.left)),
+            ]),
+            placements: vec![Placement {
+                tile: set,
+                direction: None,
+                crosswise: set.is_double(),
+            }],
+            spinners,
+            spinner: None,
+        };
+        if spinners && set.is_double() {
+            layout.add_spinner(SpinnerSpot {
+                arm: None,
+                index: 0,
+            });
+        }
+        layout
+    }
+
+    fn add_spinner(&mut self, spot: SpinnerSpot) {
+        let pips = match spot.arm {
+            Some(dir) => self.arms[&dir].tiles[spot.index].right,
+            None => self.set.right,
+        };
+        for dir in [Direction::North, Direction::South] {
+            self.arms.insert(
+                dir,
+                Arm {
+                    base: pips,
+                    base_is_double: true,
+                    from_set: false,
+                    tiles: Vec::new(),
+                },
+            );
+        }
+        self.spinner = Some(spot);
+    }
+
+    /// The first tile played.
+    #[must_use]
+    pub fn set(&self) -> Domino {
+        self.set
+    }
+
+    /// The spinner, once a double has become one.
+    #[must_use]
+    pub fn spinner(&self) -> Option<Domino> {
+        let spot = self.spinner?;
+        Some(match spot.arm {
+            Some(dir) => self.arms[&dir].tiles[spot.index],
+            None => self.set,
+        })
+    }
+
+    /// Every tile on the table, in the order played.
+    #[must_use]
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    /// Number of tiles on the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.placements.len()
+    }
+
+    /// A layout always holds at least its set, so this is always `false`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+
+    /// The tiles on the arm growing in `direction`, from the centre outward.
+    #[must_use]
+    pub fn arm(&self, direction: Direction) -> &[Domino] {
+        self.arms.get(&direction).map_or(&[], |arm| &arm.tiles)
+    }
+
+    fn spinner_is_open(&self) -> bool {
+        match self.spinner {
+            None => false,
+            Some(SpinnerSpot { arm: None, .. }) => {
+                !self.arms[&Direction::East].tiles.is_empty()
+                    && !self.arms[&Direction::West].tiles.is_empty()
+            }
+            Some(SpinnerSpot {
+                arm: Some(dir),
+                index,
+            }) => self.arms[&dir].tiles.len() > index + 1,
+        }
+    }
+
+    /// The ends that can be played on now, with the number each one shows.
+    #[must_use]
+    pub fn open_ends(&self) -> Vec<(Direction, u8)> {
+        let spinner_open = self.spinner_is_open();
+        self.arms
+            .iter()
+            .filter(|(dir, _)| spinner_open || matches!(dir, Direction::East | Direction::West))
+            .map(|(dir, arm)| (*dir, arm.tip()))
+            .collect()
+    }
+
+    /// Whether `tile` can be played on the end facing `end`.
+    #[must_use]
+    pub fn accepts(&self, tile: &Domino, end: Direction) -> bool {
+        self.open_ends()
+            .iter()
+            .any(|&(dir, pips)| dir == end && (tile.left == pips || tile.right == pips))
+    }
+
+    /// Play `tile` on the end facing `end`.
+    ///
+    /// With spinners enabled, the first double played becomes the spinner.
+    ///
+    /// # Errors
+    /// - [`DominoError::EndNotOpen`] if there is no open end in that direction.
+    /// - [`DominoError::TileUnconnected`] if the tile does not match the end.
+    pub fn play(&mut self, tile: Domino, end: Direction) -> GameResult<()> {
+        let pips = self
+            .open_ends()
+            .into_iter()
+            .find(|(dir, _)| *dir == end)
+            .map(|(_, pips)| pips)
+            .ok_or(DominoError::EndNotOpen(end))?;
+        let tile = match tile {
+            _ if tile.left == pips => tile,
+            _ if tile.right == pips => tile.flipped(),
+            _ => return Err(DominoError::TileUnconnected.into()),
+        };
+        let arm = self.arms.get_mut(&end).expect("open ends have arms");
+        arm.tiles.push(tile);
+        let index = arm.tiles.len() - 1;
+        self.placements.push(Placement {
+            tile,
+            direction: Some(end),
+            crosswise: tile.is_double(),
+        });
+        if self.spinners && self.spinner.is_none() && tile.is_double() {
+            self.add_spinner(SpinnerSpot {
+                arm: Some(end),
+                index,
+            });
+        }
+        Ok(())
+    }
+
+    /// Play the tile with `tile_id` from `hand` on the end facing `end`.
+    ///
+    /// The hand is only changed if the play succeeds.
+    ///
+    /// # Errors
+    /// - [`DominoError::TileNotFound`] if the tile is not in the hand.
+    /// - any error that [`play`](Self::play) can return.
+    pub fn play_from_hand(
+        &mut self,
+        hand: &mut DominoHand,
+        tile_id: usize,
+        end: Direction,
+    ) -> GameResult<()> {
+        let pos = hand
+            .tiles
+            .iter()
+            .position(|t| t.id == tile_id)
+            .ok_or(DominoError::TileNotFound(tile_id))?;
+        self.play(hand.tiles[pos], end)?;
+        hand.tiles.remove(pos);
+        Ok(())
+    }
+
+    /// Every tile and end combination `hand` could legally play now.
+    #[must_use]
+    pub fn legal_moves(&self, hand: &DominoHand) -> Vec<LayoutMove> {
+        let ends = self.open_ends();
+        hand.tiles
+            .iter()
+            .flat_map(|tile| {
+                ends.iter()
+                    .filter(|(_, pips)| tile.left == *pips || tile.right == *pips)
+                    .map(|(end, _)| LayoutMove {
+                        tile_id: tile.id,
+                        end: *end,
+                    })
+            })
+            .collect()
+    }
+
+    /// Sum of the pips showing on the open ends, as counted in All Fives and Muggins.
+    ///
+    /// A double at an end counts both halves. Until both sides of the set are played on,
+    /// its uncovered side still counts; the arms of a spinner count only once a tile has
+    /// been played on them.
+    #[must_use]
+    pub fn end_sum(&self) -> u32 {
+        if self.placements.len() == 1 {
+            return u32::from(self.set.points());
+        }
+        self.arms
+            .values()
+            .filter(|arm| arm.from_set || !arm.tiles.is_empty())
+            .map(|arm| {
+                let tip = u32::from(arm.tip());
+                if arm.tip_is_double() { tip * 2 } else { tip }
+            })
+            .sum()
+    }
+
+    /// Points scored under All Fives: the end sum when it is a multiple of five, otherwise 0.
+    #[must_use]
+    pub fn fives_score(&self) -> u32 {
+        self.score_multiple_of(5)
+    }
+
+    /// The end sum when it is a multiple of `divisor` (e.g. 3 for All Threes), otherwise 0.
+    #[must_use]
+    pub fn score_multiple_of(&self, divisor: u32) -> u32 {
+        let sum = self.end_sum();
+        if sum.is_multiple_of(divisor) { sum } else { 0 }
+    }
+
+    /// Whether none of `hands` can play. In a Draw game the boneyard must also be empty
+    /// for the game to be blocked.
+    #[must_use]
+    pub fn is_blocked(&self, hands: &[DominoHand]) -> bool {
+        hands.iter().all(|hand| self.legal_moves(hand).is_empty())
+    }
+
+    /// Resolve a blocked game: the hand with the fewest pips wins, and a tie has no winner.
+    #[must_use]
+    pub fn resolve_blocked(hands: &[DominoHand]) -> BlockedResult {
+        let pip_counts: Vec<u32> = hands
+            .iter()
+            .map(|hand| hand.tiles.iter().map(|t| u32::from(t.points())).sum())
+            .collect();
+        let lowest = pip_counts.iter().min().copied();
+        let mut leaders = pip_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| Some(**count) == lowest);
+        let winner = match (leaders.next(), leaders.next()) {
+            (Some((idx, _)), None) => Some(idx),
+            _ => None,
+        };
+        BlockedResult { winner, pip_counts }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let west: Vec<String> = self
+            .arm(Direction::West)
+            .iter()
+            .rev()
+            .map(|t| t.flipped().to_string())
+            .collect();
+        let east: Vec<String> = self
+            .arm(Direction::East)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        write!(f, "{}{}{}", west.concat(), self.set, east.concat())?;
+        for dir in [Direction::North, Direction::South] {
+            if !self.arm(dir).is_empty() {
+                let tiles: Vec<String> = self.arm(dir).iter().map(ToString::to_string).collect();
+                write!(f, " {dir}:{}", tiles.concat())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domino_hand;
+
+    #[test]
+    fn tiles_attach_at_either_end_and_are_oriented() {
+        let mut layout = Layout::new(Domino::new(3, 4, 0));
+        layout.play(Domino::new(6, 4, 1), Direction::East).unwrap();
+        layout.play(Domino::new(3, 1, 2), Direction::West).unwrap();
+        assert_eq!(
+            layout.open_ends(),
+            [(Direction::East, 6), (Direction::West, 1)]
+        );
+        assert_eq!(layout.arm(Direction::East)[0].as_tuple(), (4, 6, 1));
+        assert_eq!(layout.to_string(), "[1:3][3:4][4:6]");
+        assert_eq!(layout.len(), 3);
+
+        assert_eq!(
+            layout.play(Domino::new(2, 2, 3), Direction::East),
+            Err(DominoError::TileUnconnected.into())
+        );
+        assert_eq!(
+            layout.play(Domino::new(6, 6, 3), Direction::North),
+            Err(DominoError::EndNotOpen(Direction::North).into())
+        );
+        // without spinners a double is just laid crosswise
+        layout.play(Domino::new(6, 6, 3), Direction::East).unwrap();
+        assert!(layout.placements()[3].crosswise);
+        assert_eq!(layout.spinner(), None);
+    }
+
+    #[test]
+    fn spinner_opens_four_ways_once_both_sides_are_covered() {
+        let mut layout = Layout::with_spinner(Domino::new(2, 3, 0));
+        layout.play(Domino::new(3, 3, 1), Direction::East).unwrap();
+        assert_eq!(layout.spinner().map(|d| d.id()), Some(1));
+        assert_eq!(layout.open_ends().len(), 2);
+
+        layout.play(Domino::new(3, 5, 2), Direction::East).unwrap();
+        assert_eq!(
+            layout.open_ends(),
+            [
+                (Direction::East, 5),
+                (Direction::West, 2),
+                (Direction::North, 3),
+                (Direction::South, 3)
+            ]
+        );
+        // a second double is not a spinner
+        layout.play(Domino::new(5, 5, 3), Direction::East).unwrap();
+        assert_eq!(layout.spinner().map(|d| d.id()), Some(1));
+        assert_eq!(layout.open_ends().len(), 4);
+    }
+
+    #[test]
+    fn end_sum_follows_all_fives_counting() {
+        let mut layout = Layout::with_spinner(Domino::new(5, 5, 0));
+        assert_eq!(layout.end_sum(), 10);
+        layout.play(Domino::new(5, 0, 1), Direction::East).unwrap();
+        assert_eq!(layout.end_sum(), 10); // 5-5 still counts on the west end
+        layout.play(Domino::new(5, 6, 2), Direction::West).unwrap();
+        assert_eq!(layout.end_sum(), 6);
+        assert_eq!(layout.fives_score(), 0);
+        layout.play(Domino::new(5, 4, 3), Direction::North).unwrap();
+        assert_eq!(layout.end_sum(), 10);
+        layout.play(Domino::new(0, 0, 4), Direction::East).unwrap();
+        layout.play(Domino::new(4, 4, 5), Direction::North).unwrap();
+        assert_eq!(layout.end_sum(), 14);
+        assert_eq!(layout.fives_score(), 0);
+        assert_eq!(layout.score_multiple_of(7), 14);
+    }
+
+    #[test]
+    fn legal_moves_and_play_from_hand() {
+        let mut layout = Layout::new(Domino::new(1, 2, 0));
+        let mut player = domino_hand("ann", &[(2, 6), (1, 2), (4, 4)], 100);
+        let moves = layout.legal_moves(&player);
+        assert_eq!(
+            moves,
+            [
+                LayoutMove {
+                    tile_id: 100,
+                    end: Direction::East
+                },
+                LayoutMove {
+                    tile_id: 101,
+                    end: Direction::East
+                },
+                LayoutMove {
+                    tile_id: 101,
+                    end: Direction::West
+                },
+            ]
+        );
+        assert!(
+            layout
+                .play_from_hand(&mut player, 102, Direction::East)
+                .is_err()
+        );
+        assert!(
+            layout
+                .play_from_hand(&mut player, 999, Direction::East)
+                .is_err()
+        );
+        assert_eq!(player.tiles().len(), 3);
+        layout
+            .play_from_hand(&mut player, 101, Direction::West)
+            .unwrap();
+        assert_eq!(player.tiles().len(), 2);
+        assert_eq!(
+            layout.open_ends(),
+            [(Direction::East, 2), (Direction::West, 2)]
+        );
+    }
+
+    #[test]
+    fn blocked_games_go_to_the_lowest_pip_count() {
+        let layout = Layout::new(Domino::new(0, 0, 0));
+        let hands = [
+            domino_hand("ann", &[(1, 2), (3, 3)], 100),
+            domino_hand("ben", &[(6, 1)], 100),
+        ];
+        assert!(layout.is_blocked(&hands));
+        let result = Layout::resolve_blocked(&hands);
+        assert_eq!(result.pip_counts, [9, 7]);
+        assert_eq!(result.winner, Some(1));
+
+        let tied = [
+            domino_hand("ann", &[(1, 2)], 100),
+            domino_hand("ben", &[(3, 0)], 100),
+        ];
+        assert_eq!(Layout::resolve_blocked(&tied).winner, None);
+        assert!(!layout.is_blocked(&[domino_hand("cy", &[(0, 4)], 100)]));
+    }
+}
@@ -0,0 +1,370 @@
+//! # Line Planning by Objective
+//!
+//! The longest line isn't always the best one. Late in a round a player may rather dump
+//! high-pip tiles, clear out doubles, or hold on to tiles of a number they expect to need.
+//! [`DominoHand::plan_lines`] enumerates the lines a hand can play from a given number,
+//! scores each with a [`LineObjective`], and returns the best candidates ranked from
+//! highest score down.
+//!
+//! Objectives are pluggable: use one of the built-in ones ([`Longest`], [`MostPips`],
+//! [`MostDoubles`], [`LeastRemaining`], [`KeepNumber`]) or any closure taking the tiles
+//! played and the tiles kept.
+//!
+//! ```
+//! use gametools::{BonePile, DominoHand, GameResult};
+//! use gametools::dominos::line_planner::{LeastRemaining, MostPips};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut pile = BonePile::new(12);
+//! let hand = DominoHand::new_with_draw("ann", 12, &mut pile)?;
+//!
+//! let by_pips = hand.plan_lines(12, &MostPips, 3);
+//! assert!(by_pips.lines.len() <= 3);
+//! assert!(by_pips.lines.windows(2).all(|w| w[0].score >= w[1].score));
+//!
+//! // closures work as objectives too: here, the fewest tiles left in hand
+//! let shortest_hand = hand.plan_lines(12, &|_: &[_], kept: &[_]| -(kept.len() as i64), 1);
+//! let least_points = hand.plan_lines(12, &LeastRemaining { zero_worth: 50 }, 1);
+//! assert_eq!(shortest_hand.lines.len(), least_points.lines.len());
+//! # Ok(())
+//! # }
+//! ```
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::dominos::{Domino, DominoHand};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Upper limit on lines examined by one call to [`DominoHand::plan_lines`], so that very
+/// large hands still return promptly (with the best candidates among those examined, and
+/// [`LinePlan::complete`] set to `false`).
+pub const MAX_LINES_EXAMINED: usize = 250_000;
+
+/// Scores a candidate line. Higher scores rank first.
+pub trait LineObjective {
+    /// Score a line given the tiles `played` (in play order, oriented so each tile's
+    /// `left` matches the previous tile's `right`) and the tiles `kept` in hand.
+    fn score(&self, played: &[Domino], kept: &[Domino]) -> i64;
+}
+
+impl<F> LineObjective for F
+where
+    F: Fn(&[Domino], &[Domino]) -> i64,
+{
+    fn score(&self, played: &[Domino], kept: &[Domino]) -> i64 {
+        self(played, kept)
+    }
+}
+
+/// Play as many tiles as possible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Longest;
+
+impl LineObjective for Longest {
+    fn score(&self, played: &[Domino], _kept: &[Domino]) -> i64 {
+        played.len() as i64
+    }
+}
+
+/// Play as many pips as possible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MostPips;
+
+impl LineObjective for MostPips {
+    fn score(&self, played: &[Domino], _kept: &[Domino]) -> i64 {
+        played.iter().map(|t| i64::from(t.points())).sum()
+    }
+}
+
+/// Play as many doubles as possible, then as many tiles as possible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MostDoubles;
+
+impl LineObjective for MostDoubles {
+    fn score(&self, played: &[Domino], _kept: &[Domino]) -> i64 {
+        let doubles = played.iter().filter(|t| t.is_double()).count() as i64;
+        doubles * 1000 + played.len() as i64
+    }
+}
+
+/// Leave as few points in hand as possible, counting the 0-0 tile as `zero_worth`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeastRemaining {
+    pub zero_worth: u8,
+}
+
+impl LineObjective for LeastRemaining {
+    fn score(&self, _played: &[Domino], kept: &[Domino]) -> i64 {
+        -kept
+            .iter()
+            .map(|t| i64::from(t.points_with_zero_worth(self.zero_worth)))
+            .sum::<i64>()
+    }
+}
+
+/// Keep as many tiles showing this number as possible, then play as many tiles as possible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeepNumber(pub u8);
+
+impl LineObjective for KeepNumber {
+    fn score(&self, played: &[Domino], kept: &[Domino]) -> i64 {
+        let keepers = kept
+            .iter()
+            .filter(|t| t.left == self.0 || t.right == self.0)
+            .count() as i64;
+        keepers * 1000 + played.len() as i64
+    }
+}
+
+/// A candidate line and its score under the objective used to plan it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RankedLine {
+    /// Domino ids in play order, ready for [`DominoHand::play_line`].
+    pub line: Vec<usize>,
+    pub score: i64,
+}
+
+/// The result of [`DominoHand::plan_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinePlan {
+    /// The best candidates, highest score first.
+    pub lines: Vec<RankedLine>,
+    /// `true` if every line was examined; `false` if the search stopped at
+    /// [`MAX_LINES_EXAMINED`], so a better line may have been missed.
+    pub complete: bool,
+}
+
+impl DominoHand {
+    /// The best `count` lines this hand can play from `head`, ranked by `objective`.
+    ///
+    /// Every non-empty line is a candidate, including lines that stop early. Lines that
+    /// use the same tiles and end on the same number score the same and are only listed
+    /// once. Ties rank longer lines first, then lines found earlier. At most
+    /// [`MAX_LINES_EXAMINED`] lines are scored.
+    ///
+    /// ```
+    /// use gametools::{BonePile, DominoHand, GameResult};
+    /// use gametools::dominos::line_planner::Longest;
+    /// # fn main() -> GameResult<()> {
+    /// let mut pile = BonePile::new(9);
+    /// let hand = DominoHand::new_with_draw("ann", 10, &mut pile)?;
+    ///
+    /// // ranking by length agrees with the longest-line solver
+    /// let plan = hand.plan_lines(9, &Longest, 1);
+    /// assert!(plan.complete);
+    /// if let Some(best) = plan.lines.first() {
+    ///     assert_eq!(best.line.len(), hand.find_longest_from(9).len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn plan_lines(&self, head: u8, objective: &dyn LineObjective, count: usize) -> LinePlan {
+        if count == 0 {
+            return LinePlan {
+                lines: Vec::new(),
+                complete: true,
+            };
+        }
+        let mut planner = Planner {
+            tiles: &self.tiles,
+            objective,
+            count,
+            used: vec![0; self.tiles.len().div_ceil(64)],
+            path: Vec::new(),
+            seen: HashSet::new(),
+            examined: 0,
+            best: BinaryHeap::new(),
+            played: Vec::new(),
+            kept: Vec::new(),
+        };
+        planner.extend(head);
+        let complete = planner.examined < MAX_LINES_EXAMINED;
+        let lines = planner
+            .best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(((score, _, _), line))| RankedLine { line, score })
+            .collect();
+        LinePlan { lines, complete }
+    }
+}
+
+/// Ranks a candidate by score, then length, then discovery order (earlier first).
+type RankKey = (i64, usize, Reverse<usize>);
+
+struct Planner<'a> {
+    tiles: &'a [Domino],
+    objective: &'a dyn LineObjective,
+    count: usize,
+    /// Bitset of the tiles in `path`, indexed like `tiles`.
+    used: Vec<u64>,
+    /// Tile indices in play order, with the orientation each was played in.
+    path: Vec<(usize, Domino)>,
+    seen: HashSet<(Vec<u64>, u8)>,
+    examined: usize,
+    /// The best `count` candidates so far as domino ids, worst on top so it can be evicted.
+    best: BinaryHeap<Reverse<(RankKey, Vec<usize>)>>,
+    /// Scratch buffers for the tiles handed to the objective.
+    played: Vec<Domino>,
+    kept: Vec<Domino>,
+}
+
+impl Planner<'_> {
+    fn extend(&mut self, head: u8) {
+        for idx in 0..self.tiles.len() {
+            if self.examined >= MAX_LINES_EXAMINED {
+                return;
+            }
+            let tile = self.tiles[idx];
+            let (word, bit) = (idx / 64, 1 << (idx % 64));
+            if self.used[word] & bit != 0 {
+                continue;
+            }
+            let oriented = match tile {
+                _ if tile.left == head => tile,
+                _ if tile.right == head => tile.flipped(),
+                _ => continue,
+            };
+            self.used[word] |= bit;
+            self.path.push((idx, oriented));
+            if self.seen.insert((self.used.clone(), oriented.right)) {
+                self.record();
+                self.extend(oriented.right);
+            }
+            self.path.pop();
+            self.used[word] &= !bit;
+        }
+    }
+
+    fn record(&mut self) {
+        let order = Reverse(self.examined);
+        self.examined += 1;
+        self.played.clear();
+        self.played.extend(self.path.iter().map(|&(_, tile)| tile));
+        self.kept.clear();
+        self.kept.extend(
+            self.tiles
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| self.used[idx / 64] & (1 << (idx % 64)) == 0)
+                .map(|(_, tile)| *tile),
+        );
+        let score = self.objective.score(&self.played, &self.kept);
+        let key = (score, self.path.len(), order);
+        if self.best.len() == self.count
+            && self
+                .best
+                .peek()
+                .is_some_and(|Reverse((worst, _))| key <= *worst)
+        {
+            return;
+        }
+        let line = self
+            .path
+            .iter()
+            .map(|&(idx, _)| self.tiles[idx].id)
+            .collect();
+        self.best.push(Reverse((key, line)));
+        if self.best.len() > self.count {
+            self.best.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domino_hand;
+
+    #[test]
+    fn objectives_pick_different_lines() {
+        let hand = domino_hand("test", &[(1, 2), (2, 3), (3, 4), (1, 9), (9, 9)], 0);
+        assert_eq!(hand.plan_lines(1, &Longest, 1).lines[0].line, [0, 1, 2]);
+        let pips = &hand.plan_lines(1, &MostPips, 1).lines[0];
+        assert_eq!(pips.line, [3, 4]);
+        assert_eq!(pips.score, 28);
+        assert_eq!(hand.plan_lines(1, &MostDoubles, 1).lines[0].line, [3, 4]);
+        let least = &hand
+            .plan_lines(1, &LeastRemaining { zero_worth: 50 }, 1)
+            .lines[0];
+        assert_eq!(least.line, [3, 4]);
+        assert_eq!(least.score, -15);
+    }
+
+    #[test]
+    fn keep_number_holds_back_matching_tiles() {
+        let hand = domino_hand("test", &[(1, 2), (2, 3), (3, 4), (1, 5)], 0);
+        // keeping 3s means stopping after [1:2]; everything else loses a 3
+        let ranked = hand.plan_lines(1, &KeepNumber(3), 2).lines;
+        assert_eq!(ranked[0].line, [0]);
+        assert_eq!(ranked[0].score, 2001);
+        assert_eq!(ranked[1].line, [3]);
+    }
+
+    #[test]
+    fn candidates_are_ranked_and_deduplicated() {
+        // two ways round the 1-2-3 triangle end on 1 having used the same tiles
+        let hand = domino_hand("test", &[(1, 2), (2, 3), (3, 1)], 0);
+        let plan = hand.plan_lines(1, &Longest, usize::MAX);
+        assert!(plan.complete);
+        let ranked = plan.lines;
+        let lines: Vec<&[usize]> = ranked.iter().map(|r| r.line.as_slice()).collect();
+        assert_eq!(lines, [&[0, 1, 2][..], &[0, 1], &[2, 1], &[0], &[2]]);
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn closures_and_empty_plans() {
+        let hand = domino_hand("test", &[(4, 5), (5, 6)], 0);
+        let ends_on_six = |played: &[Domino], _: &[Domino]| {
+            i64::from(played.last().is_some_and(|t| t.right() == 6))
+        };
+        let best = &hand.plan_lines(4, &ends_on_six, 1).lines[0];
+        assert_eq!(best.line, [0, 1]);
+        assert_eq!(best.score, 1);
+        assert!(hand.plan_lines(8, &Longest, 5).lines.is_empty());
+        assert!(hand.plan_lines(4, &Longest, 0).lines.is_empty());
+    }
+
+    #[test]
+    fn ranked_lines_can_be_played() {
+        let mut hand = domino_hand("test", &[(6, 1), (1, 1), (1, 4), (4, 6), (6, 2)], 0);
+        let best = hand.plan_lines(6, &MostPips, 1).lines.remove(0);
+        let mut train = crate::Train::new("test", true, 6);
+        hand.play_line(&best.line, &mut train).unwrap();
+        assert_eq!(train.tiles().len(), best.line.len());
+    }
+
+    #[test]
+    fn bounded_plans_keep_the_best_and_report_truncation() {
+        let small = domino_hand(
+            "test",
+            &[
+                (6, 1),
+                (1, 1),
+                (1, 4),
+                (4, 6),
+                (6, 2),
+                (2, 4),
+                (4, 4),
+                (2, 1),
+            ],
+            0,
+        );
+        let everything = small.plan_lines(6, &MostPips, usize::MAX);
+        let top = small.plan_lines(6, &MostPips, 4);
+        assert!(everything.complete && top.complete);
+        assert_eq!(top.lines, everything.lines[..4]);
+
+        let full_set: Vec<(u8, u8)> = (0..=6).flat_map(|l| (l..=6).map(move |r| (l, r))).collect();
+        let plan = domino_hand("test", &full_set, 0).plan_lines(6, &Longest, 3);
+        assert!(!plan.complete);
+        assert_eq!(plan.lines.len(), 3);
+        assert!(plan.lines.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+}
@@ -0,0 +1,398 @@
+//! # Longest Line Solver
+//!
+//! Finds the longest line of tiles a [`DominoHand`] can play from a given number. Tiles
+//! are edges in a multigraph whose nodes are pip values, so the longest line is the
+//! longest trail starting at that node.
+//!
+//! Rather than growing trails tile by tile, the solver searches for the fewest tiles to
+//! *leave out* so that everything still reachable from the head can be walked in one
+//! trail (an Euler trail, which Hierholzer's algorithm then lays out). That search:
+//!
+//! * tracks dropped tiles in a bitset and never revisits the same remaining set
+//! * bounds each state by parity: every odd-degree node other than the trail's two ends
+//!   costs at least one more dropped tile
+//! * branches only on the tiles of two odd nodes, one of which must lose a tile
+//! * never drops doubles, and tries only one of several identical tiles
+//!
+//! Hands of 30+ tiles usually solve in milliseconds. For hard cases or tight frame
+//! budgets, [`DominoHand::find_longest_within`] stops after a time budget and returns
+//! the best line found so far.
+//!
+//! ```
+//! use std::time::Duration;
+//! use gametools::{BonePile, DominoHand, GameResult};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut pile = BonePile::new(12);
+//! let hand = DominoHand::new_with_draw("ann", 30, &mut pile)?;
+//! let search = hand.find_longest_within(12, Duration::from_millis(250));
+//! assert!(search.line.len() <= 30);
+//! if search.complete {
+//!     assert_eq!(search.line.len(), hand.find_longest_from(12).len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::dominos::{Domino, DominoHand};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The result of a time-budgeted longest-line search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LineSearch {
+    /// Domino ids of the best line found, in play order.
+    pub line: Vec<usize>,
+    /// `true` if the search finished, so `line` is a longest line; `false` if the budget
+    /// ran out first.
+    pub complete: bool,
+}
+
+impl DominoHand {
+    /// Search for the longest line from `head`, giving up after `budget` and returning the
+    /// best line found so far.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use gametools::{Domino, DominoHand};
+    ///
+    /// let hand = DominoHand::new("ann");
+    /// let search = hand.find_longest_within(6, Duration::from_millis(10));
+    /// assert!(search.line.is_empty());
+    /// assert!(search.complete);
+    /// ```
+    #[must_use]
+    pub fn find_longest_within(&self, head: u8, budget: Duration) -> LineSearch {
+        let deadline = Instant::now().checked_add(budget);
+        Solver::new(&self.tiles, deadline).run(head)
+    }
+}
+
+/// Longest line from `head` through `tiles`, as domino ids, searching to completion.
+pub(crate) fn longest_line(tiles: &[Domino], head: u8) -> Vec<usize> {
+    Solver::new(tiles, None).run(head).line
+}
+
+/// How many search steps pass between deadline checks.
+const DEADLINE_INTERVAL: u32 = 256;
+
+/// The tiles still in play around the head in one search state.
+struct Component {
+    edges: usize,
+    degree: Vec<usize>,
+    odd: Vec<usize>,
+}
+
+struct Solver<'a> {
+    tiles: &'a [Domino],
+    /// For each pip value, the tiles touching it as (other end, tile index).
+    adjacency: Vec<Vec<(u8, usize)>>,
+    /// For each tile, the previous tile index with the same pips, if any.
+    twin: Vec<Option<usize>>,
+    head: u8,
+    /// Tiles left out of the line in the current state, one bit per tile index.
+    dropped: Vec<u64>,
+    best: Vec<usize>,
+    limit: usize,
+    seen: HashSet<Vec<u64>>,
+    deadline: Option<Instant>,
+    timed_out: bool,
+    steps: u32,
+}
+
+impl<'a> Solver<'a> {
+    fn new(tiles: &'a [Domino], deadline: Option<Instant>) -> Self {
+        let top = tiles.iter().map(|t| t.left.max(t.right)).max().unwrap_or(0);
+        let mut adjacency = vec![Vec::new(); usize::from(top) + 1];
+        let mut twin = vec![None; tiles.len()];
+        for (idx, tile) in tiles.iter().enumerate() {
+            adjacency[usize::from(tile.left)].push((tile.right, idx));
+            if !tile.is_double() {
+                adjacency[usize::from(tile.right)].push((tile.left, idx));
+            }
+            twin[idx] = tiles[..idx].iter().rposition(|t| {
+                (t.left, t.right) == (tile.left, tile.right)
+                    || (t.left, t.right) == (tile.right, tile.left)
+            });
+        }
+        Self {
+            tiles,
+            adjacency,
+            twin,
+            head: 0,
+            dropped: vec![0; tiles.len().div_ceil(64)],
+            best: Vec::new(),
+            limit: 0,
+            seen: HashSet::new(),
+            deadline,
+            timed_out: false,
+            steps: 0,
+        }
+    }
+
+    fn run(mut self, head: u8) -> LineSearch {
+        if self
+            .adjacency
+            .get(usize::from(head))
+            .is_some_and(|adj| !adj.is_empty())
+        {
+            self.head = head;
+            self.best = self.greedy_walk();
+            let root = self.component();
+            self.limit = root.edges - Self::parity_cost(&root, usize::from(head));
+            self.search();
+        }
+        LineSearch {
+            line: self.best.iter().map(|&idx| self.tiles[idx].id).collect(),
+            complete: !self.timed_out,
+        }
+    }
+
+    fn is_dropped(&self, idx: usize) -> bool {
+        self.dropped[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    fn toggle(&mut self, idx: usize) {
+        self.dropped[idx / 64] ^= 1 << (idx % 64);
+    }
+
+    fn out_of_time(&mut self) -> bool {
+        if self.timed_out {
+            return true;
+        }
+        self.steps += 1;
+        if self.steps.is_multiple_of(DEADLINE_INTERVAL)
+            && self.deadline.is_some_and(|d| Instant::now() >= d)
+        {
+            self.timed_out = true;
+        }
+        self.timed_out
+    }
+
+    /// Fewest further tiles that must be dropped before the component can be walked in a
+    /// single trail from `head`: every odd node but the trail's far end needs one.
+    fn parity_cost(component: &Component, head: usize) -> usize {
+        let odd = component.odd.len();
+        if component.degree[head] % 2 == 1 {
+            odd.saturating_sub(2) / 2
+        } else {
+            odd / 2
+        }
+    }
+
+    /// Search over which tiles to drop. A state where the tiles reachable from the head
+    /// can all be walked in one trail is a candidate line; otherwise one of two odd nodes
+    /// (other than the head) must lose a tile, so only their tiles are branched on.
+    fn search(&mut self) {
+        if self.out_of_time() || self.best.len() == self.limit {
+            return;
+        }
+        let head = usize::from(self.head);
+        let component = self.component();
+        let cost = Self::parity_cost(&component, head);
+        if cost == 0 {
+            if component.edges > self.best.len() {
+                self.best = self.euler_trail();
+            }
+            return;
+        }
+        if component.edges - cost <= self.best.len() {
+            return;
+        }
+
+        let mut odd: Vec<usize> = component
+            .odd
+            .iter()
+            .copied()
+            .filter(|&n| n != head)
+            .collect();
+        odd.sort_by_key(|&n| component.degree[n]);
+        let mut candidates: Vec<(bool, usize)> = odd[..2]
+            .iter()
+            .flat_map(|&node| &self.adjacency[node])
+            .filter(|&&(_, idx)| {
+                !self.tiles[idx].is_double()
+                    && !self.is_dropped(idx)
+                    && self.twin[idx].is_none_or(|t| self.is_dropped(t))
+            })
+            .map(|&(_, idx)| {
+                let tile = self.tiles[idx];
+                let fixes_both = component.degree[usize::from(tile.left)] % 2 == 1
+                    && component.degree[usize::from(tile.right)] % 2 == 1;
+                (!fixes_both, idx)
+            })
+            .collect();
+        // dropping a tile between two odd nodes fixes both at once, so try those first
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        for (_, idx) in candidates {
+            self.toggle(idx);
+            if self.seen.insert(self.dropped.clone()) {
+                self.search();
+            }
+            self.toggle(idx);
+            if self.timed_out || self.best.len() == self.limit {
+                return;
+            }
+        }
+    }
+
+    /// Size and node degrees of the graph of undropped tiles reachable from the head.
+    fn component(&self) -> Component {
+        let mut degree = vec![0; self.adjacency.len()];
+        let mut visited = vec![false; self.adjacency.len()];
+        let mut counted = vec![false; self.tiles.len()];
+        let mut edges = 0;
+        let mut queue = vec![usize::from(self.head)];
+        visited[usize::from(self.head)] = true;
+        while let Some(node) = queue.pop() {
+            for &(other, idx) in &self.adjacency[node] {
+                if self.is_dropped(idx) || counted[idx] {
+                    continue;
+                }
+                counted[idx] = true;
+                edges += 1;
+                degree[node] += 1;
+                degree[usize::from(other)] += 1;
+                if !visited[usize::from(other)] {
+                    visited[usize::from(other)] = true;
+                    queue.push(usize::from(other));
+                }
+            }
+        }
+        let odd = (0..degree.len()).filter(|&n| degree[n] % 2 == 1).collect();
+        Component { edges, degree, odd }
+    }
+
+    /// A quick first line for the anytime search: walk from the head, preferring doubles
+    /// and then the tile leading to the pip value with the most tiles left.
+    fn greedy_walk(&self) -> Vec<usize> {
+        let mut used = vec![false; self.tiles.len()];
+        let mut remaining: Vec<usize> = self.adjacency.iter().map(Vec::len).collect();
+        let mut line = Vec::new();
+        let mut node = usize::from(self.head);
+        while let Some(&(next, idx)) = self.adjacency[node]
+            .iter()
+            .filter(|(_, idx)| !used[*idx])
+            .max_by_key(|(next, idx)| (self.tiles[*idx].is_double(), remaining[usize::from(*next)]))
+        {
+            used[idx] = true;
+            remaining[node] -= 1;
+            if !self.tiles[idx].is_double() {
+                remaining[usize::from(next)] -= 1;
+            }
+            line.push(idx);
+            node = usize::from(next);
+        }
+        line
+    }
+
+    /// Walk every undropped tile reachable from the head in one trail (Hierholzer's
+    /// algorithm). Only called once the parity cost is zero, so such a trail exists.
+    fn euler_trail(&self) -> Vec<usize> {
+        let mut used = self.dropped.clone();
+        let mut cursor = vec![0; self.adjacency.len()];
+        let mut stack: Vec<(u8, Option<usize>)> = vec![(self.head, None)];
+        let mut trail = Vec::new();
+        while let Some(&(node, _)) = stack.last() {
+            let adj = &self.adjacency[usize::from(node)];
+            let pos = &mut cursor[usize::from(node)];
+            while *pos < adj.len() && used[adj[*pos].1 / 64] & (1 << (adj[*pos].1 % 64)) != 0 {
+                *pos += 1;
+            }
+            if let Some(&(next, idx)) = adj.get(*pos) {
+                used[idx / 64] |= 1 << (idx % 64);
+                stack.push((next, Some(idx)));
+            } else if let Some((_, Some(idx))) = stack.pop() {
+                trail.push(idx);
+            }
+        }
+        trail.reverse();
+        trail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dominos::BonePile;
+
+    /// Plain exhaustive search, for checking the solver on small hands.
+    fn brute_force(tiles: &[Domino], head: u8, used: &mut Vec<bool>) -> usize {
+        let mut best = 0;
+        for (idx, tile) in tiles.iter().enumerate() {
+            if used[idx] || (tile.left != head && tile.right != head) {
+                continue;
+            }
+            let next = if tile.left == head {
+                tile.right
+            } else {
+                tile.left
+            };
+            used[idx] = true;
+            best = best.max(1 + brute_force(tiles, next, used));
+            used[idx] = false;
+        }
+        best
+    }
+
+    fn assert_valid_line(hand: &DominoHand, head: u8, line: &[usize]) {
+        let mut train = crate::Train::new("test", true, head);
+        let mut hand = hand.clone();
+        hand.play_line(line, &mut train).unwrap();
+    }
+
+    #[test]
+    fn matches_exhaustive_search_on_small_hands() {
+        for _ in 0..40 {
+            let mut pile = BonePile::new(6);
+            let hand = DominoHand::new_with_draw("test", 9, &mut pile).unwrap();
+            for head in 0..=6 {
+                let line = hand.find_longest_from(head);
+                assert_valid_line(&hand, head, &line);
+                let expected = brute_force(&hand.tiles, head, &mut vec![false; 9]);
+                assert_eq!(line.len(), expected, "hand {hand} from {head}");
+            }
+        }
+    }
+
+    #[test]
+    fn large_hands_finish() {
+        for size in [30, 40] {
+            let mut pile = BonePile::new(12);
+            let hand = DominoHand::new_with_draw("test", size, &mut pile).unwrap();
+            let search = hand.find_longest_within(12, Duration::from_secs(5));
+            assert!(search.complete);
+            assert_valid_line(&hand, 12, &search.line);
+        }
+    }
+
+    #[test]
+    fn duplicate_tiles_and_doubles_are_handled() {
+        let mut hand = DominoHand::new("test");
+        hand.tiles = vec![
+            Domino::new(1, 2, 0),
+            Domino::new(2, 1, 1),
+            Domino::new(1, 2, 2),
+            Domino::new(2, 2, 3),
+            Domino::new(5, 6, 4),
+        ];
+        let line = hand.find_longest_from(1);
+        assert_eq!(line.len(), 4);
+        assert_valid_line(&hand, 1, &line);
+        assert!(hand.find_longest_from(9).is_empty());
+    }
+
+    #[test]
+    fn zero_budget_returns_a_valid_partial_line() {
+        let mut pile = BonePile::new(15);
+        let hand = DominoHand::new_with_draw("test", 60, &mut pile).unwrap();
+        let search = hand.find_longest_within(15, Duration::ZERO);
+        assert_valid_line(&hand, 15, &search.line);
+    }
+}
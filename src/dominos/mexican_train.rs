@@ -0,0 +1,817 @@
+//! # Mexican Train Game Engine
+//!
+//! A turn-by-turn state machine for complete games of Mexican Train, built on the
+//! [`BonePile`], [`Train`] and [`DominoHand`] pieces of the parent module.
+//! [`MexicanTrainGame`] owns the boneyard, every hand and train, and enforces:
+//!
+//! * round anchors (the engine double) counting down from the highest double to 0-0
+//! * one personal train per player plus the shared Mexican train
+//! * train markers: a player who cannot play opens their train to everyone, and
+//!   closes it again by playing on it
+//! * a played double must be covered before any other play
+//! * drawing one tile when unable to play, then playing it or passing
+//! * end-of-round scoring from the pips left in each hand, with a configurable
+//!   value for the 0-0 tile
+//!
+//! The lowest total score after the 0-0 round wins.
+//!
+//! ```
+//! use gametools::GameResult;
+//! use gametools::dominos::mexican_train::{DrawOutcome, MexicanTrainGame, MexicanTrainRules, TurnOutcome};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut game = MexicanTrainGame::new(&["Ann", "Ben", "Cat"], MexicanTrainRules::default())?;
+//! assert_eq!(game.anchor(), 12);
+//!
+//! // play the first legal tile each turn, drawing when nothing fits
+//! for _ in 0..50 {
+//!     if let Some(&play) = game.legal_plays().first() {
+//!         if let TurnOutcome::RoundWon { player } = game.play(play.tile_id, play.train)? {
+//!             assert!(game.hand(player)?.tiles().is_empty());
+//!             break;
+//!         }
+//!     } else if let DrawOutcome::Drew { playable: true, .. } = game.draw()? {
+//!         let play = game.legal_plays()[0];
+//!         game.play(play.tile_id, play.train)?;
+//!     }
+//!     if game.is_round_over() {
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+
+use crate::dominos::{BonePile, Domino, DominoHand, MAX_PIPS, Train};
+use crate::{DominoError, GameResult, MexicanTrainError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Owner name given to the shared Mexican train.
+pub const MEXICAN_TRAIN: &str = "Mexican";
+
+/// Tunable constants for a game of Mexican Train.
+///
+/// The `Default` implementation gives the common rules: a double-12 set, hand sizes
+/// chosen by player count (see [`hand_size_for`](Self::hand_size_for)), and the 0-0
+/// tile worth 50 points when left in a hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MexicanTrainRules {
+    /// Highest double in the set; the first round is anchored on it. Capped at [`MAX_PIPS`].
+    pub max_pips: u8,
+    /// Tiles dealt to each player, or `None` for the standard count for the table size.
+    pub hand_size: Option<usize>,
+    /// Points charged for the 0-0 tile when it is left in a hand.
+    pub double_zero_worth: u8,
+}
+
+impl Default for MexicanTrainRules {
+    fn default() -> Self {
+        Self {
+            max_pips: 12,
+            hand_size: None,
+            double_zero_worth: 50,
+        }
+    }
+}
+
+impl MexicanTrainRules {
+    /// Tiles dealt to each of `players`: the configured `hand_size`, or else 15 tiles for
+    /// 2-4 players, 12 for 5-6, and 10 for 7-8.
+    ///
+    /// ```
+    /// use gametools::dominos::mexican_train::MexicanTrainRules;
+    ///
+    /// let rules = MexicanTrainRules::default();
+    /// assert_eq!(rules.hand_size_for(4), 15);
+    /// assert_eq!(rules.hand_size_for(8), 10);
+    /// ```
+    #[must_use]
+    pub fn hand_size_for(&self, players: usize) -> usize {
+        self.hand_size.unwrap_or(match players {
+            0..=4 => 15,
+            5 | 6 => 12,
+            _ => 10,
+        })
+    }
+}
+
+/// Identifies one of the trains on the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TrainId {
+    /// The personal train of the player at this seat.
+    Personal(usize),
+    /// The shared Mexican train, open to everyone.
+    Mexican,
+}
+
+/// A tile that may be played on a train.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrainPlay {
+    pub tile_id: usize,
+    pub train: TrainId,
+}
+
+/// The result of a successful play or pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TurnOutcome {
+    /// The turn passed to the next player.
+    Continue,
+    /// A double was played; the same player must now cover it (or draw).
+    CoverDouble,
+    /// The player at seat `player` emptied their hand, ending the round.
+    RoundWon { player: usize },
+    /// The boneyard is empty and nobody has played for a full rotation, ending the round.
+    RoundBlocked,
+}
+
+/// The result of the current player drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DrawOutcome {
+    /// `tile` was drawn. When `playable` is true the player must now
+    /// [`play`](MexicanTrainGame::play) it or [`pass`](MexicanTrainGame::pass); otherwise
+    /// their train has been marked and the turn has passed.
+    Drew { tile: Domino, playable: bool },
+    /// The boneyard is empty; the player's train has been marked and the turn has passed.
+    BoneyardEmpty,
+    /// The boneyard is empty and nobody has played for a full rotation, ending the round.
+    RoundBlocked,
+}
+
+/// The full state of a game of Mexican Train, from the first deal through the 0-0 round.
+///
+/// Players are identified by their seat index, which matches the order of the names
+/// supplied to [`MexicanTrainGame::new`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MexicanTrainGame {
+    rules: MexicanTrainRules,
+    hands: Vec<DominoHand>,
+    trains: Vec<Train>,
+    mexican: Train,
+    boneyard: BonePile,
+    engine: Domino,
+    scores: Vec<u32>,
+    round_points: Vec<u32>,
+    round: u8,
+    current: usize,
+    drawn_tile: Option<usize>,
+    open_double: Option<TrainId>,
+    idle_turns: usize,
+    round_over: bool,
+    round_winner: Option<usize>,
+}
+
+impl MexicanTrainGame {
+    /// Start a new game and deal the first round, anchored on the highest double.
+    ///
+    /// The player at seat 0 leads the first round, and the lead rotates each round after that.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::InvalidPlayerCount`] unless there are 2-8 players.
+    /// - [`MexicanTrainError::DuplicatePlayer`] if a name appears twice.
+    /// - [`MexicanTrainError::HandSizeTooLarge`] if the set cannot supply every hand.
+    pub fn new(players: &[&str], mut rules: MexicanTrainRules) -> GameResult<Self> {
+        if !(2..=8).contains(&players.len()) {
+            return Err(MexicanTrainError::InvalidPlayerCount(players.len()).into());
+        }
+        for (idx, name) in players.iter().enumerate() {
+            if players[..idx].contains(name) {
+                return Err(MexicanTrainError::DuplicatePlayer((*name).to_string()).into());
+            }
+        }
+        rules.max_pips = rules.max_pips.min(MAX_PIPS);
+        let top = usize::from(rules.max_pips);
+        let available = (top + 1) * (top + 2) / 2 - 1;
+        let needed = rules.hand_size_for(players.len()) * players.len();
+        if needed > available {
+            return Err(MexicanTrainError::HandSizeTooLarge { needed, available }.into());
+        }
+
+        let mut game = Self {
+            hands: players.iter().map(|name| DominoHand::new(name)).collect(),
+            trains: Vec::new(),
+            mexican: Train::new(MEXICAN_TRAIN, true, rules.max_pips),
+            boneyard: BonePile { tiles: Vec::new() },
+            engine: Domino::new(rules.max_pips, rules.max_pips, 0),
+            scores: vec![0; players.len()],
+            round_points: vec![0; players.len()],
+            round: 0,
+            current: 0,
+            drawn_tile: None,
+            open_double: None,
+            idle_turns: 0,
+            round_over: false,
+            round_winner: None,
+            rules,
+        };
+        game.deal_round()?;
+        Ok(game)
+    }
+
+    /// Deal the next round, anchored one lower than the last.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::RoundInProgress`] if the current round has not ended.
+    /// - [`MexicanTrainError::GameOver`] after the 0-0 round.
+    pub fn next_round(&mut self) -> GameResult<()> {
+        if !self.round_over {
+            return Err(MexicanTrainError::RoundInProgress.into());
+        }
+        if self.is_game_over() {
+            return Err(MexicanTrainError::GameOver.into());
+        }
+        self.round += 1;
+        self.deal_round()
+    }
+
+    fn deal_round(&mut self) -> GameResult<()> {
+        let anchor = self.anchor();
+        let names: Vec<String> = self.hands.iter().map(|h| h.player.clone()).collect();
+
+        let mut boneyard = BonePile::new(self.rules.max_pips);
+        let pos = boneyard
+            .tiles
+            .iter()
+            .position(|t| t.left == anchor && t.right == anchor)
+            .expect("every set contains the anchor double");
+        self.engine = boneyard.tiles.remove(pos);
+
+        let hand_size = self.rules.hand_size_for(names.len());
+        self.hands = names
+            .iter()
+            .map(|name| DominoHand::new_with_draw(name, hand_size, &mut boneyard))
+            .collect::<GameResult<_>>()?;
+        self.trains = names
+            .iter()
+            .map(|name| Train::new(name, false, anchor))
+            .collect();
+        self.mexican = Train::new(MEXICAN_TRAIN, true, anchor);
+        self.boneyard = boneyard;
+
+        self.round_points = vec![0; names.len()];
+        self.current = usize::from(self.round) % names.len();
+        self.drawn_tile = None;
+        self.open_double = None;
+        self.idle_turns = 0;
+        self.round_over = false;
+        self.round_winner = None;
+        Ok(())
+    }
+
+    /// The active rules.
+    #[must_use]
+    pub fn rules(&self) -> &MexicanTrainRules {
+        &self.rules
+    }
+
+    /// Number of players in the game.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Seat index of the player whose turn it is.
+    #[must_use]
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Zero-based number of the current round.
+    #[must_use]
+    pub fn round(&self) -> u8 {
+        self.round
+    }
+
+    /// The number every train starts from this round.
+    #[must_use]
+    pub fn anchor(&self) -> u8 {
+        self.rules.max_pips - self.round
+    }
+
+    /// The engine double in the centre of the table.
+    #[must_use]
+    pub fn engine(&self) -> Domino {
+        self.engine
+    }
+
+    /// The hand held by the player at `seat`.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::PlayerNotFound`] if `seat` is out of range.
+    pub fn hand(&self, seat: usize) -> GameResult<&DominoHand> {
+        self.hands
+            .get(seat)
+            .ok_or_else(|| MexicanTrainError::PlayerNotFound(seat).into())
+    }
+
+    /// All hands, in seat order.
+    #[must_use]
+    pub fn hands(&self) -> &[DominoHand] {
+        &self.hands
+    }
+
+    /// The train identified by `id`.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::PlayerNotFound`] for a personal train at a missing seat.
+    pub fn train(&self, id: TrainId) -> GameResult<&Train> {
+        match id {
+            TrainId::Mexican => Ok(&self.mexican),
+            TrainId::Personal(seat) => self
+                .trains
+                .get(seat)
+                .ok_or_else(|| MexicanTrainError::PlayerNotFound(seat).into()),
+        }
+    }
+
+    /// The train whose double must be covered before any other play, if any.
+    #[must_use]
+    pub fn open_double(&self) -> Option<TrainId> {
+        self.open_double
+    }
+
+    /// Number of tiles left in the boneyard.
+    #[must_use]
+    pub fn boneyard_size(&self) -> usize {
+        self.boneyard.remaining()
+    }
+
+    /// Running scores for each seat. Lower is better.
+    #[must_use]
+    pub fn scores(&self) -> &[u32] {
+        &self.scores
+    }
+
+    /// Points each seat was charged at the end of the most recent round.
+    #[must_use]
+    pub fn round_points(&self) -> &[u32] {
+        &self.round_points
+    }
+
+    /// Returns `true` once the current round has been won or blocked.
+    #[must_use]
+    pub fn is_round_over(&self) -> bool {
+        self.round_over
+    }
+
+    /// Seat of the player who went out this round, if the round ended that way.
+    #[must_use]
+    pub fn round_winner(&self) -> Option<usize> {
+        self.round_winner
+    }
+
+    /// Returns `true` once the 0-0 round has ended.
+    #[must_use]
+    pub fn is_game_over(&self) -> bool {
+        self.round_over && self.anchor() == 0
+    }
+
+    /// Seat of the player with the lowest total once the game is over.
+    #[must_use]
+    pub fn game_winner(&self) -> Option<usize> {
+        if !self.is_game_over() {
+            return None;
+        }
+        self.scores
+            .iter()
+            .enumerate()
+            .min_by_key(|(seat, score)| (**score, *seat))
+            .map(|(seat, _)| seat)
+    }
+
+    /// The trains the current player may play on right now.
+    #[must_use]
+    pub fn playable_trains(&self) -> Vec<TrainId> {
+        if let Some(double) = self.open_double {
+            return vec![double];
+        }
+        let mut trains = vec![TrainId::Personal(self.current), TrainId::Mexican];
+        trains.extend(
+            self.trains
+                .iter()
+                .enumerate()
+                .filter(|(seat, train)| *seat != self.current && train.is_open())
+                .map(|(seat, _)| TrainId::Personal(seat)),
+        );
+        trains
+    }
+
+    /// Every tile and train combination the current player may legally play now.
+    #[must_use]
+    pub fn legal_plays(&self) -> Vec<TrainPlay> {
+        if self.round_over {
+            return Vec::new();
+        }
+        let trains = self.playable_trains();
+        let mut plays = Vec::new();
+        for tile in &self.hands[self.current].tiles {
+            if self.drawn_tile.is_some_and(|id| id != tile.id) {
+                continue;
+            }
+            for &train in &trains {
+                let tail = self.train(train).map_or(u8::MAX, Train::tail);
+                if tile.left == tail || tile.right == tail {
+                    plays.push(TrainPlay {
+                        tile_id: tile.id,
+                        train,
+                    });
+                }
+            }
+        }
+        plays
+    }
+
+    /// Play the tile with `tile_id` from the current player's hand onto `train`.
+    ///
+    /// Playing on your own train removes its marker. Playing a double keeps the turn
+    /// with the same player, who must cover it.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::RoundOver`] if the round has ended.
+    /// - [`MexicanTrainError::OnlyDrawnTilePlayable`] after drawing, for any other tile.
+    /// - [`MexicanTrainError::MustCoverDouble`] while a double on another train is uncovered.
+    /// - [`MexicanTrainError::PlayerNotFound`] for a personal train at a missing seat.
+    /// - [`DominoError::TileNotFound`] if the tile is not in the current hand.
+    /// - [`DominoError::TrainClosed`] for another player's unmarked train.
+    /// - [`DominoError::TileUnconnected`] if the tile does not match the train's tail.
+    pub fn play(&mut self, tile_id: usize, train: TrainId) -> GameResult<TurnOutcome> {
+        self.ensure_round_active()?;
+        if self.drawn_tile.is_some_and(|id| id != tile_id) {
+            return Err(MexicanTrainError::OnlyDrawnTilePlayable.into());
+        }
+        if let Some(double) = self.open_double
+            && double != train
+        {
+            return Err(MexicanTrainError::MustCoverDouble(self.train_label(double)).into());
+        }
+        let seat = self.current;
+        let pos = self.hands[seat]
+            .tiles
+            .iter()
+            .position(|t| t.id == tile_id)
+            .ok_or(DominoError::TileNotFound(tile_id))?;
+        let tile = self.hands[seat].tiles[pos];
+        let player = self.hands[seat].player.clone();
+        let target = match train {
+            TrainId::Mexican => &mut self.mexican,
+            TrainId::Personal(owner) => self
+                .trains
+                .get_mut(owner)
+                .ok_or(MexicanTrainError::PlayerNotFound(owner))?,
+        };
+        target.play(tile, &player)?;
+        if train == TrainId::Personal(seat) {
+            target.close();
+        }
+        self.hands[seat].tiles.remove(pos);
+        self.drawn_tile = None;
+        self.idle_turns = 0;
+
+        if self.hands[seat].tiles.is_empty() {
+            self.end_round(Some(seat));
+            return Ok(TurnOutcome::RoundWon { player: seat });
+        }
+        if tile.is_double() {
+            self.open_double = Some(train);
+            return Ok(TurnOutcome::CoverDouble);
+        }
+        self.open_double = None;
+        self.advance();
+        Ok(TurnOutcome::Continue)
+    }
+
+    /// Draw one tile for the current player, who may only draw when nothing in their
+    /// hand can be played.
+    ///
+    /// If the tile can be played the turn stays with the player, who must then
+    /// [`play`](Self::play) it or [`pass`](Self::pass). Otherwise, or if the boneyard is
+    /// empty, the player's train is marked and the turn passes.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::AlreadyDrew`] if the player already drew this turn.
+    /// - [`MexicanTrainError::MustPlay`] if the player holds a playable tile.
+    /// - [`MexicanTrainError::RoundOver`] if the round has ended.
+    pub fn draw(&mut self) -> GameResult<DrawOutcome> {
+        self.ensure_round_active()?;
+        if self.drawn_tile.is_some() {
+            return Err(MexicanTrainError::AlreadyDrew.into());
+        }
+        if !self.legal_plays().is_empty() {
+            return Err(MexicanTrainError::MustPlay.into());
+        }
+        let Some(tile) = self.boneyard.draw_tile() else {
+            return Ok(match self.fail_turn() {
+                TurnOutcome::RoundBlocked => DrawOutcome::RoundBlocked,
+                _ => DrawOutcome::BoneyardEmpty,
+            });
+        };
+        self.hands[self.current].tiles.push(tile);
+        self.drawn_tile = Some(tile.id);
+        let playable = !self.legal_plays().is_empty();
+        if !playable {
+            self.fail_turn();
+        }
+        Ok(DrawOutcome::Drew { tile, playable })
+    }
+
+    /// Keep a playable tile that was just drawn, mark the player's train, and end the turn.
+    ///
+    /// # Errors
+    /// - [`MexicanTrainError::MustDrawFirst`] unless the current player has just drawn a
+    ///   playable tile.
+    /// - [`MexicanTrainError::RoundOver`] if the round has ended.
+    pub fn pass(&mut self) -> GameResult<TurnOutcome> {
+        self.ensure_round_active()?;
+        if self.drawn_tile.is_none() {
+            return Err(MexicanTrainError::MustDrawFirst.into());
+        }
+        Ok(self.fail_turn())
+    }
+
+    fn ensure_round_active(&self) -> GameResult<()> {
+        if self.round_over {
+            return Err(MexicanTrainError::RoundOver.into());
+        }
+        Ok(())
+    }
+
+    fn train_label(&self, id: TrainId) -> String {
+        match id {
+            TrainId::Mexican => "the Mexican train".to_string(),
+            TrainId::Personal(seat) => format!("{}'s train", self.hands[seat].player),
+        }
+    }
+
+    /// End a turn without a play: mark the train and check for a blocked round.
+    fn fail_turn(&mut self) -> TurnOutcome {
+        self.trains[self.current].open();
+        self.drawn_tile = None;
+        if self.boneyard.is_empty() {
+            self.idle_turns += 1;
+            if self.idle_turns >= self.hands.len() {
+                self.end_round(None);
+                return TurnOutcome::RoundBlocked;
+            }
+        } else {
+            self.idle_turns = 0;
+        }
+        self.advance();
+        TurnOutcome::Continue
+    }
+
+    fn advance(&mut self) {
+        self.current = (self.current + 1) % self.hands.len();
+    }
+
+    fn end_round(&mut self, winner: Option<usize>) {
+        let zero_worth = self.rules.double_zero_worth;
+        self.round_points = self
+            .hands
+            .iter()
+            .map(|hand| {
+                hand.tiles
+                    .iter()
+                    .map(|t| u32::from(t.points_with_zero_worth(zero_worth)))
+                    .sum()
+            })
+            .collect();
+        for (score, points) in self.scores.iter_mut().zip(&self.round_points) {
+            *score += points;
+        }
+        self.round_over = true;
+        self.round_winner = winner;
+    }
+}
+
+impl fmt::Display for MexicanTrainGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "engine {}", self.engine)?;
+        for train in &self.trains {
+            writeln!(f, "{train}")?;
+        }
+        write!(f, "{}", self.mexican)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameError;
+    use crate::test_support::{dominos, seat_name, seat_names};
+
+    /// A two-player game with known hands and boneyard, anchored on 12.
+    fn rigged(p0: &[(u8, u8)], p1: &[(u8, u8)], boneyard: &[(u8, u8)]) -> MexicanTrainGame {
+        let names = seat_names(2);
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut game = MexicanTrainGame::new(&names, MexicanTrainRules::default()).unwrap();
+        game.hands[0].tiles = dominos(p0, 100);
+        game.hands[1].tiles = dominos(p1, 200);
+        game.boneyard.tiles = dominos(boneyard, 300);
+        game
+    }
+
+    fn id_of(game: &MexicanTrainGame, seat: usize, pips: (u8, u8)) -> usize {
+        game.hands[seat]
+            .tiles
+            .iter()
+            .find(|t| (t.left, t.right) == pips)
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn deal_respects_player_count_and_hand_sizes() {
+        let game =
+            MexicanTrainGame::new(&["a", "b", "c", "d"], MexicanTrainRules::default()).unwrap();
+        assert!(game.hands().iter().all(|h| h.tiles().len() == 15));
+        assert_eq!(game.boneyard_size(), 91 - 1 - 60);
+        assert_eq!(game.engine().as_tuple().0, 12);
+        assert!(game.engine().is_double());
+        assert!(
+            game.hands()
+                .iter()
+                .flat_map(|h| h.tiles())
+                .all(|t| t.id() != game.engine().id())
+        );
+
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let eight = MexicanTrainGame::new(&names, MexicanTrainRules::default()).unwrap();
+        assert!(eight.hands().iter().all(|h| h.tiles().len() == 10));
+
+        assert!(MexicanTrainGame::new(&["solo"], MexicanTrainRules::default()).is_err());
+        assert_eq!(
+            MexicanTrainGame::new(&["a", "b", "a"], MexicanTrainRules::default()).unwrap_err(),
+            GameError::MexicanTrainError(MexicanTrainError::DuplicatePlayer("a".into()))
+        );
+        assert!(MexicanTrainGame::new(&names[..], MexicanTrainRules::default()).is_ok());
+        let huge = MexicanTrainRules {
+            hand_size: Some(30),
+            ..MexicanTrainRules::default()
+        };
+        assert_eq!(
+            MexicanTrainGame::new(&["a", "b", "c", "d"], huge).unwrap_err(),
+            GameError::MexicanTrainError(MexicanTrainError::HandSizeTooLarge {
+                needed: 120,
+                available: 90
+            })
+        );
+    }
+
+    #[test]
+    fn markers_open_and_close_personal_trains() {
+        let mut game = rigged(&[(12, 3), (3, 4), (7, 7)], &[(12, 5), (1, 2)], &[(0, 1)]);
+        let p1_tile = id_of(&game, 1, (12, 5));
+
+        // p0 plays on their own train, p1 cannot play on it while it is unmarked
+        game.play(id_of(&game, 0, (12, 3)), TrainId::Personal(0))
+            .unwrap();
+        assert_eq!(
+            game.play(p1_tile, TrainId::Personal(0)),
+            Err(DominoError::TrainClosed.into())
+        );
+        game.play(p1_tile, TrainId::Personal(1)).unwrap();
+
+        // p0 draws a tile they cannot use: their train is marked and the turn passes
+        game.hands[0].tiles.retain(|t| t.left == 7);
+        let outcome = game.draw().unwrap();
+        assert!(matches!(
+            outcome,
+            DrawOutcome::Drew {
+                playable: false,
+                ..
+            }
+        ));
+        assert!(game.train(TrainId::Personal(0)).unwrap().is_open());
+        assert_eq!(game.current_player(), 1);
+        assert!(game.playable_trains().contains(&TrainId::Personal(0)));
+    }
+
+    #[test]
+    fn doubles_must_be_covered_first() {
+        let mut game = rigged(
+            &[(12, 6), (6, 6), (1, 2)],
+            &[(12, 9), (6, 9), (3, 3)],
+            &[(0, 0)],
+        );
+        game.play(id_of(&game, 0, (12, 6)), TrainId::Mexican)
+            .unwrap();
+        game.play(id_of(&game, 1, (12, 9)), TrainId::Personal(1))
+            .unwrap();
+
+        let outcome = game
+            .play(id_of(&game, 0, (6, 6)), TrainId::Mexican)
+            .unwrap();
+        assert_eq!(outcome, TurnOutcome::CoverDouble);
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.open_double(), Some(TrainId::Mexican));
+
+        // p0 cannot cover, draws an unplayable tile, and p1 inherits the double
+        assert!(game.legal_plays().is_empty());
+        game.draw().unwrap();
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(
+            game.play(id_of(&game, 1, (3, 3)), TrainId::Personal(1)),
+            Err(MexicanTrainError::MustCoverDouble("the Mexican train".into()).into())
+        );
+        let cover = id_of(&game, 1, (6, 9));
+        assert_eq!(
+            game.legal_plays(),
+            [TrainPlay {
+                tile_id: cover,
+                train: TrainId::Mexican
+            }]
+        );
+        assert_eq!(
+            game.play(cover, TrainId::Mexican).unwrap(),
+            TurnOutcome::Continue
+        );
+        assert_eq!(game.open_double(), None);
+    }
+
+    #[test]
+    fn drawing_and_passing_follow_the_turn_rules() {
+        let mut game = rigged(&[(12, 1)], &[], &[]);
+        assert_eq!(game.draw(), Err(MexicanTrainError::MustPlay.into()));
+
+        let mut game = rigged(&[(1, 2), (3, 4)], &[(5, 6)], &[(0, 0), (12, 8)]);
+        assert_eq!(game.pass(), Err(MexicanTrainError::MustDrawFirst.into()));
+
+        let DrawOutcome::Drew { tile, playable } = game.draw().unwrap() else {
+            panic!("the boneyard is not empty");
+        };
+        assert!(playable);
+        assert_eq!((tile.left(), tile.right()), (12, 8));
+        assert_eq!(game.draw(), Err(MexicanTrainError::AlreadyDrew.into()));
+        assert_eq!(
+            game.play(id_of(&game, 0, (1, 2)), TrainId::Mexican),
+            Err(MexicanTrainError::OnlyDrawnTilePlayable.into())
+        );
+        assert_eq!(game.pass().unwrap(), TurnOutcome::Continue);
+        assert!(game.train(TrainId::Personal(0)).unwrap().is_open());
+        assert!(game.hands[0].tiles.contains(&tile));
+
+        // p1 draws the 0-0, which plays nowhere, so their turn passes at once
+        let outcome = game.draw().unwrap();
+        assert!(matches!(
+            outcome,
+            DrawOutcome::Drew {
+                playable: false,
+                ..
+            }
+        ));
+        assert_eq!(game.current_player(), 0);
+        assert!(game.train(TrainId::Personal(1)).unwrap().is_open());
+    }
+
+    #[test]
+    fn going_out_scores_the_round_and_anchors_count_down() {
+        let mut game = rigged(&[(12, 1)], &[(0, 0), (5, 6)], &[]);
+        assert_eq!(
+            game.next_round(),
+            Err(MexicanTrainError::RoundInProgress.into())
+        );
+        let outcome = game
+            .play(id_of(&game, 0, (12, 1)), TrainId::Personal(0))
+            .unwrap();
+        assert_eq!(outcome, TurnOutcome::RoundWon { player: 0 });
+        assert_eq!(game.round_points(), [0, 61]);
+        assert_eq!(game.scores(), [0, 61]);
+        assert_eq!(game.draw(), Err(MexicanTrainError::RoundOver.into()));
+        assert_eq!(game.game_winner(), None);
+
+        game.next_round().unwrap();
+        assert_eq!(game.anchor(), 11);
+        assert_eq!(game.current_player(), 1);
+        assert!(!game.is_round_over());
+        assert_eq!(game.train(TrainId::Mexican).unwrap().tail(), 11);
+    }
+
+    #[test]
+    fn empty_boneyard_blocks_the_round_and_final_round_ends_the_game() {
+        let mut game = rigged(&[(1, 2)], &[(3, 4), (5, 5)], &[]);
+        game.round = 12;
+        assert_eq!(game.anchor(), 0);
+        game.mexican = Train::new(MEXICAN_TRAIN, true, 0);
+        game.trains = vec![
+            Train::new(&seat_name(0), false, 0),
+            Train::new(&seat_name(1), false, 0),
+        ];
+
+        assert_eq!(game.draw().unwrap(), DrawOutcome::BoneyardEmpty);
+        assert_eq!(game.draw().unwrap(), DrawOutcome::RoundBlocked);
+        assert!(game.is_round_over());
+        assert_eq!(game.round_winner(), None);
+        assert_eq!(game.scores(), [3, 17]);
+        assert!(game.is_game_over());
+        assert_eq!(game.game_winner(), Some(0));
+        assert_eq!(game.next_round(), Err(MexicanTrainError::GameOver.into()));
+    }
+}
@@ -0,0 +1,480 @@
+//! # Multi-Train Turn Planning
+//!
+//! In Mexican Train a turn is more than one tile: a double must be covered by another
+//! play on the same train, and many house rules let a player add to their own train
+//! and then to the public train or an opponent's open train in the same turn.
+//! [`DominoHand::plan_turns`] enumerates every legal turn a hand can make across a set of
+//! [`Train`]s, respecting [`Train::is_open`] and ownership, and
+//! [`DominoHand::recommend_turn`] picks one according to a [`TurnStrategy`].
+//!
+//! Strategies are pluggable: use one of the built-in ones ([`ProtectLongestLine`],
+//! [`BlockOpponent`], [`DumpPips`]) or any closure taking a [`TurnView`].
+//!
+//! ```
+//! use gametools::{BonePile, DominoHand, GameResult, Train};
+//! use gametools::dominos::turn_planner::{ProtectLongestLine, TurnRules};
+//! # fn main() -> GameResult<()> {
+//!
+//! let mut trains = vec![
+//!     Train::new("ann", false, 12),
+//!     Train::new("bob", false, 12),
+//!     Train::new("Mexican", true, 12),
+//! ];
+//! let mut pile = BonePile::new(12);
+//! let mut hand = DominoHand::new_with_draw("ann", 15, &mut pile)?;
+//!
+//! let turns = hand.plan_turns(&trains, TurnRules::default());
+//! if let Some(turn) = hand.recommend_turn(&trains, TurnRules::default(), &ProtectLongestLine) {
+//!     assert!(turns.contains(&turn));
+//!     turn.apply(&mut hand, &mut trains)?;
+//! }
+//! // bob's train is closed, so nothing was played on it
+//! assert!(trains[1].tiles().is_empty());
+//! # Ok(())
+//! # }
+//! ```
+use crate::dominos::longest_line::longest_line;
+use crate::dominos::{Domino, DominoHand, Train};
+use crate::{DominoError, GameResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which multi-tile turns are allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnRules {
+    /// After playing on their own train, a player may also play on one other train.
+    pub two_train_turns: bool,
+    /// A double must be followed by a tile covering it on the same train, if the hand
+    /// holds one.
+    pub doubles_play_again: bool,
+}
+
+impl Default for TurnRules {
+    fn default() -> Self {
+        Self {
+            two_train_turns: true,
+            doubles_play_again: true,
+        }
+    }
+}
+
+/// One tile played on one train, identified by its index in the planned train slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnStep {
+    pub tile_id: usize,
+    pub train: usize,
+}
+
+/// A complete legal turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnPlan {
+    /// Tiles in play order.
+    pub steps: Vec<TurnStep>,
+    /// Index of the train left ending in a double the hand could not cover, if any.
+    pub uncovered_double: Option<usize>,
+}
+
+impl TurnPlan {
+    /// Play this turn from `hand` onto `trains` (the same slice it was planned against).
+    ///
+    /// The whole turn is validated before anything changes: on error, neither the hand
+    /// nor the trains are modified.
+    ///
+    /// # Errors
+    /// - if a step names a train index outside `trains`
+    /// - if a tile is missing from the hand, doesn't connect, or its train is closed
+    pub fn apply(&self, hand: &mut DominoHand, trains: &mut [Train]) -> GameResult<()> {
+        let mut new_hand = hand.clone();
+        let mut new_trains = trains.to_vec();
+        for step in &self.steps {
+            let train = new_trains
+                .get_mut(step.train)
+                .ok_or(DominoError::NoSuchTrain(step.train))?;
+            new_hand.play_line(&[step.tile_id], train)?;
+        }
+        *hand = new_hand;
+        trains.clone_from_slice(&new_trains);
+        Ok(())
+    }
+}
+
+/// What a strategy gets to see when scoring a candidate turn.
+#[derive(Debug, Clone, Copy)]
+pub struct TurnView<'a> {
+    /// The player taking the turn.
+    pub player: &'a str,
+    /// The trains as they were before the turn.
+    pub trains: &'a [Train],
+    /// Train index and tile (oriented to the train) for each step, in play order.
+    pub played: &'a [(usize, Domino)],
+    /// Tiles left in hand after the turn.
+    pub kept: &'a [Domino],
+    /// Each train's tail after the turn.
+    pub tails: &'a [u8],
+}
+
+impl TurnView<'_> {
+    /// Index of the player's own train, if it is among the planned trains.
+    #[must_use]
+    pub fn own_train(&self) -> Option<usize> {
+        self.trains.iter().position(|t| t.player() == self.player)
+    }
+
+    /// Total pips played this turn.
+    #[must_use]
+    pub fn pips_played(&self) -> i64 {
+        self.played
+            .iter()
+            .map(|(_, tile)| i64::from(tile.points()))
+            .sum()
+    }
+}
+
+/// Scores a candidate turn. Higher scores are preferred.
+pub trait TurnStrategy {
+    fn score(&self, view: &TurnView) -> i64;
+}
+
+impl<F> TurnStrategy for F
+where
+    F: Fn(&TurnView) -> i64,
+{
+    fn score(&self, view: &TurnView) -> i64 {
+        self(view)
+    }
+}
+
+/// Keep the longest line off your own train intact: prefer turns that leave the most
+/// tiles playable in a single line from your train's tail, counting tiles played this
+/// turn, then turns that shed the most pips.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProtectLongestLine;
+
+impl TurnStrategy for ProtectLongestLine {
+    fn score(&self, view: &TurnView) -> i64 {
+        let line = view
+            .own_train()
+            .map_or(0, |own| longest_line(view.kept, view.tails[own]).len());
+        (view.played.len() + line) as i64 * 1000 + view.pips_played()
+    }
+}
+
+/// Play on `opponent`'s train when it is open, leaving its tail on a number you hold
+/// the most of (and so the opponent is least likely to hold). Otherwise play as many
+/// tiles as possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockOpponent {
+    pub opponent: String,
+}
+
+impl TurnStrategy for BlockOpponent {
+    fn score(&self, view: &TurnView) -> i64 {
+        let target = view.trains.iter().position(|t| t.player() == self.opponent);
+        let tiles = view.played.len() as i64;
+        match target {
+            Some(target) if view.played.iter().any(|&(train, _)| train == target) => {
+                let tail = view.tails[target];
+                let held = view
+                    .kept
+                    .iter()
+                    .filter(|t| t.left == tail || t.right == tail)
+                    .count() as i64;
+                1000 + held * 10 + tiles
+            }
+            _ => tiles,
+        }
+    }
+}
+
+/// Shed as many pips as possible this turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DumpPips;
+
+impl TurnStrategy for DumpPips {
+    fn score(&self, view: &TurnView) -> i64 {
+        view.pips_played()
+    }
+}
+
+impl DominoHand {
+    /// Every legal turn this hand can take on `trains` under `rules`.
+    ///
+    /// The hand may play on a train it owns or on any open train. A turn is one tile on
+    /// one train, extended by covering tiles after each double when
+    /// `rules.doubles_play_again` is set. With `rules.two_train_turns`, a turn that starts
+    /// on the player's own train may continue with the same kind of play on one other
+    /// train. Turns that stop early are listed too; an empty list means the hand must
+    /// draw or pass.
+    #[must_use]
+    pub fn plan_turns(&self, trains: &[Train], rules: TurnRules) -> Vec<TurnPlan> {
+        let mut search = TurnSearch::new(self, trains, rules);
+        search.start_segment(false);
+        search.plans.into_iter().map(|(plan, _)| plan).collect()
+    }
+
+    /// The legal turn `strategy` scores highest, or `None` if no tile can be played.
+    /// Ties go to the turn playing more tiles, then to the turn listed first by
+    /// [`plan_turns`](Self::plan_turns).
+    #[must_use]
+    pub fn recommend_turn(
+        &self,
+        trains: &[Train],
+        rules: TurnRules,
+        strategy: &dyn TurnStrategy,
+    ) -> Option<TurnPlan> {
+        let mut search = TurnSearch::new(self, trains, rules);
+        search.strategy = Some(strategy);
+        search.start_segment(false);
+        let mut best: Option<(TurnPlan, (i64, usize))> = None;
+        for (plan, key) in search.plans {
+            if best.as_ref().is_none_or(|(_, best_key)| key > *best_key) {
+                best = Some((plan, key));
+            }
+        }
+        best.map(|(plan, _)| plan)
+    }
+}
+
+struct TurnSearch<'a> {
+    player: &'a str,
+    trains: &'a [Train],
+    rules: TurnRules,
+    strategy: Option<&'a dyn TurnStrategy>,
+    kept: Vec<Domino>,
+    tails: Vec<u8>,
+    played: Vec<(usize, Domino)>,
+    /// Candidates keyed by (score, tiles played) when a strategy is scoring them.
+    plans: Vec<(TurnPlan, (i64, usize))>,
+}
+
+impl<'a> TurnSearch<'a> {
+    fn new(hand: &'a DominoHand, trains: &'a [Train], rules: TurnRules) -> Self {
+        Self {
+            player: &hand.player,
+            trains,
+            rules,
+            strategy: None,
+            kept: hand.tiles.clone(),
+            tails: trains.iter().map(|t| t.tail).collect(),
+            played: Vec::new(),
+            plans: Vec::new(),
+        }
+    }
+
+    /// Try every tile on every train a new segment of the turn may start on.
+    fn start_segment(&mut self, second: bool) {
+        for train in 0..self.trains.len() {
+            let own = self.trains[train].player() == self.player;
+            // a second segment follows one on the player's own train
+            if second && own {
+                continue;
+            }
+            if own || self.trains[train].is_open() {
+                self.play_each(train, second);
+            }
+        }
+    }
+
+    fn play_each(&mut self, train: usize, second: bool) {
+        for idx in 0..self.kept.len() {
+            let tile = self.kept[idx];
+            let tail = self.tails[train];
+            let oriented = match tile {
+                _ if tile.left == tail => tile,
+                _ if tile.right == tail => tile.flipped(),
+                _ => continue,
+            };
+            self.kept.remove(idx);
+            self.played.push((train, oriented));
+            self.tails[train] = oriented.right;
+            self.after_play(train, oriented, second);
+            self.tails[train] = tail;
+            self.played.pop();
+            self.kept.insert(idx, tile);
+        }
+    }
+
+    fn after_play(&mut self, train: usize, tile: Domino, second: bool) {
+        if tile.is_double() && self.rules.doubles_play_again {
+            let tail = self.tails[train];
+            if self.kept.iter().any(|t| t.left == tail || t.right == tail) {
+                self.play_each(train, second);
+            } else {
+                self.record(Some(train));
+            }
+            return;
+        }
+        self.record(None);
+        let started_own = self.trains[self.played[0].0].player() == self.player;
+        if !second && started_own && self.rules.two_train_turns {
+            self.start_segment(true);
+        }
+    }
+
+    fn record(&mut self, uncovered_double: Option<usize>) {
+        let plan = TurnPlan {
+            steps: self
+                .played
+                .iter()
+                .map(|&(train, tile)| TurnStep {
+                    tile_id: tile.id,
+                    train,
+                })
+                .collect(),
+            uncovered_double,
+        };
+        let score = self.strategy.map_or(0, |strategy| {
+            strategy.score(&TurnView {
+                player: self.player,
+                trains: self.trains,
+                played: &self.played,
+                kept: &self.kept,
+                tails: &self.tails,
+            })
+        });
+        self.plans.push((plan, (score, self.played.len())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domino_hand;
+
+    fn trains() -> Vec<Train> {
+        vec![
+            Train::new("ann", false, 6),
+            Train::new("bob", false, 6),
+            Train::new("Mexican", true, 6),
+        ]
+    }
+
+    fn steps(plan: &TurnPlan) -> Vec<(usize, usize)> {
+        plan.steps.iter().map(|s| (s.tile_id, s.train)).collect()
+    }
+
+    #[test]
+    fn closed_trains_and_two_train_turns() {
+        let hand = domino_hand("ann", &[(6, 1), (6, 2)], 0);
+        let turns = hand.plan_turns(&trains(), TurnRules::default());
+        let listed: Vec<_> = turns.iter().map(steps).collect();
+        // bob's train is closed; the second play must go on another train
+        assert_eq!(
+            listed,
+            [
+                vec![(0, 0)],
+                vec![(0, 0), (1, 2)],
+                vec![(1, 0)],
+                vec![(1, 0), (0, 2)],
+                vec![(0, 2)],
+                vec![(1, 2)],
+            ]
+        );
+        let single = TurnRules {
+            two_train_turns: false,
+            ..TurnRules::default()
+        };
+        assert_eq!(hand.plan_turns(&trains(), single).len(), 4);
+    }
+
+    #[test]
+    fn doubles_must_be_covered() {
+        let hand = domino_hand("ann", &[(6, 6), (6, 3), (4, 4)], 0);
+        let mut trains = trains();
+        trains[1].open();
+        let turns = hand.plan_turns(&trains[..2], TurnRules::default());
+        // a double alone is only a turn when it can't be covered
+        assert!(
+            turns
+                .iter()
+                .all(|t| t.steps.len() != 1 || t.steps[0].tile_id != 0)
+        );
+        assert!(turns.iter().any(|t| steps(t) == [(0, 0), (1, 0)]));
+        // bob's train can take the double but nothing left covers it
+        assert!(
+            turns
+                .iter()
+                .any(|t| steps(t) == [(1, 0), (0, 1)] && t.uncovered_double == Some(1))
+        );
+
+        let hand = domino_hand("ann", &[(6, 6), (1, 2)], 0);
+        let turns = hand.plan_turns(&trains[..1], TurnRules::default());
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].uncovered_double, Some(0));
+    }
+
+    #[test]
+    fn protecting_the_longest_line() {
+        // 6-1-2-3 is ann's line; 6:5 is spare and should go on the Mexican train
+        let hand = domino_hand("ann", &[(6, 1), (1, 2), (2, 3), (6, 5)], 0);
+        let single = TurnRules {
+            two_train_turns: false,
+            ..TurnRules::default()
+        };
+        let turn = hand
+            .recommend_turn(&trains(), single, &ProtectLongestLine)
+            .unwrap();
+        assert_eq!(steps(&turn), [(3, 2)]);
+        let turn = hand
+            .recommend_turn(&trains(), TurnRules::default(), &ProtectLongestLine)
+            .unwrap();
+        assert_eq!(steps(&turn), [(0, 0), (3, 2)]);
+    }
+
+    #[test]
+    fn blocking_an_opponent() {
+        let hand = domino_hand("ann", &[(6, 1), (6, 4), (4, 2), (4, 3)], 0);
+        let mut trains = trains();
+        let blocker = BlockOpponent {
+            opponent: "bob".to_string(),
+        };
+        let closed = hand
+            .recommend_turn(&trains, TurnRules::default(), &blocker)
+            .unwrap();
+        assert!(closed.steps.iter().all(|s| s.train != 1));
+        trains[1].open();
+        let open = hand
+            .recommend_turn(&trains, TurnRules::default(), &blocker)
+            .unwrap();
+        // leave bob facing a 4, which ann holds two more of
+        assert!(open.steps.contains(&TurnStep {
+            tile_id: 1,
+            train: 1
+        }));
+    }
+
+    #[test]
+    fn applying_plans() {
+        let mut hand = domino_hand("ann", &[(6, 1), (6, 9), (8, 8)], 0);
+        let mut trains = trains();
+        let turn = hand
+            .recommend_turn(&trains, TurnRules::default(), &DumpPips)
+            .unwrap();
+        assert_eq!(steps(&turn), [(0, 0), (1, 2)]);
+        turn.apply(&mut hand, &mut trains).unwrap();
+        assert_eq!(hand.tiles().len(), 1);
+        assert_eq!((trains[0].tail(), trains[2].tail()), (1, 9));
+
+        let bad = TurnPlan {
+            steps: vec![TurnStep {
+                tile_id: 2,
+                train: 7,
+            }],
+            uncovered_double: None,
+        };
+        assert_eq!(
+            bad.apply(&mut hand, &mut trains),
+            Err(DominoError::NoSuchTrain(7).into())
+        );
+        let none = domino_hand("ann", &[(2, 3)], 0);
+        assert!(none.plan_turns(&trains, TurnRules::default()).is_empty());
+        assert!(
+            none.recommend_turn(&trains, TurnRules::default(), &DumpPips)
+                .is_none()
+        );
+    }
+}
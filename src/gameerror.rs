@@ -5,7 +5,9 @@
 //! more than one subsystem.
 //!
 
+use crate::dominos::Direction;
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Aggregate error type for APIs that may surface failures from multiple modules.
 #[derive(Debug, Error, PartialEq)]
@@ -20,6 +22,24 @@ pub enum GameError {
     RefillingPoolError(#[from] RefillingPoolError),
     #[error("spinner error: {0}")]
     SpinnerError(#[from] SpinnerError),
+    #[error("uno error: {0}")]
+    UnoError(#[from] UnoError),
+    #[error("shedding game error: {0}")]
+    SheddingError(#[from] SheddingError),
+    #[error("solitaire error: {0}")]
+    SolitaireError(#[from] SolitaireError),
+    #[error("deck list error: {0}")]
+    DeckListError(#[from] DeckListError),
+    #[error("deckbuilding error: {0}")]
+    DeckbuildingError(#[from] DeckbuildingError),
+    #[error("shuffle error: {0}")]
+    ShuffleError(#[from] ShuffleError),
+    #[error("mexican train error: {0}")]
+    MexicanTrainError(#[from] MexicanTrainError),
+    #[error("inference error: {0}")]
+    InferenceError(#[from] InferenceError),
+    #[error("chickenfoot error: {0}")]
+    ChickenfootError(#[from] ChickenfootError),
     #[error("value error: {0}")]
     ValueError(#[from] ValueError),
 }
@@ -33,6 +53,80 @@ pub enum CardError {
     StackTooSmall(String),
     #[error("the card sought was not found in this collection")]
     CardNotFound,
+    #[error("'{0}' is not a recognized card, rank, or suit")]
+    InvalidCardText(String),
+    #[error("no card at position {0}")]
+    InvalidIndex(usize),
+    #[error("a new order must list every card position exactly once")]
+    InvalidArrangement,
+}
+
+/// Errors specific to running a game of Uno with [`crate::cards::uno_game::UnoGame`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum UnoError {
+    #[error("uno needs 2-10 players, {0} requested")]
+    InvalidPlayerCount(usize),
+    #[error("the deck holds {available} cards but the deal needs {needed}")]
+    DeckTooSmall { needed: usize, available: usize },
+    #[error("the deck recipe contains Flip cards, which this engine can't play")]
+    FlipUnsupported,
+    #[error("no player at seat {0}")]
+    PlayerNotFound(usize),
+    #[error("no card at index {0} in the current hand")]
+    CardNotFound(usize),
+    #[error("that card cannot be played on the current discard")]
+    IllegalPlay,
+    #[error("a color must be declared when playing a wild")]
+    ColorNotDeclared,
+    #[error("a seven requires choosing a player to swap hands with")]
+    SwapTargetRequired,
+    #[error("a {0} card draw penalty must be stacked or taken")]
+    PenaltyPending(usize),
+    #[error("only the card just drawn may be played this turn")]
+    OnlyDrawnCardPlayable,
+    #[error("the current player has already drawn this turn")]
+    AlreadyDrew,
+    #[error("a player may only pass after drawing")]
+    MustDrawFirst,
+    #[error("house rule '{0}' is not enabled")]
+    HouseRuleDisabled(String),
+    #[error("the round is over")]
+    RoundOver,
+    #[error("the round is still in progress")]
+    RoundInProgress,
+    #[error("the game is over")]
+    GameOver,
+}
+
+/// Errors specific to shedding games run with [`crate::cards::shedding_game::SheddingGame`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum SheddingError {
+    #[error("a shedding game needs 2-10 players, {0} requested")]
+    InvalidPlayerCount(usize),
+    #[error("the deck holds {available} cards but the deal needs {needed}")]
+    DeckTooSmall { needed: usize, available: usize },
+    #[error("no player at seat {0}")]
+    PlayerNotFound(usize),
+    #[error("no card at index {0} in the current hand")]
+    CardNotFound(usize),
+    #[error("that card cannot be played on the current discard")]
+    IllegalPlay,
+    #[error("a suit must be declared when playing a wild rank")]
+    SuitNotDeclared,
+    #[error("a {0} card draw penalty must be stacked or taken")]
+    PenaltyPending(usize),
+    #[error("only a card drawn this turn may be played")]
+    OnlyDrawnCardPlayable,
+    #[error("the current player may not draw again this turn")]
+    DrawLimitReached,
+    #[error("a player may only pass after drawing")]
+    MustDrawFirst,
+    #[error("the round is over")]
+    RoundOver,
+    #[error("the round is still in progress")]
+    RoundInProgress,
+    #[error("the game is over")]
+    GameOver,
 }
 
 /// Errors specific to domino hands, trains, and bone piles.
@@ -46,6 +140,179 @@ pub enum DominoError {
     TileNotFound(usize),
     #[error("attempted to play on a closed train")]
     TrainClosed,
+    #[error("the layout has no open end facing {0}")]
+    EndNotOpen(Direction),
+    #[error("no train at index {0}")]
+    NoSuchTrain(usize),
+    #[error("tile with id '{0}' appears more than once")]
+    DuplicateTile(usize),
+    #[error("the tile at position {0} does not connect to the train")]
+    TrainBroken(usize),
+    #[error("train has {tiles} tiles but {records} play records")]
+    HistoryMismatch { tiles: usize, records: usize },
+}
+
+/// Errors specific to solitaire layouts run with [`crate::cards::solitaire::Solitaire`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum SolitaireError {
+    #[error("the deck holds {available} cards but the deal needs {needed}")]
+    DeckTooSmall { needed: usize, available: usize },
+    #[error("no such zone: {0}")]
+    ZoneNotFound(String),
+    #[error("not enough face-up cards in the source to move {0}")]
+    NotEnoughCards(usize),
+    #[error("that move breaks the stacking rules")]
+    IllegalMove,
+    #[error("cannot move {requested} cards at once, the limit is {limit}")]
+    SequenceTooLong { requested: usize, limit: usize },
+    #[error("there are no cards left to draw")]
+    StockEmpty,
+    #[error("the stock cannot be dealt while a column is empty")]
+    EmptyColumn,
+    #[error("there is no move to undo")]
+    NothingToUndo,
+    #[error("the solver does not support {0}")]
+    UnsupportedRules(String),
+    #[error("spider is played with 1, 2, or 4 suits, not {0}")]
+    InvalidSuitCount(usize),
+}
+
+/// Errors specific to loading and validating [`crate::cards::custom_deck::DeckList`]s.
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum DeckListError {
+    #[error("could not parse deck list: {0}")]
+    Parse(String),
+    #[error("card entry #{0} has no name")]
+    EmptyName(usize),
+    #[error("'{0}' has a quantity of zero")]
+    ZeroQuantity(String),
+    #[error("'{0}' is listed more than once")]
+    DuplicateCard(String),
+    #[error("'{card}' has {copies} copies but the limit is {limit}")]
+    TooManyCopies {
+        card: String,
+        copies: usize,
+        limit: usize,
+    },
+    #[error("the deck has {count} cards but needs at least {min}")]
+    TooFewCards { count: usize, min: usize },
+    #[error("the deck has {count} cards but allows at most {max}")]
+    TooManyCards { count: usize, max: usize },
+    #[error("template '{0}' has an unclosed or empty placeholder")]
+    BadTemplate(String),
+    #[error("match and compare keys cannot be empty")]
+    EmptyKey,
+}
+
+/// Errors specific to deckbuilding markets and supply stacks in [`crate::cards::deckbuilding`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum DeckbuildingError {
+    #[error("the card costs {cost} but only {available} is available")]
+    CannotAfford { cost: u32, available: u32 },
+    #[error("market row slot {0} is empty or does not exist")]
+    EmptySlot(usize),
+    #[error("no supply stack is named '{0}'")]
+    NoSuchStack(String),
+    #[error("the '{0}' supply stack is empty")]
+    StackEmpty(String),
+}
+
+/// Errors specific to commit-reveal shuffles in `cards::fair_shuffle`, which needs the
+/// `fair-shuffle` feature.
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum ShuffleError {
+    #[error("a shuffle needs at least one participant")]
+    NoParticipants,
+    #[error("'{0}' is listed more than once")]
+    DuplicateParticipant(String),
+    #[error("'{0}' is not taking part in this shuffle")]
+    UnknownParticipant(String),
+    #[error("'{0}' has already committed")]
+    AlreadyCommitted(String),
+    #[error("'{0}' has already revealed their seed")]
+    AlreadyRevealed(String),
+    #[error("still waiting for commitments from: {}", .0.join(", "))]
+    AwaitingCommitments(Vec<String>),
+    #[error("still waiting for seeds from: {}", .0.join(", "))]
+    AwaitingReveals(Vec<String>),
+    #[error("the seed revealed by '{0}' does not match their commitment")]
+    CommitmentMismatch(String),
+    #[error("the deck holds {actual} cards but the shuffle covered {expected}")]
+    DeckMismatch { expected: usize, actual: usize },
+    #[error("the deck is not in the order the shuffle session started with")]
+    OrderMismatch,
+    #[error("card {0} was not part of the shuffled deck")]
+    UnknownCard(Uuid),
+    #[error("card {uuid} should have been dealt at position {expected}, not {claimed}")]
+    WrongDealPosition {
+        uuid: Uuid,
+        expected: usize,
+        claimed: usize,
+    },
+}
+
+/// Errors specific to running a game of Mexican Train with
+/// [`crate::dominos::mexican_train::MexicanTrainGame`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum MexicanTrainError {
+    #[error("mexican train needs 2-8 players, {0} requested")]
+    InvalidPlayerCount(usize),
+    #[error("'{0}' is seated more than once")]
+    DuplicatePlayer(String),
+    #[error("dealing {needed} tiles needs more than the {available} in the set")]
+    HandSizeTooLarge { needed: usize, available: usize },
+    #[error("no player at seat {0}")]
+    PlayerNotFound(usize),
+    #[error("the double on {0} must be covered first")]
+    MustCoverDouble(String),
+    #[error("only the tile just drawn may be played this turn")]
+    OnlyDrawnTilePlayable,
+    #[error("the current player has already drawn this turn")]
+    AlreadyDrew,
+    #[error("a player holding a playable tile must play rather than draw")]
+    MustPlay,
+    #[error("a player may only pass after drawing")]
+    MustDrawFirst,
+    #[error("the round is over")]
+    RoundOver,
+    #[error("the round is still in progress")]
+    RoundInProgress,
+    #[error("the game is over")]
+    GameOver,
+}
+
+/// Errors specific to tracking hidden domino hands with
+/// [`crate::dominos::inference::TileInference`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum InferenceError {
+    #[error("no opponent named {0}")]
+    UnknownPlayer(String),
+    #[error("tile with id '{0}' is not in the set")]
+    UnknownTile(usize),
+    #[error("tile with id '{0}' has already been seen")]
+    TileAlreadySeen(usize),
+    #[error("{player} cannot hold tile '{tile}' given their earlier passes")]
+    ImpossiblePlay { player: String, tile: usize },
+    #[error("{hidden} unseen tiles but hands and boneyard account for {accounted}")]
+    CountMismatch { hidden: usize, accounted: usize },
+    #[error("no deal of the unseen tiles fits every opponent's passes")]
+    NoConsistentWorld,
+}
+
+/// Errors specific to branching trains built with
+/// [`crate::dominos::chickenfoot::BranchingTrain`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum ChickenfootError {
+    #[error("a branching train must start from a double")]
+    RootNotDouble,
+    #[error("doubles must be covered at least one way")]
+    NoCoverWays,
+    #[error("no tile at position {0} in the train")]
+    NoSuchTile(usize),
+    #[error("the tile at position {0} has no open end left")]
+    EndClosed(usize),
+    #[error("the {number}s double needs {remaining} more covering tiles first")]
+    MustCoverDouble { number: u8, remaining: usize },
 }
 
 /// Errors specific to spinners.
@@ -53,6 +320,8 @@ pub enum DominoError {
 pub enum SpinnerError {
     #[error("spin() returned None: empty spinner or landed on covered wedge")]
     SpinnerEmpty,
+    #[error("spin strength must be finite and non-negative, and friction positive")]
+    InvalidPhysics,
 }
 
 /// Errors specific to [`crate::RefillingPool`].
@@ -87,8 +356,11 @@ pub enum ValueError {
 #[cfg(test)]
 mod tests {
     use super::{
-        CardError, DiceError, DominoError, GameError, RefillingPoolError, SpinnerError, ValueError,
+        CardError, ChickenfootError, DeckListError, DeckbuildingError, DiceError, DominoError,
+        GameError, InferenceError, MexicanTrainError, RefillingPoolError, SheddingError,
+        ShuffleError, SolitaireError, SpinnerError, UnoError, ValueError,
     };
+    use crate::dominos::Direction;
     use std::error::Error;
 
     #[test]
@@ -106,6 +378,14 @@ mod tests {
                 CardError::CardNotFound.into(),
                 "card error: the card sought was not found in this collection",
             ),
+            (
+                CardError::InvalidCardText("ZZ".to_string()).into(),
+                "card error: 'ZZ' is not a recognized card, rank, or suit",
+            ),
+            (
+                CardError::InvalidIndex(7).into(),
+                "card error: no card at position 7",
+            ),
             (
                 DominoError::InsufficientTiles.into(),
                 "domino error: insufficient tiles left in the bone pile",
@@ -118,10 +398,127 @@ mod tests {
                 DominoError::TrainClosed.into(),
                 "domino error: attempted to play on a closed train",
             ),
+            (
+                DominoError::EndNotOpen(Direction::North).into(),
+                "domino error: the layout has no open end facing north",
+            ),
+            (
+                DominoError::NoSuchTrain(3).into(),
+                "domino error: no train at index 3",
+            ),
+            (
+                DominoError::DuplicateTile(4).into(),
+                "domino error: tile with id '4' appears more than once",
+            ),
+            (
+                DominoError::TrainBroken(2).into(),
+                "domino error: the tile at position 2 does not connect to the train",
+            ),
             (
                 SpinnerError::SpinnerEmpty.into(),
                 "spinner error: spin() returned None: empty spinner or landed on covered wedge",
             ),
+            (
+                SpinnerError::InvalidPhysics.into(),
+                "spinner error: spin strength must be finite and non-negative, and friction positive",
+            ),
+            (
+                SheddingError::DeckTooSmall {
+                    needed: 36,
+                    available: 32,
+                }
+                .into(),
+                "shedding game error: the deck holds 32 cards but the deal needs 36",
+            ),
+            (
+                SolitaireError::InvalidSuitCount(3).into(),
+                "solitaire error: spider is played with 1, 2, or 4 suits, not 3",
+            ),
+            (
+                UnoError::DeckTooSmall {
+                    needed: 15,
+                    available: 10,
+                }
+                .into(),
+                "uno error: the deck holds 10 cards but the deal needs 15",
+            ),
+            (
+                UnoError::FlipUnsupported.into(),
+                "uno error: the deck recipe contains Flip cards, which this engine can't play",
+            ),
+            (
+                UnoError::PenaltyPending(4).into(),
+                "uno error: a 4 card draw penalty must be stacked or taken",
+            ),
+            (
+                SheddingError::SuitNotDeclared.into(),
+                "shedding game error: a suit must be declared when playing a wild rank",
+            ),
+            (
+                SolitaireError::SequenceTooLong {
+                    requested: 6,
+                    limit: 4,
+                }
+                .into(),
+                "solitaire error: cannot move 6 cards at once, the limit is 4",
+            ),
+            (
+                DeckListError::TooManyCopies {
+                    card: "Goblin".to_string(),
+                    copies: 5,
+                    limit: 4,
+                }
+                .into(),
+                "deck list error: 'Goblin' has 5 copies but the limit is 4",
+            ),
+            (
+                DeckbuildingError::CannotAfford {
+                    cost: 6,
+                    available: 4,
+                }
+                .into(),
+                "deckbuilding error: the card costs 6 but only 4 is available",
+            ),
+            (
+                ShuffleError::OrderMismatch.into(),
+                "shuffle error: the deck is not in the order the shuffle session started with",
+            ),
+            (
+                ShuffleError::CommitmentMismatch("bob".into()).into(),
+                "shuffle error: the seed revealed by 'bob' does not match their commitment",
+            ),
+            (
+                ShuffleError::AwaitingReveals(vec!["ann".into(), "cy".into()]).into(),
+                "shuffle error: still waiting for seeds from: ann, cy",
+            ),
+            (
+                MexicanTrainError::MustCoverDouble("the Mexican train".into()).into(),
+                "mexican train error: the double on the Mexican train must be covered first",
+            ),
+            (
+                MexicanTrainError::DuplicatePlayer("ann".into()).into(),
+                "mexican train error: 'ann' is seated more than once",
+            ),
+            (
+                MexicanTrainError::MustPlay.into(),
+                "mexican train error: a player holding a playable tile must play rather than draw",
+            ),
+            (
+                InferenceError::CountMismatch {
+                    hidden: 10,
+                    accounted: 9,
+                }
+                .into(),
+                "inference error: 10 unseen tiles but hands and boneyard account for 9",
+            ),
+            (
+                ChickenfootError::MustCoverDouble {
+                    number: 6,
+                    remaining: 2,
+                }
+                .into(),
+                "chickenfoot error: the 6s double needs 2 more covering tiles first",
+            ),
             (
                 RefillingPoolError::PoolCannotBeEmpty.into(),
                 "refilling pool error: refilling pool must have items with which to refill",
@@ -5,15 +5,18 @@
 //! games and simulations.
 //!
 //! ## Features
-//! - `cards`: generic card faces plus deck, hand, and pile abstractions, with standard 52-card and Uno helpers.
+//! - `art`: multi-line terminal renderings of cards, hands, and dominos with optional ANSI color.
+//! - `cards`: generic card faces plus deck, hand, and pile abstractions, with standard 52-card, Uno, and tarot helpers and commit-reveal verifiable shuffles.
 //! - `dice`: `Die` and `Rolls` support for regular and exploding dice along with common roll-analysis helpers.
 //! - `ordering`: stable ranked lists (`RankedOrder`) and heap-backed queues (`PriorityQueue`) for turn order and scheduling.
 //! - `metered_resource`: bounded unsigned counters with saturating increase and reduction helpers.
 //! - `refilling_pool`: infinitely reusable random pools with conditional and contextual draw helpers.
-//! - `spinners`: decision wheels with weighted, coverable wedges that can hold arbitrary values.
-//! - `dominos`: domino set creation, train management, and longest-train solving.
+//! - `spinners`: decision wheels with weighted, coverable wedges that can hold arbitrary values, with landing angles and deterministic spin physics.
+//! - `dominos`: domino set creation, train management, longest-train solving, two-ended layouts with spinners, Chickenfoot branching trains, and a Mexican Train game engine.
 //! - Module-specific error enums plus `GameError` / `GameResult` for aggregate error handling across the crate.
 
+pub mod art;
+
 pub mod cards;
 pub use cards::{
     AddCard, Card, CardCollection, CardFaces, CardHand, Deck, Hand, Pile, Rank, Suit, TakeCard,
@@ -23,7 +26,7 @@ pub mod dice;
 pub use dice::{Die, DieResult, Rolls};
 
 pub mod dominos;
-pub use dominos::{BonePile, Domino, DominoHand, MAX_PIPS, Train};
+pub use dominos::{BonePile, BonePileStats, Domino, DominoHand, MAX_PIPS, PlayRecord, Train};
 
 pub mod metered_resource;
 pub use metered_resource::MeteredResource;
@@ -36,7 +39,9 @@ pub use spinners::{Spinner, Wedge, wedges_from_tuples, wedges_from_values};
 
 pub mod gameerror;
 pub use gameerror::{
-    CardError, DiceError, DominoError, GameError, RefillingPoolError, SpinnerError, ValueError,
+    CardError, ChickenfootError, DeckListError, DeckbuildingError, DiceError, DominoError,
+    GameError, InferenceError, MexicanTrainError, RefillingPoolError, SheddingError, ShuffleError,
+    SolitaireError, SpinnerError, UnoError, ValueError,
 };
 pub type GameResult<T> = Result<T, GameError>;
 
@@ -45,3 +50,6 @@ pub use ordering::{
     AscendingOrder, DescendingOrder, Max, MaxPriorityQ, Min, MinPriorityQ, PriorityQueue,
     RankedOrder,
 };
+
+#[cfg(test)]
+mod test_support;
@@ -75,6 +75,8 @@
 use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
 
+use crate::{GameResult, SpinnerError};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -276,6 +278,185 @@ impl<T: Clone> Spinner<T> {
     }
 }
 
+/// Degrees in a full turn of the spinner.
+pub const FULL_TURN: f64 = 360.0;
+
+/// Where a spin came to rest, for drawing the spinner and settling the result together.
+///
+/// Angles are in degrees, clockwise from where the first wedge starts, in `0.0..360.0`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Landing<T>
+where
+    T: Clone,
+{
+    /// The resting angle under the pointer, in degrees clockwise from the start of the
+    /// first wedge.
+    pub angle: f64,
+    /// Index of the wedge landed on.
+    pub index: usize,
+    /// The wedge's value, or `None` if it is covered.
+    pub value: Option<T>,
+    /// Degrees from the landing angle to the nearer edge of the wedge.
+    pub border_distance: f64,
+}
+
+impl<T: Clone> Landing<T> {
+    /// Whether the spin stopped within `tolerance` degrees of a wedge border, for games
+    /// that re-spin or nudge close calls.
+    #[must_use]
+    pub fn is_near_border(&self, tolerance: f64) -> bool {
+        self.border_distance <= tolerance
+    }
+}
+
+/// A deterministic spin: the spinner starts turning at `strength` degrees per second and
+/// slows by `friction` degrees per second squared until it stops.
+///
+/// The same start angle, strength and friction always give the same landing, and
+/// [`angle_at`](Self::angle_at) traces the path there, so an animation and the game
+/// result cannot disagree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpinPhysics {
+    strength: f64,
+    friction: f64,
+}
+
+impl SpinPhysics {
+    /// Create spin physics from an initial speed in degrees per second and a deceleration
+    /// in degrees per second squared.
+    ///
+    /// # Errors
+    /// - if `strength` is negative or not finite, or `friction` is not a positive finite number
+    pub fn new(strength: f64, friction: f64) -> GameResult<Self> {
+        if !(strength.is_finite() && strength >= 0.0 && friction.is_finite() && friction > 0.0) {
+            return Err(SpinnerError::InvalidPhysics.into());
+        }
+        Ok(Self { strength, friction })
+    }
+
+    /// Initial speed in degrees per second.
+    #[must_use]
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+
+    /// Deceleration in degrees per second squared.
+    #[must_use]
+    pub fn friction(&self) -> f64 {
+        self.friction
+    }
+
+    /// Seconds until the spinner stops.
+    #[must_use]
+    pub fn duration(&self) -> f64 {
+        self.strength / self.friction
+    }
+
+    /// Total degrees turned before stopping (may be several full turns).
+    #[must_use]
+    pub fn rotation(&self) -> f64 {
+        self.strength * self.strength / (2.0 * self.friction)
+    }
+
+    /// The spinner's angle `seconds` after starting from `start`. Times past
+    /// [`duration`](Self::duration) give the final angle.
+    #[must_use]
+    pub fn angle_at(&self, start: f64, seconds: f64) -> f64 {
+        let t = seconds.clamp(0.0, self.duration());
+        normalize_angle(start + self.strength * t - 0.5 * self.friction * t * t)
+    }
+
+    /// The angle the spinner stops at when started from `start`.
+    #[must_use]
+    pub fn final_angle(&self, start: f64) -> f64 {
+        normalize_angle(start + self.rotation())
+    }
+}
+
+fn normalize_angle(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(FULL_TURN);
+    // rem_euclid can round tiny negative angles up to a full turn
+    if angle >= FULL_TURN { 0.0 } else { angle }
+}
+
+impl<T: Clone> Spinner<T> {
+    /// The angles (start, end) in degrees that a wedge covers, in proportion to its
+    /// width. Wedges run clockwise from 0 in order. Returns `None` for an out of range
+    /// index or a spinner whose wedges have no width at all.
+    ///
+    /// ```
+    /// use gametools::spinners::{Spinner, Wedge};
+    /// let spinner = Spinner::new(vec![Wedge::new_weighted("A", 1), Wedge::new_weighted("B", 3)]);
+    /// assert_eq!(spinner.wedge_span(1), Some((90.0, 360.0)));
+    /// ```
+    #[must_use]
+    pub fn wedge_span(&self, index: usize) -> Option<(f64, f64)> {
+        let total: usize = self.weights.iter().sum();
+        if index >= self.wedges.len() || total == 0 {
+            return None;
+        }
+        let before: usize = self.weights[..index].iter().sum();
+        let degrees = |width: usize| FULL_TURN * width as f64 / total as f64;
+        Some((degrees(before), degrees(before + self.weights[index])))
+    }
+
+    /// Index of the wedge under `angle` (in degrees, any value; it is wrapped to one turn).
+    /// An angle exactly on a border belongs to the wedge that starts there.
+    #[must_use]
+    pub fn wedge_at(&self, angle: f64) -> Option<usize> {
+        let angle = normalize_angle(angle);
+        (0..self.wedges.len()).find(|&i| {
+            self.wedge_span(i)
+                .is_some_and(|(start, end)| start <= angle && angle < end)
+        })
+    }
+
+    /// Where the spinner rests with `angle` under the pointer.
+    #[must_use]
+    pub fn land_at(&self, angle: f64) -> Option<Landing<T>> {
+        let angle = normalize_angle(angle);
+        let index = self.wedge_at(angle)?;
+        let (start, end) = self.wedge_span(index)?;
+        let wedge = &self.wedges[index];
+        Some(Landing {
+            angle,
+            index,
+            value: wedge.active.then(|| wedge.value.clone()),
+            border_distance: (angle - start).min(end - angle),
+        })
+    }
+
+    /// Spin to a uniformly random angle. Wedges are landed on in proportion to their
+    /// widths, as with [`spin`](Self::spin), but the landing angle is reported too.
+    #[must_use]
+    pub fn spin_landing(&self) -> Option<Landing<T>> {
+        self.land_at(rand::rng().random_range(0.0..FULL_TURN))
+    }
+
+    /// Spin from `start` with the given physics; the same inputs always land the same way.
+    ///
+    /// ```
+    /// use gametools::spinners::{SpinPhysics, Spinner, wedges_from_values};
+    /// # fn main() -> gametools::GameResult<()> {
+    /// let spinner = Spinner::new(wedges_from_values(vec![1, 2, 3, 4]));
+    /// // 360 deg/s slowing at 120 deg/s^2 turns 540 degrees in 3 seconds
+    /// let physics = SpinPhysics::new(360.0, 120.0)?;
+    /// let landing = spinner.spin_with_physics(10.0, &physics).unwrap();
+    /// assert_eq!(landing.angle, 190.0);
+    /// assert_eq!(landing.value, Some(3));
+    /// assert_eq!(physics.angle_at(10.0, physics.duration()), landing.angle);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn spin_with_physics(&self, start: f64, physics: &SpinPhysics) -> Option<Landing<T>> {
+        self.land_at(physics.final_angle(start))
+    }
+}
+
 impl<T: Clone + PartialEq> Spinner<T> {
     /// Returns a new spinner with a target value covered (blocked).
     /// Returns a clone of the original spinner if there is no wedge matching the target value.
@@ -591,6 +772,88 @@ mod spinner_tests {
         assert_eq!(values, vec![1, 2]);
     }
 
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn wedge_spans_follow_widths() {
+        let spinner = Spinner::new(wedges_from_tuples(vec![("A", 1), ("B", 0), ("C", 2)]));
+        assert_eq!(spinner.wedge_span(0), Some((0.0, 120.0)));
+        assert_eq!(spinner.wedge_span(1), Some((120.0, 120.0)));
+        assert_eq!(spinner.wedge_span(2), Some((120.0, 360.0)));
+        assert_eq!(spinner.wedge_span(3), None);
+        // borders belong to the wedge starting there; zero-width wedges are never hit
+        assert_eq!(spinner.wedge_at(120.0), Some(2));
+        assert_eq!(spinner.wedge_at(-10.0), Some(2));
+        assert_eq!(spinner.wedge_at(725.0), Some(0));
+        let empty: Spinner<u8> = Spinner::new(vec![Wedge::new_weighted(1, 0)]);
+        assert!(empty.wedge_span(0).is_none());
+        assert!(empty.land_at(10.0).is_none());
+    }
+
+    #[test]
+    fn landing_reports_value_and_border_distance() {
+        let spinner = Spinner::new(wedges_from_values(vec!["N", "E", "S", "W"])).cover(&"S");
+        let landing = spinner.land_at(95.0).unwrap();
+        assert_eq!((landing.index, landing.value), (1, Some("E")));
+        assert!(close(landing.border_distance, 5.0));
+        assert!(landing.is_near_border(5.0));
+        assert!(!landing.is_near_border(4.9));
+        let covered = spinner.land_at(225.0).unwrap();
+        assert_eq!((covered.index, covered.value), (2, None));
+        assert!(close(covered.border_distance, 45.0));
+    }
+
+    #[test]
+    fn physics_is_deterministic_and_matches_animation() {
+        let physics = SpinPhysics::new(600.0, 100.0).unwrap();
+        assert!(close(physics.duration(), 6.0));
+        assert!(close(physics.rotation(), 1800.0));
+        assert!(close(physics.final_angle(30.0), 30.0));
+        assert!(close(physics.angle_at(30.0, 1.0), 220.0));
+        assert!(close(
+            physics.angle_at(30.0, 100.0),
+            physics.final_angle(30.0)
+        ));
+
+        let spinner = Spinner::new(wedges_from_values((1..=8).collect()));
+        let first = spinner.spin_with_physics(30.0, &physics).unwrap();
+        assert_eq!(first, spinner.spin_with_physics(30.0, &physics).unwrap());
+        assert_eq!(first.value, Some(1));
+        let still = SpinPhysics::new(0.0, 1.0).unwrap();
+        assert_eq!(
+            spinner.spin_with_physics(100.0, &still).unwrap().value,
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn invalid_physics_is_rejected() {
+        for (strength, friction) in [
+            (-1.0, 1.0),
+            (1.0, 0.0),
+            (f64::NAN, 1.0),
+            (1.0, f64::INFINITY),
+        ] {
+            assert_eq!(
+                SpinPhysics::new(strength, friction),
+                Err(SpinnerError::InvalidPhysics.into())
+            );
+        }
+    }
+
+    #[test]
+    fn random_landings_stay_inside_their_wedge() {
+        let spinner = Spinner::new(wedges_from_tuples(vec![("A", 1), ("B", 5)]));
+        for _ in 0..200 {
+            let landing = spinner.spin_landing().unwrap();
+            let (start, end) = spinner.wedge_span(landing.index).unwrap();
+            assert!(start <= landing.angle && landing.angle < end);
+            assert!(landing.border_distance >= 0.0);
+        }
+    }
+
     #[test]
     fn can_use_iterator_over_spinner_wedges() {
         let spinner = Spinner::new(vec![Wedge::new(1), Wedge::new(2)]);
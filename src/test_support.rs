@@ -0,0 +1,46 @@
+//! Fixture builders shared by the game-engine tests, so every rigged table names its
+//! seats and stacks its piles the same way.
+use crate::cards::{AddCard, Card, CardFaces, Hand, Pile};
+use crate::dominos::{BonePile, Domino, DominoHand};
+
+/// The name given to the player at `seat` in rigged games: `p0`, `p1`, ...
+pub(crate) fn seat_name(seat: usize) -> String {
+    format!("p{seat}")
+}
+
+/// Seat names for a rigged game of `players`.
+pub(crate) fn seat_names(players: usize) -> Vec<String> {
+    (0..players).map(seat_name).collect()
+}
+
+/// A hand for `seat` holding fresh cards with the given faces.
+pub(crate) fn hand_of<T: CardFaces>(seat: usize, faces: Vec<T>) -> Hand<T> {
+    let mut hand = Hand::new(&seat_name(seat));
+    hand.add_cards(faces.into_iter().map(Card::new_card).collect());
+    hand
+}
+
+/// A pile named `label` holding `cards`, last card on top.
+pub(crate) fn pile_of<T: CardFaces>(label: &str, cards: Vec<Card<T>>) -> Pile<T> {
+    let mut pile = Pile::new_pile(label);
+    pile.add_cards(cards);
+    pile
+}
+
+/// Dominos with the given pips, numbered from `first_id` upward.
+pub(crate) fn dominos(pairs: &[(u8, u8)], first_id: usize) -> Vec<Domino> {
+    pairs
+        .iter()
+        .zip(first_id..)
+        .map(|(&(left, right), id)| Domino::new(left, right, id))
+        .collect()
+}
+
+/// A domino hand for `player` holding tiles with the given pips, in order, numbered from
+/// `first_id` upward.
+pub(crate) fn domino_hand(player: &str, pairs: &[(u8, u8)], first_id: usize) -> DominoHand {
+    let mut pile = BonePile::from_tiles(&[]);
+    pile.return_tiles(dominos(pairs, first_id))
+        .expect("fixture ids are unique");
+    DominoHand::new_with_draw(player, pairs.len(), &mut pile).expect("pile holds every tile")
+}